@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mc_chat::{Chat, ClickEvent, HoverEvent, TextColor, TranslationComponent, VERSION_1_16};
+
+/// A chat line like a server sends it: a translation with styled arguments.
+fn message() -> Chat {
+    Chat::component(
+        TranslationComponent::new("chat.type.text")
+            .argument(
+                Chat::text("Steve")
+                    .click(Some(ClickEvent::suggest("/msg Steve ")))
+                    .hover(Some(HoverEvent::ShowText(Box::new(
+                        Chat::text("Steve").color(TextColor::Gold),
+                    )))),
+            )
+            .argument(Chat::text("Hello ").child(Chat::text("world").bold(true))),
+    )
+}
+
+/// A component with many children, like a long legacy formatted text.
+fn wide(width: usize) -> Chat {
+    (0..width).fold(Chat::text(""), |chat, index| {
+        chat.child(Chat::text(index.to_string()).color(TextColor::from_rgb(0xff, 0x80, 0x00)))
+    })
+}
+
+/// A component nested as deep as components get in practice.
+fn deep(depth: usize) -> Chat {
+    (0..depth).fold(Chat::text("leaf"), |chat, index| {
+        Chat::text(index.to_string()).italic(Some(true)).child(chat)
+    })
+}
+
+fn serialize(c: &mut Criterion) {
+    let message = message();
+    let wide = wide(256);
+    let deep = deep(64);
+    c.bench_function("serialize message", |b| {
+        b.iter(|| black_box(&message).serialize_str(VERSION_1_16).unwrap())
+    });
+    c.bench_function("serialize wide", |b| {
+        b.iter(|| black_box(&wide).serialize_str(VERSION_1_16).unwrap())
+    });
+    c.bench_function("serialize deep", |b| {
+        b.iter(|| black_box(&deep).serialize_str(VERSION_1_16).unwrap())
+    });
+}
+
+criterion_group!(benches, serialize);
+criterion_main!(benches);
@@ -0,0 +1,167 @@
+//! The `book!` macro, building a written book from chat literals.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{bracketed, parse::Parse, punctuated::Punctuated, Error, Ident, LitStr, Token};
+
+use crate::parsing::{map_to_tree, Argument, ChatPart, Content, ExpandedChatPart, LegacyChat};
+
+/// Mirrors `mc_chat::Book::MAX_PAGES`.
+const MAX_PAGES: usize = 100;
+/// Mirrors `mc_chat::Book::MAX_PAGE_LENGTH`.
+const MAX_PAGE_LENGTH: usize = 256;
+/// Mirrors `mc_chat::Book::MAX_TITLE_LENGTH`.
+const MAX_TITLE_LENGTH: usize = 32;
+
+pub struct BookInput {
+    pub pattern: LitStr,
+    pub title: LitStr,
+    pub author: LitStr,
+    /// Every page with the span of its brackets.
+    pub pages: Vec<(Span, LegacyChat)>,
+}
+
+impl Parse for BookInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let pattern: LitStr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let title = parse_field(input, "title")?;
+        let author = parse_field(input, "author")?;
+        let mut pages = Vec::new();
+        while !input.is_empty() {
+            let content;
+            let brackets = bracketed!(content in input);
+            let page = LegacyChat {
+                pattern: pattern.clone(),
+                translate: None,
+                chat_parts: Punctuated::<ChatPart, Token![,]>::parse_terminated(&content)?,
+            };
+            pages.push((brackets.span.join(), page));
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(BookInput {
+            pattern,
+            title,
+            author,
+            pages,
+        })
+    }
+}
+
+/// Parses `name: "value"` and the comma after it.
+fn parse_field(input: syn::parse::ParseStream, name: &str) -> syn::Result<LitStr> {
+    let ident: Ident = input.parse()?;
+    if ident != name {
+        return Err(Error::new(
+            ident.span(),
+            format!("Expected `{}: \"...\"`", name),
+        ));
+    }
+    input.parse::<Token![:]>()?;
+    let value = input.parse()?;
+    if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+    }
+    Ok(value)
+}
+
+/// Counts the characters of all literal text, values only known at
+/// runtime don't count towards the limit.
+fn text_length(part: &ExpandedChatPart) -> usize {
+    let own = match part.content {
+        Some(Content::Text(ref text)) => text.chars().count(),
+        Some(Content::Translation { ref arguments, .. }) => arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Literal(part) => text_length(part),
+                Argument::Chat(_) => 0,
+            })
+            .sum(),
+        _ => 0,
+    };
+    own + part.children.iter().map(text_length).sum::<usize>()
+}
+
+pub fn expand_book(book: BookInput) -> syn::Result<TokenStream> {
+    let title_length = book.title.value().chars().count();
+    if title_length > MAX_TITLE_LENGTH {
+        return Err(Error::new(
+            book.title.span(),
+            format!(
+                "The title is {} characters long, the limit is {}",
+                title_length, MAX_TITLE_LENGTH
+            ),
+        ));
+    }
+    if book.pages.len() > MAX_PAGES {
+        return Err(Error::new(
+            book.pattern.span(),
+            format!(
+                "The book has {} pages, the limit is {}",
+                book.pages.len(),
+                MAX_PAGES
+            ),
+        ));
+    }
+
+    let title = &book.title;
+    let author = &book.author;
+    let mut tokens = quote!(::mc_chat::Book::new(#title, #author));
+    for (index, (span, page)) in book.pages.into_iter().enumerate() {
+        let page = map_to_tree(page)?;
+        let length = text_length(&page);
+        if length > MAX_PAGE_LENGTH {
+            return Err(Error::new(
+                span,
+                format!(
+                    "Page {} has {} characters, the limit is {}",
+                    index + 1,
+                    length,
+                    MAX_PAGE_LENGTH
+                ),
+            ));
+        }
+        tokens = quote!(#tokens.page(#page));
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let book: BookInput = parse_quote!("§", title: "Adventures", author: "Steve",
+            ["§lChapter 1", name], ["The end"],);
+        assert_eq!("Adventures", book.title.value());
+        assert_eq!(2, book.pages.len());
+        assert_eq!(2, book.pages[0].1.chat_parts.len());
+    }
+
+    #[test]
+    fn missing_author() {
+        assert!(syn::parse2::<BookInput>(quote!("§", title: "Adventures", ["Hi"])).is_err());
+    }
+
+    #[test]
+    fn page_length() {
+        let long = "a".repeat(MAX_PAGE_LENGTH);
+        let book: BookInput =
+            parse_quote!("§", title: "Long", author: "Steve", ["§l", #long, name]);
+        assert!(expand_book(book).is_ok());
+        let book: BookInput = parse_quote!("§", title: "Long", author: "Steve", [#long, "!"]);
+        assert!(expand_book(book).is_err());
+    }
+
+    #[test]
+    fn title_length() {
+        let title = "a".repeat(MAX_TITLE_LENGTH + 1);
+        let book: BookInput = parse_quote!("§", title: #title, author: "Steve");
+        assert!(expand_book(book).is_err());
+    }
+}
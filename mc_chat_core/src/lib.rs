@@ -1,25 +1,111 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::{abort, set_dummy};
 use quote::quote;
+use syn::{DeriveInput, LitStr};
 
-use crate::parsing::{map_to_tree, LegacyChat};
+use crate::book::{expand_book, BookInput};
+use crate::parsing::map_to_tree;
+use crate::serialize::{to_json, StaticChat};
+use crate::to_chat::derive_to_chat;
+use crate::version::{check_target, check_version_expr, version_from_expr, TargetedChat};
 
+mod book;
 mod parsing;
+mod serialize;
+mod to_chat;
+mod version;
 
 pub fn chat_core(input: TokenStream) -> TokenStream {
     set_dummy(quote!(unimplemented!(
         "Compile time error in chat!() macro"
     )));
 
-    let legacy_chat: LegacyChat = match syn::parse2(input) {
+    let targeted_chat: TargetedChat = match syn::parse2(input) {
         Ok(parts) => parts,
         Err(error) => abort!(error.span(), error.to_string()),
     };
 
-    let root = match map_to_tree(legacy_chat) {
+    let mut version_check = TokenStream::new();
+    if let Some(ref target) = targeted_chat.target {
+        let version = match version_from_expr(target) {
+            Ok(version) => version,
+            Err(error) => abort!(error.span(), error.to_string()),
+        };
+        if let Err(error) = check_target(&targeted_chat.legacy_chat, version) {
+            abort!(error.span(), error.to_string());
+        }
+        version_check = check_version_expr(target, version);
+    }
+
+    let root = match map_to_tree(targeted_chat.legacy_chat) {
         Ok(root) => root,
         Err(error) => abort!(error.span(), error.to_string()),
     };
 
-    quote!(#root)
+    if version_check.is_empty() {
+        quote!(#root)
+    } else {
+        quote!({
+            #version_check
+            #root
+        })
+    }
+}
+
+pub fn chat_static_core(input: TokenStream) -> TokenStream {
+    set_dummy(quote!(""));
+
+    let static_chat: StaticChat = match syn::parse2(input) {
+        Ok(parts) => parts,
+        Err(error) => abort!(error.span(), error.to_string()),
+    };
+
+    let version = match version_from_expr(&static_chat.version) {
+        Ok(version) => version,
+        Err(error) => abort!(error.span(), error.to_string()),
+    };
+
+    let json = match map_to_tree(static_chat.legacy_chat).and_then(|root| to_json(&root, version)) {
+        Ok(json) => json,
+        Err(error) => abort!(error.span(), error.to_string()),
+    };
+
+    let literal = LitStr::new(&json, Span::call_site());
+    let version_check = check_version_expr(&static_chat.version, version);
+    if version_check.is_empty() {
+        quote!(#literal)
+    } else {
+        quote!({
+            #version_check
+            #literal
+        })
+    }
+}
+
+pub fn book_core(input: TokenStream) -> TokenStream {
+    set_dummy(quote!(unimplemented!(
+        "Compile time error in book!() macro"
+    )));
+
+    let book: BookInput = match syn::parse2(input) {
+        Ok(book) => book,
+        Err(error) => abort!(error.span(), error.to_string()),
+    };
+
+    match expand_book(book) {
+        Ok(tokens) => tokens,
+        Err(error) => abort!(error.span(), error.to_string()),
+    }
+}
+
+pub fn derive_to_chat_core(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = match syn::parse2(input) {
+        Ok(input) => input,
+        Err(error) => abort!(error.span(), error.to_string()),
+    };
+
+    match derive_to_chat(input) {
+        Ok(tokens) => tokens,
+        Err(error) => abort!(error.span(), error.to_string()),
+    }
 }
@@ -1,22 +1,37 @@
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::ops::Range;
 
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error::abort;
 use quote::{quote, ToTokens};
-use syn::{parse::Parse, parse_quote, punctuated::Punctuated, Expr, ExprPath, LitStr, Token};
+use syn::{
+    parse::Parse, parse_quote, punctuated::Punctuated, Expr, ExprPath, Ident, LitStr, Token,
+};
+
+const HEX_DIGITS: &str = "0123456789abcdefABCDEF";
 
 pub fn map_to_tree(legacy_chat: LegacyChat) -> syn::Result<ExpandedChatPart> {
+    validate_literals(&legacy_chat)?;
+    if let Some(key) = legacy_chat.translate {
+        return map_translation(&legacy_chat.pattern, &key, legacy_chat.chat_parts);
+    }
+
     // Root Chat component
-    let mut root = ExpandedChatPart::new(quote!(::mc_chat::Chat::text("")));
+    let mut root = ExpandedChatPart::new(Content::Text(String::new()));
 
     let mut current_parent = ExpandedChatPart::default();
-    for part in legacy_chat.chat_parts.into_iter().rev() {
+    for part in expand_placeholders(legacy_chat.chat_parts)?
+        .into_iter()
+        .rev()
+    {
         match part {
             ChatPart::Literal(part) => {
                 let pattern = legacy_chat.pattern.value();
-                let value = part.value();
-                let mut piece_iter = value.rsplit(&pattern);
+                let value = collapse_hex_sequences(&part.value(), &pattern);
+                let value = expand_gradients(&value, &pattern, part.span())?;
+                let pieces = split_codes(&value, &pattern);
+                let mut piece_iter = pieces.iter().rev().map(String::as_str);
                 let mut piece = piece_iter.next().ok_or(syn::Error::new(
                     part.span(),
                     "Empty string should be able to be rsplit-ted",
@@ -28,37 +43,54 @@ pub fn map_to_tree(legacy_chat: LegacyChat) -> syn::Result<ExpandedChatPart> {
                             abort!(part.span(), "Invalid escape sequence detected!");
                         }
                     } else if next_piece.is_none() {
+                        if !current_parent.is_placeholder() {
+                            // text in front of a styled node is its sibling, not its child
+                            current_parent.children.reverse();
+                            let node = current_parent;
+                            current_parent = ExpandedChatPart::default();
+                            current_parent.children.push(node);
+                        }
                         current_parent
                             .children
-                            .push(ExpandedChatPart::new(quote!(::mc_chat::Chat::text(#piece))))
+                            .push(ExpandedChatPart::new(Content::Text(piece.to_string())))
                     } else {
                         let mut chars = piece.chars();
                         let code = chars.next();
-                        if code.is_none() || !"0123456789abcdefklmnor".contains(code.unwrap()) {
+                        if code.is_none() || !"0123456789abcdefklmnor#".contains(code.unwrap()) {
                             abort!(part.span(), "Invalid escape sequence detected!");
                         }
                         let code = code.unwrap();
-                        let rest = chars.as_str();
+                        let mut rest = chars.as_str();
+
+                        let color = if code == '#' {
+                            let (hex, remaining) = split_hex_color(part.span(), rest)?;
+                            rest = remaining;
+                            Some(hex)
+                        } else if "0123456789abcdef".contains(code) {
+                            Some(color_from_code(part.span(), code)?)
+                        } else {
+                            None
+                        };
 
-                        if "0123456789abcdef".contains(code) {
+                        if let Some(color) = color {
                             if current_parent.is_placeholder() {
                                 if !rest.is_empty() {
                                     let mut node =
-                                        ExpandedChatPart::new(quote!(::mc_chat::Chat::text(#rest)));
-                                    node.color = Some(color_from_code(part.span(), code)?);
+                                        ExpandedChatPart::new(Content::Text(rest.to_string()));
+                                    node.color = Some(color);
                                     current_parent.children.push(node);
                                 }
                             } else if !rest.is_empty() {
                                 let mut node =
-                                    ExpandedChatPart::new(quote!(::mc_chat::Chat::text(#rest)));
-                                node.color = Some(color_from_code(part.span(), code)?);
+                                    ExpandedChatPart::new(Content::Text(rest.to_string()));
+                                node.color = Some(color);
                                 // reverse for correct left to right order
                                 current_parent.children.reverse();
                                 node.children.push(current_parent);
                                 current_parent = ExpandedChatPart::default();
                                 current_parent.children.push(node);
                             } else if current_parent.color.is_none() {
-                                current_parent.color = Some(color_from_code(part.span(), code)?);
+                                current_parent.color = Some(color);
                             }
                         } else if code == 'r' {
                             if current_parent.is_placeholder() {
@@ -69,13 +101,11 @@ pub fn map_to_tree(legacy_chat: LegacyChat) -> syn::Result<ExpandedChatPart> {
                             }
                             current_parent = ExpandedChatPart::default();
                             if !rest.is_empty() {
-                                root.children.push(ExpandedChatPart::new(
-                                    quote!(::mc_chat::Chat::text(#rest)),
-                                ));
+                                root.children
+                                    .push(ExpandedChatPart::new(Content::Text(rest.to_string())));
                             }
                         } else if !rest.is_empty() {
-                            let mut node =
-                                ExpandedChatPart::new(quote!(::mc_chat::Chat::text(#rest)));
+                            let mut node = ExpandedChatPart::new(Content::Text(rest.to_string()));
                             node.extra_style.insert(code);
                             if current_parent.is_placeholder() {
                                 node.children.extend(current_parent.children);
@@ -88,8 +118,8 @@ pub fn map_to_tree(legacy_chat: LegacyChat) -> syn::Result<ExpandedChatPart> {
                             || !current_parent.children.is_empty()
                         {
                             current_parent.extra_style.insert(code);
-                            if current_parent.tokens.is_none() {
-                                current_parent.tokens = Some(quote!(::mc_chat::Chat::text("")));
+                            if current_parent.content.is_none() {
+                                current_parent.content = Some(Content::Text(String::new()));
                             }
                         }
                     }
@@ -102,19 +132,48 @@ pub fn map_to_tree(legacy_chat: LegacyChat) -> syn::Result<ExpandedChatPart> {
                 }
             }
             ChatPart::Variable(part) => {
-                let mut node = ExpandedChatPart::new(quote!(::mc_chat::Chat::text(#part)));
-                if current_parent.is_placeholder() {
-                    node.children.extend(current_parent.children);
+                let mut node = ExpandedChatPart::new(Content::Variable(part));
+                node.adopt(current_parent);
+                current_parent = node;
+            }
+            ChatPart::Annotated(part) => {
+                let content = if part.chat {
+                    Content::Chat(part.expr)
                 } else {
-                    node.children.push(current_parent);
+                    Content::Variable(part.expr)
+                };
+                let mut variable = ExpandedChatPart::new(content);
+                variable.attributes = part.attributes;
+                if part.sibling {
+                    // Independent of the surrounding codes, as if surrounded by resets.
+                    if current_parent.is_placeholder() {
+                        root.children.extend(current_parent.children);
+                    } else {
+                        current_parent.children.reverse();
+                        root.children.push(current_parent);
+                    }
+                    current_parent = ExpandedChatPart::default();
+                    root.children.push(variable);
+                    continue;
+                }
+                if variable.attributes.is_empty() {
+                    variable.adopt(current_parent);
+                    current_parent = variable;
+                } else {
+                    // The runtime style only applies to the variable itself, the parts
+                    // following it are siblings inside an unstyled container instead.
+                    let mut node = ExpandedChatPart::new(Content::Text(String::new()));
+                    node.adopt(current_parent);
+                    node.children.push(variable);
+                    current_parent = node;
                 }
-                current_parent = node;
             }
         }
     }
     if current_parent.is_placeholder() {
         root.children.extend(current_parent.children);
     } else {
+        current_parent.children.reverse();
         root.children.push(current_parent);
     }
     if root.children.len() == 1 {
@@ -125,54 +184,662 @@ pub fn map_to_tree(legacy_chat: LegacyChat) -> syn::Result<ExpandedChatPart> {
     }
 }
 
-pub fn color_from_code(span: Span, code: char) -> syn::Result<ExprPath> {
-    Ok(match code {
-        '0' => parse_quote!(::mc_chat::TextColor::Black),
-        '1' => parse_quote!(::mc_chat::TextColor::DarkBlue),
-        '2' => parse_quote!(::mc_chat::TextColor::DarkGreen),
-        '3' => parse_quote!(::mc_chat::TextColor::DarkCyan),
-        '4' => parse_quote!(::mc_chat::TextColor::DarkRed),
-        '5' => parse_quote!(::mc_chat::TextColor::Purple),
-        '6' => parse_quote!(::mc_chat::TextColor::Gold),
-        '7' => parse_quote!(::mc_chat::TextColor::Gray),
-        '8' => parse_quote!(::mc_chat::TextColor::DarkGray),
-        '9' => parse_quote!(::mc_chat::TextColor::Blue),
-        'a' => parse_quote!(::mc_chat::TextColor::Green),
-        'b' => parse_quote!(::mc_chat::TextColor::Cyan),
-        'c' => parse_quote!(::mc_chat::TextColor::Red),
-        'd' => parse_quote!(::mc_chat::TextColor::Pink),
-        'e' => parse_quote!(::mc_chat::TextColor::Yellow),
-        'f' => parse_quote!(::mc_chat::TextColor::White),
-        _ => abort!(span, "Function contract broken, unrecognized color code"),
-    })
+/// Checks the formatting codes of every literal as written in the source,
+/// so errors can point at the offending code itself.
+pub fn validate_literals(legacy_chat: &LegacyChat) -> syn::Result<()> {
+    let pattern = legacy_chat.pattern.value();
+    let literals = legacy_chat
+        .translate
+        .iter()
+        .chain(legacy_chat.chat_parts.iter().filter_map(|part| match part {
+            ChatPart::Literal(literal) => Some(literal),
+            ChatPart::Variable(_) | ChatPart::Annotated(_) => None,
+        }));
+    let mut errors: Option<syn::Error> = None;
+    for literal in literals {
+        if let Err(error) = validate_codes(literal, &pattern) {
+            match errors {
+                Some(ref mut errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+fn validate_codes(literal: &LitStr, pattern: &str) -> syn::Result<()> {
+    let value = literal.value();
+    let mut offset = 0;
+    while let Some(index) = value[offset..].find(pattern) {
+        let index = offset + index;
+        let after = &value[index + pattern.len()..];
+        let code = match after.chars().next() {
+            Some(code) => code,
+            None => {
+                return Err(syn::Error::new(
+                    literal_subspan(literal, index..value.len()),
+                    format!(
+                        "Dangling `{}` at the end of the literal, write `{}{}` for the character itself",
+                        pattern, pattern, pattern
+                    ),
+                ))
+            }
+        };
+        let mut end = index + pattern.len() + code.len_utf8();
+        if after.starts_with(pattern) {
+            // escaped delimiter
+            end = index + 2 * pattern.len();
+        } else if code == '#' {
+            let digits: String = after[1..].chars().take(6).collect();
+            end += digits.len();
+            if digits.len() != 6 || !digits.chars().all(|c| HEX_DIGITS.contains(c)) {
+                return Err(syn::Error::new(
+                    literal_subspan(literal, index..end),
+                    format!(
+                        "Invalid hex color `{}`, expected 6 hex digits after '#'!",
+                        &value[index..end]
+                    ),
+                ));
+            }
+        } else if code == 'x' {
+            let mut sequence = &after[1..];
+            for _ in 0..6 {
+                let digit = sequence
+                    .strip_prefix(pattern)
+                    .and_then(|rest| rest.chars().next())
+                    .filter(|c| HEX_DIGITS.contains(*c));
+                match digit {
+                    Some(_) => {
+                        sequence = &sequence[pattern.len() + 1..];
+                        end += pattern.len() + 1;
+                    }
+                    None => {
+                        return Err(syn::Error::new(
+                            literal_subspan(literal, index..end),
+                            format!(
+                                "Invalid hex color sequence `{}`, expected `{}x` followed by 6 hex digits each prefixed by `{}`",
+                                &value[index..end],
+                                pattern,
+                                pattern
+                            ),
+                        ))
+                    }
+                }
+            }
+        } else if !"0123456789abcdefklmnor<>".contains(code) {
+            return Err(syn::Error::new(
+                literal_subspan(literal, index..end),
+                format!("Invalid formatting code `{}`", &value[index..end]),
+            ));
+        }
+        offset = end;
+    }
+    Ok(())
+}
+
+/// The span of a byte range of the literal's value. Falls back to the span of
+/// the whole literal when the compiler doesn't support sub-spans or when the
+/// literal isn't written as a plain string without escapes.
+pub fn literal_subspan(literal: &LitStr, range: Range<usize>) -> Span {
+    let token = literal.token();
+    if token.to_string() != format!("\"{}\"", literal.value()) {
+        return literal.span();
+    }
+    token
+        .subspan(range.start + 1..range.end + 1)
+        .unwrap_or_else(|| literal.span())
+}
+
+/// Splits a literal at its formatting codes like `str::split` does,
+/// turning escaped delimiters (the pattern written twice) back into text.
+pub fn split_codes(value: &str, pattern: &str) -> Vec<String> {
+    let mut split = value.split(pattern);
+    let mut pieces = vec![split.next().unwrap_or_default().to_string()];
+    while let Some(piece) = split.next() {
+        if !piece.is_empty() {
+            pieces.push(piece.to_string());
+            continue;
+        }
+        match split.next() {
+            Some(next) => {
+                let last = pieces.last_mut().expect("at least one piece");
+                last.push_str(pattern);
+                last.push_str(next);
+            }
+            // a dangling delimiter, rejected while building the tree
+            None => pieces.push(String::new()),
+        }
+    }
+    pieces
+}
+
+/// Finds the first formatting code starting with `code`, skipping
+/// escaped delimiters. An empty `code` matches any formatting code.
+pub fn find_code(value: &str, pattern: &str, code: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(index) = value[offset..].find(pattern) {
+        let index = offset + index;
+        let after = &value[index + pattern.len()..];
+        if after.starts_with(pattern) {
+            offset = index + 2 * pattern.len();
+        } else if after.starts_with(code) {
+            return Some(index);
+        } else {
+            offset = index + pattern.len();
+        }
+    }
+    None
+}
+
+/// Splits `{}` and `{name}` placeholders out of the literals, the same way
+/// `format!` does. `{}` takes the next argument following the literal,
+/// `{name}` refers to a variable in scope. Braces are escaped as `{{` and `}}`.
+pub fn expand_placeholders(
+    chat_parts: Punctuated<ChatPart, Token![,]>,
+) -> syn::Result<Vec<ChatPart>> {
+    let mut result = Vec::with_capacity(chat_parts.len());
+    let mut parts = chat_parts.into_iter();
+    while let Some(part) = parts.next() {
+        let literal = match part {
+            ChatPart::Literal(literal) => literal,
+            variable => {
+                result.push(variable);
+                continue;
+            }
+        };
+        let span = literal.span();
+        let value = literal.value();
+        let mut piece = String::new();
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    piece.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    piece.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(syn::Error::new(
+                                    span,
+                                    "Unclosed `{` found, use `{{` to escape it",
+                                ))
+                            }
+                        }
+                    }
+                    if !piece.is_empty() {
+                        result.push(ChatPart::Literal(LitStr::new(&piece, span)));
+                        piece.clear();
+                    }
+                    let name = name.trim();
+                    let variable = if name.is_empty() {
+                        match parts.next() {
+                            Some(ChatPart::Variable(expr)) => ChatPart::Variable(expr),
+                            Some(ChatPart::Annotated(part)) => ChatPart::Annotated(part),
+                            _ => {
+                                return Err(syn::Error::new(
+                                    span,
+                                    "Missing argument for a `{}` placeholder",
+                                ))
+                            }
+                        }
+                    } else {
+                        let ident = syn::parse_str::<Ident>(name).map_err(|_| {
+                            syn::Error::new(
+                                span,
+                                format!(
+                                    "Invalid placeholder `{{{}}}`, expected `{{}}` or `{{name}}`",
+                                    name
+                                ),
+                            )
+                        })?;
+                        let ident = Ident::new(&ident.to_string(), span);
+                        ChatPart::Variable(parse_quote!(#ident))
+                    };
+                    result.push(variable);
+                }
+                '}' => {
+                    return Err(syn::Error::new(
+                        span,
+                        "Unmatched `}` found, use `}}` to escape it",
+                    ))
+                }
+                c => piece.push(c),
+            }
+        }
+        if !piece.is_empty() {
+            result.push(ChatPart::Literal(LitStr::new(&piece, span)));
+        }
+    }
+    Ok(result)
+}
+
+/// Builds a translation component. Formatting codes in front of the key
+/// style the translation component itself, literal arguments are parsed
+/// like any other chat literal and other arguments are used as is.
+pub fn map_translation(
+    pattern: &LitStr,
+    key: &LitStr,
+    arguments: Punctuated<ChatPart, Token![,]>,
+) -> syn::Result<ExpandedChatPart> {
+    let pattern_value = pattern.value();
+    let value = collapse_hex_sequences(&key.value(), &pattern_value);
+
+    let mut node = ExpandedChatPart::default();
+    let mut remaining = value.as_str();
+    while let Some(after) = remaining.strip_prefix(&pattern_value) {
+        if after.starts_with(&pattern_value) {
+            break;
+        }
+        let mut chars = after.chars();
+        let code = chars.next();
+        remaining = chars.as_str();
+        match code {
+            Some('#') => {
+                let (color, rest) = split_hex_color(key.span(), remaining)?;
+                node.color = Some(color);
+                remaining = rest;
+            }
+            Some(code) if "0123456789abcdef".contains(code) => {
+                node.color = Some(color_from_code(key.span(), code)?);
+            }
+            Some(code) if "klmno".contains(code) => {
+                node.extra_style.insert(code);
+            }
+            Some('r') => {
+                node.color = None;
+                node.extra_style.clear();
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "Invalid escape sequence detected!",
+                ))
+            }
+        }
+    }
+    if remaining.is_empty() {
+        return Err(syn::Error::new(key.span(), "Missing translation key!"));
+    }
+    if find_code(remaining, &pattern_value, "").is_some() {
+        return Err(syn::Error::new(
+            key.span(),
+            "Formatting codes are only allowed in front of the translation key!",
+        ));
+    }
+    let escape = pattern_value.repeat(2);
+    let remaining = remaining.replace(&escape, &pattern_value);
+
+    let arguments = arguments
+        .into_iter()
+        .map(|argument| match argument {
+            ChatPart::Literal(literal) => {
+                let mut chat_parts = Punctuated::new();
+                chat_parts.push(ChatPart::Literal(literal));
+                Ok(Argument::Literal(map_to_tree(LegacyChat {
+                    pattern: pattern.clone(),
+                    translate: None,
+                    chat_parts,
+                })?))
+            }
+            ChatPart::Variable(expr) => Ok(Argument::Chat(expr)),
+            ChatPart::Annotated(part) if part.attributes.is_empty() && !part.sibling => {
+                Ok(Argument::Chat(part.expr))
+            }
+            ChatPart::Annotated(part) => Err(syn::Error::new_spanned(
+                part.expr,
+                "Translation arguments can't be annotated, style the Chat value instead",
+            )),
+        })
+        .collect::<syn::Result<_>>()?;
+    node.content = Some(Content::Translation {
+        key: remaining,
+        arguments,
+    });
+    Ok(node)
+}
+
+pub fn color_from_code(span: Span, code: char) -> syn::Result<ColorCode> {
+    if !"0123456789abcdef".contains(code) {
+        abort!(span, "Function contract broken, unrecognized color code");
+    }
+    Ok(ColorCode::Named(code))
+}
+
+/// Splits the 6 hex digits of a `#RRGGBB` color code from the
+/// rest of the piece.
+pub fn split_hex_color(span: Span, piece: &str) -> syn::Result<(ColorCode, &str)> {
+    let digits: String = piece.chars().take(6).collect();
+    if digits.len() != 6 || !digits.chars().all(|c| HEX_DIGITS.contains(c)) {
+        return Err(syn::Error::new(
+            span,
+            "Invalid hex color, expected 6 hex digits after '#'!",
+        ));
+    }
+    let rgb = u32::from_str_radix(&digits, 16).expect("digits were checked");
+    Ok((ColorCode::Hex(rgb), &piece[6..]))
+}
+
+/// Rewrites every `xRRGGBB` sequence (each character prefixed by the pattern)
+/// to the shorter `#RRGGBB` form.
+pub fn collapse_hex_sequences(value: &str, pattern: &str) -> String {
+    let start = format!("{}x", pattern);
+    let mut result = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some(index) = find_code(remaining, pattern, "x") {
+        result.push_str(&remaining[..index]);
+        let mut sequence = &remaining[index + start.len()..];
+        let mut digits = String::with_capacity(6);
+        while digits.len() < 6 {
+            let mut chars = match sequence.strip_prefix(pattern) {
+                Some(after) => after.chars(),
+                None => break,
+            };
+            match chars.next() {
+                Some(c) if HEX_DIGITS.contains(c) => digits.push(c),
+                _ => break,
+            }
+            sequence = chars.as_str();
+        }
+        if digits.len() == 6 {
+            result.push_str(pattern);
+            result.push('#');
+            result.push_str(&digits);
+            remaining = sequence;
+        } else {
+            result.push_str(&start);
+            remaining = &remaining[index + start.len()..];
+        }
+    }
+    result.push_str(remaining);
+    result
+}
+
+/// Expands every `<#RRGGBB:#RRGGBB...>text>` gradient (with `<` and the closing `>`
+/// prefixed by the pattern) into hex color codes for the characters of the text.
+/// Without a closing `>`, the gradient continues up to the end of the literal.
+pub fn expand_gradients(value: &str, pattern: &str, span: Span) -> syn::Result<String> {
+    let start = format!("{}<", pattern);
+    let end = format!("{}>", pattern);
+    let unexpected_end = || syn::Error::new(span, "Gradient end found without a gradient!");
+
+    let mut result = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some(index) = find_code(remaining, pattern, "<") {
+        if find_code(&remaining[..index], pattern, ">").is_some() {
+            return Err(unexpected_end());
+        }
+        result.push_str(&remaining[..index]);
+        let after = &remaining[index + start.len()..];
+        let header_end = after
+            .find('>')
+            .ok_or_else(|| syn::Error::new(span, "Unclosed gradient, expected `>`!"))?;
+        let stops = after[..header_end]
+            .split(':')
+            .map(|stop| match stop.strip_prefix('#') {
+                Some(digits) => match split_hex_color(span, digits)? {
+                    (_, "") => Ok(hex_to_rgb(digits)),
+                    _ => Err(syn::Error::new(span, "Invalid gradient color!")),
+                },
+                None => Err(syn::Error::new(
+                    span,
+                    "Gradient colors should be written as `#RRGGBB`!",
+                )),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        if stops.len() < 2 {
+            return Err(syn::Error::new(
+                span,
+                "A gradient needs at least two colors!",
+            ));
+        }
+
+        let after = &after[header_end + 1..];
+        let (text, rest) = match find_code(after, pattern, ">") {
+            Some(index) => (&after[..index], &after[index + end.len()..]),
+            None => (after, ""),
+        };
+        if find_code(text, pattern, "").is_some() {
+            return Err(syn::Error::new(
+                span,
+                "Formatting codes are not allowed inside a gradient!",
+            ));
+        }
+        // an escaped delimiter is colored as a single character
+        let escape = pattern.repeat(2);
+        let mut characters = Vec::new();
+        let mut chars = text;
+        while let Some(c) = chars.chars().next() {
+            let len = if chars.starts_with(&escape) {
+                escape.len()
+            } else {
+                c.len_utf8()
+            };
+            characters.push(&chars[..len]);
+            chars = &chars[len..];
+        }
+        let mut previous = None;
+        for (index, c) in characters.iter().enumerate() {
+            let color = gradient_color(&stops, index, characters.len());
+            if previous != Some(color) {
+                result.push_str(pattern);
+                result.push_str(&format!(
+                    "#{:02x}{:02x}{:02x}",
+                    color[0], color[1], color[2]
+                ));
+                previous = Some(color);
+            }
+            result.push_str(c);
+        }
+        remaining = rest;
+    }
+    if find_code(remaining, pattern, ">").is_some() {
+        return Err(unexpected_end());
+    }
+    result.push_str(remaining);
+    Ok(result)
+}
+
+fn hex_to_rgb(digits: &str) -> [u8; 3] {
+    let value = u32::from_str_radix(digits, 16).expect("validated hex color");
+    [(value >> 16) as u8, (value >> 8) as u8, value as u8]
+}
+
+/// Linearly interpolates the color of character `index` out of `count`
+/// between the gradient's color stops.
+fn gradient_color(stops: &[[u8; 3]], index: usize, count: usize) -> [u8; 3] {
+    if count <= 1 {
+        return stops[0];
+    }
+    let position = index as f64 / (count - 1) as f64 * (stops.len() - 1) as f64;
+    let segment = (position.floor() as usize).min(stops.len() - 2);
+    let t = position - segment as f64;
+    let (from, to) = (stops[segment], stops[segment + 1]);
+    let mut color = [0; 3];
+    for (channel, (from, to)) in color.iter_mut().zip(from.iter().zip(to.iter())) {
+        *channel = (*from as f64 + (*to as f64 - *from as f64) * t).round() as u8;
+    }
+    color
+}
+
+/// A color set by a formatting code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorCode {
+    /// One of the 16 named colors, `0-9a-f`.
+    Named(char),
+    /// A custom `#RRGGBB` color, packed as `0xRRGGBB`.
+    Hex(u32),
+}
+
+impl ToTokens for ColorCode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let color: Expr = match self {
+            ColorCode::Named(code) => {
+                let path: ExprPath = match code {
+                    '0' => parse_quote!(::mc_chat::TextColor::Black),
+                    '1' => parse_quote!(::mc_chat::TextColor::DarkBlue),
+                    '2' => parse_quote!(::mc_chat::TextColor::DarkGreen),
+                    '3' => parse_quote!(::mc_chat::TextColor::DarkCyan),
+                    '4' => parse_quote!(::mc_chat::TextColor::DarkRed),
+                    '5' => parse_quote!(::mc_chat::TextColor::Purple),
+                    '6' => parse_quote!(::mc_chat::TextColor::Gold),
+                    '7' => parse_quote!(::mc_chat::TextColor::Gray),
+                    '8' => parse_quote!(::mc_chat::TextColor::DarkGray),
+                    '9' => parse_quote!(::mc_chat::TextColor::Blue),
+                    'a' => parse_quote!(::mc_chat::TextColor::Green),
+                    'b' => parse_quote!(::mc_chat::TextColor::Cyan),
+                    'c' => parse_quote!(::mc_chat::TextColor::Red),
+                    'd' => parse_quote!(::mc_chat::TextColor::Pink),
+                    'e' => parse_quote!(::mc_chat::TextColor::Yellow),
+                    'f' => parse_quote!(::mc_chat::TextColor::White),
+                    _ => panic!("Invalid color code!!"),
+                };
+                Expr::Path(path)
+            }
+            ColorCode::Hex(rgb) => {
+                let [r, g, b] = [(rgb >> 16) as u8, (rgb >> 8) as u8, *rgb as u8];
+                parse_quote!(::mc_chat::TextColor::from_rgb(#r, #g, #b))
+            }
+        };
+        color.to_tokens(tokens);
+    }
+}
+
+/// The content of a single chat component.
+pub enum Content {
+    /// Literal text.
+    Text(String),
+    /// A value turned into text at runtime.
+    Variable(Expr),
+    /// A value turned into a `Chat` at runtime.
+    Chat(Expr),
+    /// A translation component.
+    Translation {
+        key: String,
+        arguments: Vec<Argument>,
+    },
+}
+
+impl Debug for Content {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Content::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Content::Variable(expr) => f
+                .debug_tuple("Variable")
+                .field(&expr.to_token_stream().to_string())
+                .finish(),
+            Content::Chat(expr) => f
+                .debug_tuple("Chat")
+                .field(&expr.to_token_stream().to_string())
+                .finish(),
+            Content::Translation { key, arguments } => f
+                .debug_struct("Translation")
+                .field("key", key)
+                .field("arguments", arguments)
+                .finish(),
+        }
+    }
+}
+
+impl ToTokens for Content {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Content::Text(text) => quote!(::mc_chat::Chat::text(#text)),
+            Content::Variable(expr) => quote!(::mc_chat::Chat::text(#expr)),
+            Content::Chat(expr) => {
+                quote!(::core::convert::Into::<::mc_chat::Chat>::into(#expr))
+            }
+            Content::Translation { key, arguments } => {
+                let mut component = quote!(::mc_chat::TranslationComponent::new(#key));
+                for argument in arguments {
+                    component = quote!(#component.argument(#argument));
+                }
+                quote!(::mc_chat::Chat::component(#component))
+            }
+        });
+    }
+}
+
+/// An argument of a translation component.
+pub enum Argument {
+    /// An argument written as a chat literal.
+    Literal(ExpandedChatPart),
+    /// A `Chat` expression.
+    Chat(Expr),
+}
+
+impl Debug for Argument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Argument::Literal(part) => f.debug_tuple("Literal").field(part).finish(),
+            Argument::Chat(expr) => f
+                .debug_tuple("Chat")
+                .field(&expr.to_token_stream().to_string())
+                .finish(),
+        }
+    }
+}
+
+impl ToTokens for Argument {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Argument::Literal(part) => part.to_tokens(tokens),
+            Argument::Chat(expr) => expr.to_tokens(tokens),
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct ExpandedChatPart {
-    pub tokens: Option<TokenStream>,
-    pub color: Option<ExprPath>,
+    pub content: Option<Content>,
+    pub color: Option<ColorCode>,
     pub extra_style: HashSet<char>,
+    /// Style attributes evaluated at runtime.
+    pub attributes: Vec<StyleAttribute>,
     pub children: Vec<ExpandedChatPart>,
 }
 
 impl ExpandedChatPart {
-    pub fn new(tokens: TokenStream) -> Self {
+    pub fn new(content: Content) -> Self {
         Self {
-            tokens: Some(tokens),
+            content: Some(content),
             ..Default::default()
         }
     }
+    /// Takes the parts following this node, collected in reverse order by
+    /// the current parent, as its children.
+    fn adopt(&mut self, mut current_parent: ExpandedChatPart) {
+        if current_parent.is_placeholder() {
+            self.children.extend(current_parent.children);
+        } else {
+            current_parent.children.reverse();
+            self.children.push(current_parent);
+        }
+    }
+
     pub fn is_placeholder(&self) -> bool {
-        self.color.is_none() && self.extra_style.is_empty() && self.tokens.is_none()
+        self.color.is_none()
+            && self.extra_style.is_empty()
+            && self.attributes.is_empty()
+            && self.content.is_none()
     }
 }
 
 impl Debug for ExpandedChatPart {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ExpandedChatPart")
-            .field("tokens", &self.tokens.as_ref().map(|t| t.to_string()))
+            .field("content", &self.content)
             .field("color", &self.color.as_ref())
             .field("extra_style", &self.extra_style)
+            .field("attributes", &self.attributes)
             .field("children", &self.children)
             .finish()
     }
@@ -180,10 +847,10 @@ impl Debug for ExpandedChatPart {
 
 impl ToTokens for ExpandedChatPart {
     fn to_tokens(&self, token_stream: &mut TokenStream) {
-        let mut tokens: TokenStream = self
-            .tokens
-            .clone()
-            .unwrap_or(quote!(::mc_chat::Chat::text("")));
+        let mut tokens = match self.content {
+            Some(ref content) => content.to_token_stream(),
+            None => quote!(::mc_chat::Chat::text("")),
+        };
         if let Some(ref color) = self.color {
             tokens = quote!(#tokens.color(#color));
         }
@@ -197,6 +864,9 @@ impl ToTokens for ExpandedChatPart {
                 _ => panic!("Invalid non-color code!!"),
             }
         }
+        for attribute in &self.attributes {
+            tokens = quote!(#tokens.#attribute);
+        }
         for child in &self.children {
             tokens = quote!(#tokens.child(#child));
         }
@@ -206,18 +876,35 @@ impl ToTokens for ExpandedChatPart {
 
 pub struct LegacyChat {
     pub pattern: LitStr,
-    pub comma: Token![,],
+    /// The translation key when using the `tr "key", arguments...` syntax.
+    pub translate: Option<LitStr>,
     pub chat_parts: Punctuated<ChatPart, Token![,]>,
 }
 
 impl Parse for LegacyChat {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let pattern = input.parse()?;
-        let comma = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let translate = if input.peek(Ident) && input.peek2(LitStr) {
+            let ident: Ident = input.parse()?;
+            if ident != "tr" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "Expected `tr` in front of a translation key",
+                ));
+            }
+            let key = input.parse()?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+            Some(key)
+        } else {
+            None
+        };
         let chat_parts = Punctuated::parse_terminated(input)?;
         Ok(LegacyChat {
             pattern,
-            comma,
+            translate,
             chat_parts,
         })
     }
@@ -226,15 +913,162 @@ impl Parse for LegacyChat {
 pub enum ChatPart {
     Literal(LitStr),
     Variable(Expr),
+    /// A `Chat` value or a variable followed by attributes.
+    Annotated(AnnotatedPart),
+}
+
+/// `@chat_expr` and/or `expr: color = expr, bold, sibling`.
+pub struct AnnotatedPart {
+    pub expr: Expr,
+    /// Whether the value is a `Chat` (written as `@expr`) instead of text.
+    pub chat: bool,
+    pub attributes: Vec<StyleAttribute>,
+    /// Whether the value is independent of the codes around it instead of
+    /// taking the following parts as its children.
+    pub sibling: bool,
 }
 
 impl Parse for ChatPart {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         if input.peek(LitStr) {
-            Ok(ChatPart::Literal(input.parse()?))
+            return Ok(ChatPart::Literal(input.parse()?));
+        }
+        let chat = input.peek(Token![@]);
+        if chat {
+            input.parse::<Token![@]>()?;
+        }
+        let expr = input.parse()?;
+        if !chat && !input.peek(Token![:]) {
+            return Ok(ChatPart::Variable(expr));
+        }
+        let mut attributes = Vec::new();
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            attributes.push(input.parse()?);
+            // keep parsing attributes as long as the next part is one
+            while input.peek(Token![,]) && StyleAttribute::peek_after_comma(input) {
+                input.parse::<Token![,]>()?;
+                attributes.push(input.parse()?);
+            }
+        }
+        let mut sibling = false;
+        attributes.retain(|attribute: &StyleAttribute| {
+            if PLACEMENT_ATTRIBUTES
+                .iter()
+                .any(|name| attribute.name == name)
+            {
+                sibling = attribute.name == "sibling";
+                false
+            } else {
+                true
+            }
+        });
+        Ok(ChatPart::Annotated(AnnotatedPart {
+            expr,
+            chat,
+            attributes,
+            sibling,
+        }))
+    }
+}
+
+const FLAG_ATTRIBUTES: &[&str] = &[
+    "bold",
+    "italic",
+    "underlined",
+    "strikethrough",
+    "obfuscated",
+];
+const VALUE_ATTRIBUTES: &[&str] = &["color", "font", "insertion", "click", "hover"];
+/// Whether the following parts become children of the value (the default) or not.
+const PLACEMENT_ATTRIBUTES: &[&str] = &["nested", "sibling"];
+
+/// A style setting of a variable, evaluated at runtime.
+///
+/// Flags like `bold` can be written without a value to enable them.
+pub struct StyleAttribute {
+    pub name: Ident,
+    pub value: Option<Expr>,
+}
+
+impl StyleAttribute {
+    fn peek_after_comma(input: syn::parse::ParseStream) -> bool {
+        let fork = input.fork();
+        fork.parse::<Token![,]>().is_ok()
+            && fork.parse::<Ident>().is_ok_and(|ident| {
+                (FLAG_ATTRIBUTES.contains(&ident.to_string().as_str())
+                    || VALUE_ATTRIBUTES.contains(&ident.to_string().as_str())
+                    || PLACEMENT_ATTRIBUTES.contains(&ident.to_string().as_str()))
+                    && (fork.is_empty() || fork.peek(Token![,]) || fork.peek(Token![=]))
+            })
+    }
+}
+
+impl Parse for StyleAttribute {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
         } else {
-            Ok(ChatPart::Variable(input.parse()?))
+            None
+        };
+        let key = name.to_string();
+        if VALUE_ATTRIBUTES.contains(&key.as_str()) {
+            if value.is_none() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Expected a value for `{}`, e.g. `{} = value`", key, key),
+                ));
+            }
+        } else if !FLAG_ATTRIBUTES.contains(&key.as_str())
+            && !PLACEMENT_ATTRIBUTES.contains(&key.as_str())
+        {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "Unknown style attribute `{}`, expected one of {}",
+                    key,
+                    FLAG_ATTRIBUTES
+                        .iter()
+                        .chain(VALUE_ATTRIBUTES)
+                        .chain(PLACEMENT_ATTRIBUTES)
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
         }
+        Ok(StyleAttribute { name, value })
+    }
+}
+
+impl Debug for StyleAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StyleAttribute")
+            .field("name", &self.name.to_string())
+            .field(
+                "value",
+                &self
+                    .value
+                    .as_ref()
+                    .map(|value| value.to_token_stream().to_string()),
+            )
+            .finish()
+    }
+}
+
+impl ToTokens for StyleAttribute {
+    /// Emits the matching `Chat` setter call, without the leading dot.
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        tokens.extend(match (name.to_string().as_str(), &self.value) {
+            ("font" | "insertion" | "click" | "hover", Some(value)) => {
+                quote!(#name(::core::option::Option::Some(#value)))
+            }
+            (_, Some(value)) => quote!(#name(#value)),
+            (_, None) => quote!(#name(true)),
+        });
     }
 }
 
@@ -259,6 +1093,303 @@ mod tests {
             assert_eq!("§&", &text.pattern.value());
             assert_eq!(3, text.chat_parts.len());
         }
+
+        #[test]
+        fn translation() {
+            let text: LegacyChat = parse_quote!("§", tr "§echat.type.text", name, "§7Hi");
+            assert_eq!("§echat.type.text", &text.translate.unwrap().value());
+            assert_eq!(2, text.chat_parts.len());
+        }
+    }
+
+    mod placeholders {
+        use super::*;
+
+        fn expand(text: LegacyChat) -> Vec<String> {
+            expand_placeholders(text.chat_parts)
+                .unwrap()
+                .into_iter()
+                .map(|part| match part {
+                    ChatPart::Literal(literal) => literal.value(),
+                    ChatPart::Variable(expr) | ChatPart::Annotated(AnnotatedPart { expr, .. }) => {
+                        expr.into_token_stream().to_string()
+                    }
+                })
+                .collect()
+        }
+
+        #[test]
+        fn named() {
+            let text: LegacyChat = parse_quote!("§", "§9Var: §5§n{var}§e!!");
+            assert_eq!(vec!["§9Var: §5§n", "var", "§e!!"], expand(text));
+        }
+
+        #[test]
+        fn positional() {
+            let text: LegacyChat = parse_quote!("§", "{} and {}!", first, second, "end");
+            assert_eq!(vec!["first", " and ", "second", "!", "end"], expand(text));
+        }
+
+        #[test]
+        fn escaped() {
+            let text: LegacyChat = parse_quote!("§", "{{not}} a placeholder");
+            assert_eq!(vec!["{not} a placeholder"], expand(text));
+        }
+
+        #[test]
+        fn errors() {
+            let text: LegacyChat = parse_quote!("§", "missing {}");
+            assert!(expand_placeholders(text.chat_parts).is_err());
+            let text: LegacyChat = parse_quote!("§", "unclosed {var");
+            assert!(expand_placeholders(text.chat_parts).is_err());
+            let text: LegacyChat = parse_quote!("§", "invalid {a + b}");
+            assert!(expand_placeholders(text.chat_parts).is_err());
+        }
+    }
+
+    mod escape {
+        use super::*;
+
+        #[test]
+        fn split() {
+            assert_eq!(vec!["a§lb"], split_codes("a§§lb", "§"));
+            assert_eq!(vec!["§", "lb"], split_codes("§§§lb", "§"));
+            assert_eq!(vec!["", "l§"], split_codes("§l§§", "§"));
+            assert_eq!(vec!["a", ""], split_codes("a§", "§"));
+        }
+
+        #[test]
+        fn find() {
+            assert_eq!(None, find_code("§§x", "§", "x"));
+            assert_eq!(Some(4), find_code("§§§x", "§", "x"));
+            assert_eq!(Some(0), find_code("§l", "§", ""));
+        }
+
+        #[test]
+        fn literal_delimiter() {
+            let text: LegacyChat = parse_quote!("§", "§e100§§ sure");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(0, tree.children.len());
+            assert!(matches!(tree.content, Some(Content::Text(ref text)) if text == "100§ sure"));
+        }
+
+        #[test]
+        fn escaped_sequences() {
+            assert_eq!("§§x§F§F", collapse_hex_sequences("§§x§F§F", "§"));
+            assert_eq!(
+                "§§<#000000:#ffffff>a",
+                expand_gradients("§§<#000000:#ffffff>a", "§", Span::call_site()).unwrap()
+            );
+            assert_eq!(
+                "§#000000§§§#ffffffa",
+                expand_gradients("§<#000000:#ffffff>§§a", "§", Span::call_site()).unwrap()
+            );
+        }
+    }
+
+    mod diagnostics {
+        use super::*;
+
+        fn error(text: LegacyChat) -> String {
+            validate_literals(&text).unwrap_err().to_string()
+        }
+
+        #[test]
+        fn invalid_code() {
+            let message = error(parse_quote!("§", "Hello §zworld"));
+            assert!(message.contains("`§z`"), "{}", message);
+        }
+
+        #[test]
+        fn invalid_hex_colors() {
+            let message = error(parse_quote!("§", "§#ff88Hello"));
+            assert!(message.contains("`§#ff88He`"), "{}", message);
+            let message = error(parse_quote!("§", "§x§f§fHello"));
+            assert!(message.contains("`§x§f§f`"), "{}", message);
+        }
+
+        #[test]
+        fn dangling_delimiter() {
+            let message = error(parse_quote!("&", "Hello &"));
+            assert!(message.contains("`&&`"), "{}", message);
+        }
+
+        #[test]
+        fn all_literals() {
+            let text: LegacyChat = parse_quote!("§", "§zHello", name, "§yworld");
+            let errors = validate_literals(&text).unwrap_err();
+            assert_eq!(2, errors.into_iter().count());
+        }
+
+        #[test]
+        fn valid() {
+            let text: LegacyChat =
+                parse_quote!("§", "§x§0§0§0§0§0§0§<#ff0000:#00ff00>a§>§§z§r", name);
+            assert!(validate_literals(&text).is_ok());
+        }
+    }
+
+    mod attributes {
+        use super::*;
+
+        #[test]
+        fn parse() {
+            let text: LegacyChat =
+                parse_quote!("§", "Hi ", {name}: color = rank_color, bold, "!", other);
+            assert_eq!(4, text.chat_parts.len());
+            match &text.chat_parts[1] {
+                ChatPart::Annotated(AnnotatedPart { attributes, .. }) => {
+                    assert_eq!(2, attributes.len());
+                    assert_eq!("color", attributes[0].name.to_string());
+                    assert!(attributes[0].value.is_some());
+                    assert_eq!("bold", attributes[1].name.to_string());
+                    assert!(attributes[1].value.is_none());
+                }
+                _ => panic!("Expected a styled variable"),
+            }
+        }
+
+        #[test]
+        fn errors() {
+            assert!(syn::parse2::<LegacyChat>(quote!("§", {name}: colour = red)).is_err());
+            assert!(syn::parse2::<LegacyChat>(quote!("§", {name}: color)).is_err());
+        }
+
+        #[test]
+        fn scoped_to_variable() {
+            let text: LegacyChat = parse_quote!("§", "§eHi ", {name}: bold, "!");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(Some(ColorCode::Named('e')), tree.color);
+            assert_eq!(1, tree.children.len());
+            let container = &tree.children[0];
+            assert!(container.attributes.is_empty());
+            assert_eq!(2, container.children.len());
+            assert_eq!(1, container.children[0].attributes.len());
+            assert!(container.children[1].attributes.is_empty());
+        }
+    }
+
+    mod chat_values {
+        use super::*;
+
+        #[test]
+        fn parse() {
+            let text: LegacyChat = parse_quote!("§", "Hi ", @rank, @name: sibling, {name}: nested);
+            let parts: Vec<_> = text
+                .chat_parts
+                .iter()
+                .filter_map(|part| match part {
+                    ChatPart::Annotated(part) => Some((part.chat, part.sibling)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(vec![(true, false), (true, true), (false, false)], parts);
+        }
+
+        #[test]
+        fn nested() {
+            let text: LegacyChat = parse_quote!("§", "§eHi ", @rank, " there");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(1, tree.children.len());
+            assert!(matches!(tree.children[0].content, Some(Content::Chat(_))));
+            assert_eq!(1, tree.children[0].children.len());
+        }
+
+        #[test]
+        fn sibling() {
+            let text: LegacyChat = parse_quote!("§", "§eHi ", @rank: sibling, " there");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(3, tree.children.len());
+            assert!(tree.children[0].color.is_some());
+            assert!(matches!(tree.children[1].content, Some(Content::Chat(_))));
+            assert!(tree.children[1].children.is_empty());
+            assert!(tree.children[2].color.is_none());
+        }
+    }
+
+    mod translation {
+        use super::*;
+
+        #[test]
+        fn styled_key() {
+            let text: LegacyChat = parse_quote!("§", tr "§e§lchat.type.text", name, "Hi");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(Some(ColorCode::Named('e')), tree.color);
+            assert!(tree.extra_style.contains(&'l'));
+            assert!(tree.children.is_empty());
+            match tree.content {
+                Some(Content::Translation { key, arguments }) => {
+                    assert_eq!("chat.type.text", key);
+                    assert!(matches!(arguments[0], Argument::Chat(_)));
+                    assert!(matches!(arguments[1], Argument::Literal(_)));
+                }
+                _ => panic!("expected a translation component"),
+            }
+        }
+
+        #[test]
+        fn codes_inside_key() {
+            let text: LegacyChat = parse_quote!("§", tr "chat.§etype");
+            assert!(map_to_tree(text).is_err());
+        }
+
+        #[test]
+        fn missing_key() {
+            let text: LegacyChat = parse_quote!("§", tr "§e");
+            assert!(map_to_tree(text).is_err());
+        }
+    }
+
+    mod hex {
+        use super::*;
+
+        #[test]
+        fn collapse_sequence() {
+            assert_eq!(
+                "a§#00ff00b",
+                collapse_hex_sequences("a§x§0§0§f§f§0§0b", "§")
+            );
+            assert_eq!("§x§0§0b", collapse_hex_sequences("§x§0§0b", "§"));
+        }
+    }
+
+    mod gradient {
+        use super::*;
+
+        #[test]
+        fn two_stops() {
+            assert_eq!(
+                "Hi §#ff0000a§#800080b§#0000ffc!",
+                expand_gradients("Hi §<#ff0000:#0000ff>abc§>!", "§", Span::call_site()).unwrap()
+            );
+        }
+
+        #[test]
+        fn merges_equal_colors() {
+            assert_eq!(
+                "§#00ff00abc",
+                expand_gradients("§<#00ff00:#00ff00>abc", "§", Span::call_site()).unwrap()
+            );
+        }
+
+        #[test]
+        fn three_stops() {
+            assert_eq!(
+                "§#ff0000a§#00ff00b§#0000ffc",
+                expand_gradients("§<#ff0000:#00ff00:#0000ff>abc§>", "§", Span::call_site())
+                    .unwrap()
+            );
+        }
+
+        #[test]
+        fn errors() {
+            let span = Span::call_site();
+            assert!(expand_gradients("§<#ff0000>abc§>", "§", span).is_err());
+            assert!(expand_gradients("§<#ff0000:#00ff>abc§>", "§", span).is_err());
+            assert!(expand_gradients("§<#ff0000:#0000ff abc", "§", span).is_err());
+            assert!(expand_gradients("§<#ff0000:#0000ff>a§lbc§>", "§", span).is_err());
+            assert!(expand_gradients("abc§>", "§", span).is_err());
+        }
     }
 
     mod tree {
@@ -352,6 +1483,57 @@ mod tests {
             assert!(tree.extra_style.is_empty());
         }
 
+        #[test]
+        fn hex_color() {
+            let text: LegacyChat = parse_quote!("§", "§#ff8800Hello §aworld!");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(2, tree.children.len());
+            assert_eq!(Some(ColorCode::Hex(0xff8800)), tree.children[0].color);
+            assert_eq!(Some(ColorCode::Named('a')), tree.children[1].color);
+        }
+
+        #[test]
+        fn hex_color_sequence() {
+            let text: LegacyChat = parse_quote!("§", "§x§F§F§8§8§0§0Hello");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(0, tree.children.len());
+            assert_eq!(Some(ColorCode::Hex(0xff8800)), tree.color);
+        }
+
+        #[test]
+        fn invalid_hex_color() {
+            let text: LegacyChat = parse_quote!("§", "§#ff88Hello");
+            assert!(map_to_tree(text).is_err());
+        }
+
+        #[test]
+        fn text_before_variable() {
+            let text: LegacyChat = parse_quote!("§", "a", var, "b", "c");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(2, tree.children.len());
+            assert!(
+                matches!(tree.children[0].content, Some(Content::Text(ref text)) if text == "a")
+            );
+            let children: Vec<_> = tree.children[1]
+                .children
+                .iter()
+                .map(|child| match child.content {
+                    Some(Content::Text(ref text)) => text.as_str(),
+                    _ => panic!("Expected text"),
+                })
+                .collect();
+            assert_eq!(vec!["b", "c"], children);
+        }
+
+        #[test]
+        fn text_before_format_code() {
+            let text: LegacyChat = parse_quote!("§", "a§lb");
+            let tree = map_to_tree(text).unwrap();
+            assert_eq!(2, tree.children.len());
+            assert!(tree.children[0].extra_style.is_empty());
+            assert_eq!(1, tree.children[1].extra_style.len());
+        }
+
         #[test]
         fn reset() {
             let text: LegacyChat = parse_quote!("§", "§lTest §r§2reset");
@@ -0,0 +1,209 @@
+//! Compile time serialization of chat literals, producing
+//! the same JSON as `mc_chat`'s versioned serializer with the default
+//! options. The `legacy_parity` test of `mc_chat` compares both at every
+//! version the format changed in.
+
+use syn::{parse::Parse, Error, Expr, Token};
+
+use crate::parsing::{Argument, ColorCode, Content, ExpandedChatPart, LegacyChat};
+use crate::version::HEX_COLOR_VERSION;
+
+const STYLE_CODES: &[(char, &str)] = &[
+    ('l', "bold"),
+    ('o', "italic"),
+    ('n', "underlined"),
+    ('m', "strikethrough"),
+    ('k', "obfuscated"),
+];
+
+pub struct StaticChat {
+    pub version: Expr,
+    pub legacy_chat: LegacyChat,
+}
+
+impl Parse for StaticChat {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let version = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let legacy_chat = input.parse()?;
+        Ok(StaticChat {
+            version,
+            legacy_chat,
+        })
+    }
+}
+
+/// Serializes a fully static chat tree for the given protocol version.
+pub fn to_json(part: &ExpandedChatPart, version: i32) -> syn::Result<String> {
+    let mut json = String::new();
+    write_part(part, version, &mut json)?;
+    Ok(json)
+}
+
+fn write_part(part: &ExpandedChatPart, version: i32, json: &mut String) -> syn::Result<()> {
+    if let Some(attribute) = part.attributes.first() {
+        return Err(Error::new(
+            attribute.name.span(),
+            "Runtime style attributes can't be serialized at compile time!",
+        ));
+    }
+    json.push('{');
+    match &part.content {
+        None => json.push_str(r#""text":"""#),
+        Some(Content::Text(text)) => {
+            json.push_str(r#""text":"#);
+            write_str(text, json);
+        }
+        Some(Content::Variable(expr)) | Some(Content::Chat(expr)) => {
+            return Err(Error::new_spanned(
+                expr,
+                "Only literal text can be serialized at compile time!",
+            ))
+        }
+        Some(Content::Translation { key, arguments }) => {
+            json.push_str(r#""translate":"#);
+            write_str(key, json);
+            if !arguments.is_empty() {
+                json.push_str(r#","with":["#);
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i != 0 {
+                        json.push(',');
+                    }
+                    match argument {
+                        Argument::Literal(part) => write_part(part, version, json)?,
+                        Argument::Chat(expr) => {
+                            return Err(Error::new_spanned(
+                                expr,
+                                "Only literal arguments can be serialized at compile time!",
+                            ))
+                        }
+                    }
+                }
+                json.push(']');
+            }
+        }
+    }
+    for (code, name) in STYLE_CODES {
+        if part.extra_style.contains(code) {
+            json.push_str(&format!(r#","{}":true"#, name));
+        }
+    }
+    match &part.color {
+        Some(ColorCode::Named(code)) => {
+            json.push_str(&format!(r#","color":"{}""#, color_name(*code)));
+        }
+        Some(ColorCode::Hex(rgb)) if version >= HEX_COLOR_VERSION => {
+            json.push_str(&format!(r##","color":"#{:06x}""##, rgb));
+        }
+        _ => {}
+    }
+    if !part.children.is_empty() {
+        json.push_str(r#","extra":["#);
+        for (i, child) in part.children.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            write_part(child, version, json)?;
+        }
+        json.push(']');
+    }
+    json.push('}');
+    Ok(())
+}
+
+pub fn color_name(code: char) -> &'static str {
+    match code {
+        '0' => "black",
+        '1' => "dark_blue",
+        '2' => "dark_green",
+        '3' => "dark_aqua",
+        '4' => "dark_red",
+        '5' => "dark_purple",
+        '6' => "gold",
+        '7' => "gray",
+        '8' => "dark_gray",
+        '9' => "blue",
+        'a' => "green",
+        'b' => "aqua",
+        'c' => "red",
+        'd' => "light_purple",
+        'e' => "yellow",
+        'f' => "white",
+        _ => panic!("Invalid color code!!"),
+    }
+}
+
+/// Writes a JSON string, escaping the same characters as `serde_json`.
+fn write_str(value: &str, json: &mut String) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            '\u{08}' => json.push_str("\\b"),
+            '\u{0c}' => json.push_str("\\f"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use crate::parsing::map_to_tree;
+
+    use super::*;
+
+    fn serialize(version: i32, text: LegacyChat) -> syn::Result<String> {
+        to_json(&map_to_tree(text)?, version)
+    }
+
+    #[test]
+    fn styled_text() {
+        let json = serialize(47, parse_quote!("§", "§6§lHello \"world\"")).unwrap();
+        assert_eq!(
+            r#"{"text":"Hello \"world\"","bold":true,"color":"gold"}"#,
+            json
+        );
+    }
+
+    #[test]
+    fn hex_colors() {
+        let text: LegacyChat = parse_quote!("§", "§#ff8800Hi §aworld");
+        let tree = map_to_tree(text).unwrap();
+        assert_eq!(
+            r#"{"text":"","extra":[{"text":"Hi "},{"text":"world","color":"green"}]}"#,
+            to_json(&tree, 47).unwrap()
+        );
+        assert_eq!(
+            r##"{"text":"","extra":[{"text":"Hi ","color":"#ff8800"},{"text":"world","color":"green"}]}"##,
+            to_json(&tree, 735).unwrap()
+        );
+        assert_eq!(
+            r##"{"text":"Hi","color":"#ff8800"}"##,
+            serialize(735, parse_quote!("§", "§x§F§F§8§8§0§0Hi")).unwrap()
+        );
+    }
+
+    #[test]
+    fn translation() {
+        let json = serialize(47, parse_quote!("§", tr "§echat.type.text", "Steve", "Hi")).unwrap();
+        assert_eq!(
+            r#"{"translate":"chat.type.text","with":[{"text":"Steve"},{"text":"Hi"}],"color":"yellow"}"#,
+            json
+        );
+    }
+
+    #[test]
+    fn variables() {
+        assert!(serialize(47, parse_quote!("§", "Hello ", name)).is_err());
+        assert!(serialize(47, parse_quote!("§", tr "chat.type.text", name)).is_err());
+        assert!(serialize(47, parse_quote!("§", "Hello ", {name}: bold)).is_err());
+    }
+}
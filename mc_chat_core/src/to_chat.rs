@@ -0,0 +1,403 @@
+//! `#[derive(ToChat)]` for structs and enums.
+//!
+//! Every struct and enum variant becomes a text component (its `text`,
+//! the name of a unit struct/variant or empty otherwise) followed by its
+//! fields as children. A `format` string like `"{x}, {y}"` places the fields
+//! between literal text instead, otherwise they're joined by the `separator`
+//! (a single space by default). Fields are converted with `mc_chat::ToChat`,
+//! or with `format!` when they have a `format` of their own.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    meta::ParseNestedMeta, parse_quote, Attribute, Data, DeriveInput, Error, Fields, GenericParam,
+    Ident, LitBool, LitStr,
+};
+
+use crate::parsing::ColorCode;
+use crate::serialize::color_name;
+
+const FLAGS: &[&str] = &[
+    "bold",
+    "italic",
+    "underlined",
+    "strikethrough",
+    "obfuscated",
+];
+
+/// Where a `#[chat(...)]` attribute is placed, which decides the allowed keys.
+#[derive(Clone, Copy, PartialEq)]
+enum Position {
+    Enum,
+    Item,
+    Field,
+}
+
+#[derive(Default)]
+struct ChatAttributes {
+    text: Option<LitStr>,
+    format: Option<LitStr>,
+    separator: Option<LitStr>,
+    color: Option<ColorCode>,
+    flags: Vec<(Ident, bool)>,
+    skip: bool,
+}
+
+impl ChatAttributes {
+    fn parse(attributes: &[Attribute], position: Position) -> syn::Result<Self> {
+        let mut result = ChatAttributes::default();
+        for attribute in attributes {
+            if !attribute.path().is_ident("chat") {
+                continue;
+            }
+            attribute.parse_nested_meta(|meta| result.parse_meta(meta, position))?;
+        }
+        Ok(result)
+    }
+
+    fn parse_meta(&mut self, meta: ParseNestedMeta, position: Position) -> syn::Result<()> {
+        let ident = meta
+            .path
+            .get_ident()
+            .ok_or_else(|| meta.error("Expected a `chat` attribute name"))?
+            .clone();
+        let name = ident.to_string();
+        let allowed = match name.as_str() {
+            "color" => true,
+            name if FLAGS.contains(&name) => true,
+            "text" | "separator" => position == Position::Item,
+            "format" => position != Position::Enum,
+            "skip" => position == Position::Field,
+            _ => return Err(meta.error(format!("Unknown `chat` attribute `{}`", name))),
+        };
+        if !allowed {
+            return Err(meta.error(format!("`{}` is not allowed here", name)));
+        }
+        match name.as_str() {
+            "color" => {
+                let color: LitStr = meta.value()?.parse()?;
+                self.color = Some(parse_color(&color)?);
+            }
+            "text" => self.text = Some(meta.value()?.parse()?),
+            "format" => self.format = Some(meta.value()?.parse()?),
+            "separator" => self.separator = Some(meta.value()?.parse()?),
+            "skip" => self.skip = true,
+            _ => {
+                let value = if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<LitBool>()?.value
+                } else {
+                    true
+                };
+                self.flags.push((ident, value));
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends the style setters to a chat expression.
+    fn style(&self, mut tokens: TokenStream) -> TokenStream {
+        if let Some(ref color) = self.color {
+            tokens = quote!(#tokens.color(#color));
+        }
+        for (flag, value) in &self.flags {
+            tokens = quote!(#tokens.#flag(#value));
+        }
+        tokens
+    }
+}
+
+/// A Minecraft color name like `"dark_red"` or a `"#RRGGBB"` hex color.
+fn parse_color(color: &LitStr) -> syn::Result<ColorCode> {
+    let value = color.value();
+    if let Some(digits) = value.strip_prefix('#') {
+        if digits.len() == 6 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            let rgb = u32::from_str_radix(digits, 16).expect("digits were checked");
+            return Ok(ColorCode::Hex(rgb));
+        }
+    } else if let Some(code) = "0123456789abcdef"
+        .chars()
+        .find(|code| color_name(*code) == value)
+    {
+        return Ok(ColorCode::Named(code));
+    }
+    Err(Error::new(
+        color.span(),
+        format!(
+            "Unknown color `{}`, expected a Minecraft color name or `#RRGGBB`",
+            value
+        ),
+    ))
+}
+
+struct Field {
+    /// The name used in `format` strings, the index for tuple fields.
+    name: String,
+    binding: Ident,
+    attributes: ChatAttributes,
+}
+
+impl Field {
+    fn to_chat(&self) -> TokenStream {
+        let binding = &self.binding;
+        let chat = match self.attributes.format {
+            Some(ref format) => quote!(::mc_chat::Chat::text(::std::format!(#format, #binding))),
+            None => quote!(::mc_chat::ToChat::to_chat(#binding)),
+        };
+        self.attributes.style(chat)
+    }
+}
+
+/// Builds the match arm for a struct or a single enum variant.
+fn arm(
+    path: TokenStream,
+    ident: &Ident,
+    fields: &Fields,
+    attributes: ChatAttributes,
+) -> syn::Result<TokenStream> {
+    let fields_parsed = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            Ok(Field {
+                name: field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| index.to_string(), Ident::to_string),
+                binding: field
+                    .ident
+                    .clone()
+                    .unwrap_or_else(|| format_ident!("field_{}", index)),
+                attributes: ChatAttributes::parse(&field.attrs, Position::Field)?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let bindings = fields_parsed.iter().map(|field| &field.binding);
+    let pattern = match fields {
+        Fields::Named(_) => quote!(#path { #(#bindings,)* }),
+        Fields::Unnamed(_) => quote!(#path(#(#bindings,)*)),
+        Fields::Unit => path,
+    };
+
+    let text = match attributes.text {
+        Some(ref text) => text.value(),
+        None if fields.is_empty() => ident.to_string(),
+        None => String::new(),
+    };
+    let mut chat = attributes.style(quote!(::mc_chat::Chat::text(#text)));
+
+    match attributes.format {
+        Some(ref format) => {
+            for segment in parse_format(format)? {
+                chat = match segment {
+                    Segment::Text(text) => quote!(#chat.child(::mc_chat::Chat::text(#text))),
+                    Segment::Field(name) => {
+                        let field = fields_parsed
+                            .iter()
+                            .find(|field| field.name == name)
+                            .ok_or_else(|| {
+                                Error::new(format.span(), format!("Unknown field `{}`", name))
+                            })?
+                            .to_chat();
+                        quote!(#chat.child(#field))
+                    }
+                }
+            }
+        }
+        None => {
+            let separator = attributes
+                .separator
+                .as_ref()
+                .map_or_else(|| " ".to_string(), LitStr::value);
+            let visible = fields_parsed.iter().filter(|field| !field.attributes.skip);
+            for (index, field) in visible.enumerate() {
+                if index != 0 && !separator.is_empty() {
+                    chat = quote!(#chat.child(::mc_chat::Chat::text(#separator)));
+                }
+                let field = field.to_chat();
+                chat = quote!(#chat.child(#field));
+            }
+        }
+    }
+
+    Ok(quote! {
+        #[allow(unused_variables)]
+        #pattern => #chat,
+    })
+}
+
+enum Segment {
+    Text(String),
+    Field(String),
+}
+
+/// Splits a `format` string into literal text and `{field}` references.
+fn parse_format(format: &LitStr) -> syn::Result<Vec<Segment>> {
+    let value = format.value();
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(Error::new(
+                                format.span(),
+                                "Unclosed `{` found, use `{{` to escape it",
+                            ))
+                        }
+                    }
+                }
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                segments.push(Segment::Field(name.trim().to_string()));
+            }
+            '}' => {
+                return Err(Error::new(
+                    format.span(),
+                    "Unmatched `}` found, use `}}` to escape it",
+                ))
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
+
+pub fn derive_to_chat(input: DeriveInput) -> syn::Result<TokenStream> {
+    let arms = match input.data {
+        Data::Struct(ref data) => {
+            let attributes = ChatAttributes::parse(&input.attrs, Position::Item)?;
+            vec![arm(quote!(Self), &input.ident, &data.fields, attributes)?]
+        }
+        Data::Enum(ref data) => {
+            let enum_attributes = ChatAttributes::parse(&input.attrs, Position::Enum)?;
+            data.variants
+                .iter()
+                .map(|variant| {
+                    let mut attributes = ChatAttributes::parse(&variant.attrs, Position::Item)?;
+                    if attributes.color.is_none() {
+                        attributes.color = enum_attributes.color.clone();
+                    }
+                    let mut flags = enum_attributes.flags.clone();
+                    flags.append(&mut attributes.flags);
+                    attributes.flags = flags;
+                    let ident = &variant.ident;
+                    arm(quote!(Self::#ident), ident, &variant.fields, attributes)
+                })
+                .collect::<syn::Result<_>>()?
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.ident.span(),
+                "ToChat can't be derived for unions",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::mc_chat::ToChat));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let body = if arms.is_empty() {
+        quote!(match *self {})
+    } else {
+        quote!(match self { #(#arms)* })
+    };
+    Ok(quote! {
+        impl #impl_generics ::mc_chat::ToChat for #ident #ty_generics #where_clause {
+            fn to_chat(&self) -> ::mc_chat::Chat {
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn derive(input: DeriveInput) -> syn::Result<String> {
+        derive_to_chat(input).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(
+            ColorCode::Named('4'),
+            parse_color(&parse_quote!("dark_red")).unwrap()
+        );
+        assert_eq!(
+            ColorCode::Hex(0xff8800),
+            parse_color(&parse_quote!("#ff8800")).unwrap()
+        );
+        assert!(parse_color(&parse_quote!("crimson")).is_err());
+        assert!(parse_color(&parse_quote!("#ff88")).is_err());
+    }
+
+    #[test]
+    fn format_segments() {
+        let segments = parse_format(&parse_quote!("{x}, {{{y}}}")).unwrap();
+        assert_eq!(4, segments.len());
+        assert!(matches!(&segments[0], Segment::Field(name) if name == "x"));
+        assert!(matches!(&segments[1], Segment::Text(text) if text == ", {"));
+        assert!(matches!(&segments[3], Segment::Text(text) if text == "}"));
+        assert!(parse_format(&parse_quote!("{x")).is_err());
+    }
+
+    #[test]
+    fn unit_variants() {
+        let tokens = derive(parse_quote! {
+            #[chat(bold)]
+            enum Rank {
+                #[chat(text = "[Admin]", color = "red")]
+                Admin,
+                Member,
+            }
+        })
+        .unwrap();
+        assert!(tokens.contains("\"[Admin]\""));
+        assert!(tokens.contains("\"Member\""));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(derive(parse_quote! {
+            #[chat(format = "{w}")]
+            struct Coordinates { x: i32 }
+        })
+        .is_err());
+        assert!(derive(parse_quote! {
+            #[chat(skip)]
+            struct Coordinates { x: i32 }
+        })
+        .is_err());
+        assert!(derive(parse_quote! {
+            enum Rank {
+                Admin { #[chat(colour = "red")] name: String },
+            }
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Protocol version handling for the macros.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse::Parse, Error, Expr, ExprLit, ExprPath, Ident, Lit, LitStr, Token};
+
+use std::ops::Range;
+
+use crate::parsing::{find_code, literal_subspan, ChatPart, LegacyChat};
+
+macro_rules! protocol_versions {
+    ($($name:ident $version:ident $release:literal $protocol:literal;)*) => {
+        /// Protocol versions exported by `mc_chat` that can be referred to by name.
+        const KNOWN_VERSIONS: &[(&str, i32)] = &[$((stringify!($name), $protocol)),*];
+    };
+}
+
+include!("../../src/version/named.rs");
+
+/// The first version supporting custom hex colors.
+pub const HEX_COLOR_VERSION: i32 = 713;
+
+/// Chat literals with an optional `@target version,` prefix.
+pub struct TargetedChat {
+    pub target: Option<Expr>,
+    pub legacy_chat: LegacyChat,
+}
+
+impl Parse for TargetedChat {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let target = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            let ident: Ident = input.parse()?;
+            if ident != "target" {
+                return Err(Error::new(ident.span(), "Expected `@target`"));
+            }
+            let target = input.parse()?;
+            input.parse::<Token![,]>()?;
+            Some(target)
+        } else {
+            None
+        };
+        let legacy_chat = input.parse()?;
+        Ok(TargetedChat {
+            target,
+            legacy_chat,
+        })
+    }
+}
+
+/// Resolves an integer literal or one of `mc_chat`'s `VERSION_*` constants,
+/// named as is or through `mc_chat::`.
+///
+/// A bare name could be a constant of the caller's own, so the expansion
+/// has to include [`check_version_expr()`] for it.
+pub fn version_from_expr(expr: &Expr) -> syn::Result<i32> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) => {
+            let segments = path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>();
+            let name = match segments.as_slice() {
+                [name] if path.leading_colon.is_none() => name,
+                [krate, name] if krate == "mc_chat" => name,
+                _ => {
+                    return Err(Error::new_spanned(
+                        expr,
+                        "Expected a VERSION_* constant of mc_chat, like `VERSION_1_16` or `mc_chat::VERSION_1_16`",
+                    ))
+                }
+            };
+            KNOWN_VERSIONS
+                .iter()
+                .find(|(known, _)| known == name)
+                .map(|(_, version)| *version)
+                .ok_or_else(|| {
+                    Error::new_spanned(
+                        expr,
+                        "Unknown protocol version, use an integer literal or a VERSION_* constant",
+                    )
+                })
+        }
+        Expr::Group(group) => version_from_expr(&group.expr),
+        Expr::Paren(paren) => version_from_expr(&paren.expr),
+        _ => Err(Error::new_spanned(
+            expr,
+            "Expected an integer literal or a VERSION_* constant",
+        )),
+    }
+}
+
+/// A compile time check that a version named by a path really is the
+/// protocol it was resolved to, and not a constant that shadows `mc_chat`'s.
+pub fn check_version_expr(expr: &Expr, version: i32) -> TokenStream {
+    match expr {
+        Expr::Group(group) => check_version_expr(&group.expr, version),
+        Expr::Paren(paren) => check_version_expr(&paren.expr, version),
+        Expr::Path(_) => {
+            let message = format!(
+                "`{}` isn't mc_chat's protocol version {}",
+                expr.to_token_stream(),
+                version
+            );
+            quote!(
+                const _: () = ::core::assert!(#expr == #version, #message);
+            )
+        }
+        _ => TokenStream::new(),
+    }
+}
+
+/// Checks that every literal only uses features the target version supports.
+pub fn check_target(legacy_chat: &LegacyChat, version: i32) -> syn::Result<()> {
+    if version >= HEX_COLOR_VERSION {
+        return Ok(());
+    }
+    let pattern = legacy_chat.pattern.value();
+
+    let literals = legacy_chat
+        .translate
+        .iter()
+        .chain(legacy_chat.chat_parts.iter().filter_map(|part| match part {
+            ChatPart::Literal(literal) => Some(literal),
+            ChatPart::Variable(_) | ChatPart::Annotated(_) => None,
+        }));
+    let mut errors: Option<Error> = None;
+    for literal in literals {
+        let value = literal.value();
+        let index = ["#", "x", "<"]
+            .iter()
+            .filter_map(|code| find_code(&value, &pattern, code))
+            .min();
+        if let Some(index) = index {
+            let error = unsupported(literal, index..index + pattern.len() + 1, version);
+            match errors {
+                Some(ref mut errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+fn unsupported(literal: &LitStr, code: Range<usize>, version: i32) -> Error {
+    Error::new(
+        literal_subspan(literal, code.clone()),
+        format!(
+            "`{}`: hex colors and gradients need protocol version {} (1.16) or newer, the target version is {}",
+            &literal.value()[code], HEX_COLOR_VERSION, version
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn versions() {
+        assert_eq!(735, version_from_expr(&parse_quote!(VERSION_1_16)).unwrap());
+        assert_eq!(
+            47,
+            version_from_expr(&parse_quote!(mc_chat::VERSION_1_8)).unwrap()
+        );
+        assert_eq!(
+            771,
+            version_from_expr(&parse_quote!(::mc_chat::VERSION_1_21_6)).unwrap()
+        );
+        assert_eq!(340, version_from_expr(&parse_quote!(340)).unwrap());
+        assert!(version_from_expr(&parse_quote!(VERSION_2_0)).is_err());
+        assert!(version_from_expr(&parse_quote!(foo::VERSION_1_8)).is_err());
+        assert!(version_from_expr(&parse_quote!(::VERSION_1_8)).is_err());
+        assert!(version_from_expr(&parse_quote!(mc_chat::version::VERSION_1_8)).is_err());
+    }
+
+    #[test]
+    fn shadowed_versions() {
+        let check = check_version_expr(&parse_quote!(VERSION_1_16), 735).to_string();
+        assert!(check.contains("VERSION_1_16 == 735i32"));
+        assert!(check_version_expr(&parse_quote!(735), 735).is_empty());
+    }
+
+    #[test]
+    fn parse_target() {
+        let chat: TargetedChat = parse_quote!(@target VERSION_1_8, "§", "Hello");
+        assert!(chat.target.is_some());
+        let chat: TargetedChat = parse_quote!("§", "Hello");
+        assert!(chat.target.is_none());
+    }
+
+    #[test]
+    fn hex_colors() {
+        let chat: LegacyChat = parse_quote!("§", "§#ff0000Hello ", name, "§x§0§0§0§0§0§0!");
+        let error = check_target(&chat, 47).unwrap_err();
+        assert_eq!(2, error.into_iter().count());
+        assert!(check_target(&chat, 735).is_ok());
+    }
+
+    #[test]
+    fn gradients_and_translations() {
+        let chat: LegacyChat = parse_quote!("§", "§<#ff0000:#0000ff>Hello§>");
+        assert!(check_target(&chat, 47).is_err());
+        let chat: LegacyChat = parse_quote!("§", tr "§#ff0000chat.type.text", "§aok");
+        assert!(check_target(&chat, 47).is_err());
+        let chat: LegacyChat = parse_quote!("§", "§aHello");
+        assert!(check_target(&chat, 47).is_ok());
+    }
+
+    #[test]
+    fn escaped_delimiter() {
+        let chat: LegacyChat = parse_quote!("§", "§§#ff0000 is red, §§<3");
+        assert!(check_target(&chat, 47).is_ok());
+    }
+}
@@ -1,6 +1,6 @@
 #![doc = include_str!("../README.md")]
 
-use mc_chat_core::chat_core;
+use mc_chat_core::{book_core, chat_core, chat_static_core, derive_to_chat_core};
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
 
@@ -8,3 +8,14 @@ use proc_macro_error::proc_macro_error;
 #[proc_macro]
 pub fn chat(input: TokenStream) -> TokenStream { chat_core(input.into()).into() }
 
+#[proc_macro_error]
+#[proc_macro]
+pub fn chat_static(input: TokenStream) -> TokenStream { chat_static_core(input.into()).into() }
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn book(input: TokenStream) -> TokenStream { book_core(input.into()).into() }
+
+#[proc_macro_error]
+#[proc_macro_derive(ToChat, attributes(chat))]
+pub fn derive_to_chat(input: TokenStream) -> TokenStream { derive_to_chat_core(input.into()).into() }
@@ -0,0 +1,347 @@
+use std::fmt::{Display, Formatter};
+
+use crate::metrics::{FontMetrics, BOOK_LINES, BOOK_WIDTH};
+use crate::paginate::{paginate, text_length};
+use crate::{freeze::FrozenStr, Chat, ClickEvent, ComponentKind};
+
+#[cfg(feature = "snbt")]
+mod serde_support;
+
+/// A written book: a title, an author and pages of [`Chat`] components.
+///
+/// With the `macros` feature enabled, the `book!` macro builds a whole
+/// book from chat literals and checks the limits below at compile time.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Book, BookGeneration, Chat, TextColor};
+///
+/// let book = Book::new("Adventures", "Steve")
+///     .generation(BookGeneration::CopyOfOriginal)
+///     .page(Chat::text("Chapter 1").bold(true))
+///     .page(Chat::text("The end.").color(TextColor::Gray));
+/// assert_eq!(2, book.pages.len());
+/// assert!(book.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Book {
+    pub title: FrozenStr,
+    pub author: FrozenStr,
+    pub generation: BookGeneration,
+    pub pages: Vec<Chat>,
+}
+
+/// How many times a written book has been copied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BookGeneration {
+    #[default]
+    Original,
+    CopyOfOriginal,
+    CopyOfCopy,
+    Tattered,
+}
+
+impl BookGeneration {
+    /// The value of the `generation` tag.
+    pub fn id(self) -> i32 {
+        match self {
+            BookGeneration::Original => 0,
+            BookGeneration::CopyOfOriginal => 1,
+            BookGeneration::CopyOfCopy => 2,
+            BookGeneration::Tattered => 3,
+        }
+    }
+}
+
+impl Book {
+    /// The maximum amount of pages in a written book.
+    pub const MAX_PAGES: usize = 100;
+    /// The maximum amount of characters on a single page.
+    pub const MAX_PAGE_LENGTH: usize = 256;
+    /// The maximum amount of characters in the title.
+    pub const MAX_TITLE_LENGTH: usize = 32;
+
+    /// Creates an original book without any pages.
+    pub fn new<T: Into<FrozenStr>, U: Into<FrozenStr>>(title: T, author: U) -> Self {
+        Self {
+            title: title.into(),
+            author: author.into(),
+            generation: BookGeneration::Original,
+            pages: Vec::new(),
+        }
+    }
+
+    /// Change the generation of this book.
+    pub fn generation(mut self, generation: BookGeneration) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    /// Adds a page at the end of the book.
+    pub fn page<C: Into<Chat>>(mut self, page: C) -> Self {
+        self.pages.push(page.into());
+        self
+    }
+
+    /// Splits a component of any length into pages, wrapping words at the
+    /// width of a book page and starting a new page after 14 lines or
+    /// [`Book::MAX_PAGE_LENGTH`] characters.
+    ///
+    /// Every page is an empty text component with the text as children, which
+    /// carry the complete style of the text so nothing gets lost at a page break.
+    /// Components other than text are never split and are measured by their raw
+    /// contents, e.g. the translation key, which is only an estimate.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::metrics::FontMetrics;
+    /// use mc_chat::{Book, Chat, TextColor};
+    ///
+    /// struct Monospace;
+    ///
+    /// impl FontMetrics for Monospace {
+    ///     fn advance(&self, _: char, bold: bool) -> u32 {
+    ///         if bold { 7 } else { 6 }
+    ///     }
+    /// }
+    ///
+    /// let story = Chat::text("Once upon a time... ".repeat(40)).color(TextColor::DarkBlue);
+    /// let mut book = Book::new("Adventures", "Steve");
+    /// book.pages = Book::paginate(&story, &Monospace);
+    /// assert_eq!(3, book.pages.len());
+    /// assert_eq!(Some(TextColor::DarkBlue), book.pages[2].children[0].style.color);
+    /// ```
+    pub fn paginate<M: FontMetrics + ?Sized>(chat: &Chat, metrics: &M) -> Vec<Chat> {
+        paginate(chat, metrics, BOOK_WIDTH, BOOK_LINES, Self::MAX_PAGE_LENGTH)
+    }
+
+    /// Checks the title, the amount of pages and the text on every page
+    /// against the limits of a written book.
+    ///
+    /// Only the text of text components (including translation arguments)
+    /// counts towards the length of a page. Links to other pages need to
+    /// point to a page of this book.
+    pub fn validate(&self) -> Result<(), BookErr> {
+        let title_length = self.title.chars().count();
+        if title_length > Self::MAX_TITLE_LENGTH {
+            return Err(BookErr::TitleTooLong(title_length));
+        }
+        if self.pages.len() > Self::MAX_PAGES {
+            return Err(BookErr::TooManyPages(self.pages.len()));
+        }
+        for (page, chat) in self.pages.iter().enumerate() {
+            let length = text_length(chat);
+            if length > Self::MAX_PAGE_LENGTH {
+                return Err(BookErr::PageTooLong { page, length });
+            }
+            let pages = self.pages.len() as u32;
+            if let Some(target) = find_page_link(chat, &|target| !(1..=pages).contains(&target)) {
+                return Err(BookErr::InvalidPageLink { page, target });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The first `change_page` click event of the component for which `matches` is true.
+fn find_page_link(chat: &Chat, matches: &dyn Fn(u32) -> bool) -> Option<u32> {
+    if let Some(ClickEvent::ChangePage(target)) = chat.style.click_event {
+        if matches(target) {
+            return Some(target);
+        }
+    }
+    let nested = match chat.kind {
+        ComponentKind::Translation(ref translation) => Some(translation.components()),
+        _ => None,
+    };
+    nested
+        .into_iter()
+        .flatten()
+        .chain(&chat.children)
+        .find_map(|child| find_page_link(child, matches))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BookErr {
+    TitleTooLong(usize),
+    TooManyPages(usize),
+    /// The index of the page and its length.
+    PageTooLong {
+        page: usize,
+        length: usize,
+    },
+    /// The index of the page and the page number it links to.
+    InvalidPageLink {
+        page: usize,
+        target: u32,
+    },
+    #[cfg(feature = "snbt")]
+    Nbt(String),
+}
+
+impl Display for BookErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookErr::TitleTooLong(length) => write!(
+                f,
+                "the title is {} characters long, the limit is {}",
+                length,
+                Book::MAX_TITLE_LENGTH
+            ),
+            BookErr::TooManyPages(pages) => write!(
+                f,
+                "the book has {} pages, the limit is {}",
+                pages,
+                Book::MAX_PAGES
+            ),
+            BookErr::PageTooLong { page, length } => write!(
+                f,
+                "page {} has {} characters, the limit is {}",
+                page + 1,
+                length,
+                Book::MAX_PAGE_LENGTH
+            ),
+            BookErr::InvalidPageLink { page, target } => write!(
+                f,
+                "page {} links to page {}, which doesn't exist",
+                page + 1,
+                target
+            ),
+            #[cfg(feature = "snbt")]
+            BookErr::Nbt(err) => write!(f, "could not serialize the book: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for BookErr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TextColor, TranslationComponent};
+
+    struct Monospace;
+
+    impl FontMetrics for Monospace {
+        fn advance(&self, _: char, bold: bool) -> u32 {
+            if bold {
+                7
+            } else {
+                6
+            }
+        }
+    }
+
+    fn page_text(page: &Chat) -> String {
+        page.children
+            .iter()
+            .map(|child| match child.kind {
+                ComponentKind::Text(ref text) => text.text.to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn page_links() {
+        let book = Book::new("Contents", "Steve")
+            .page(Chat::text("Go to ").child(Chat::text("2").click(ClickEvent::try_page(2u32, 2))))
+            .page(Chat::text("Back").click(Some(ClickEvent::page(1u32))));
+        assert_eq!(Ok(()), book.validate());
+
+        let book = book
+            .page(Chat::component(TranslationComponent::new("%s").argument(
+                Chat::text("?").click(Some(ClickEvent::page(4u32))),
+            )));
+        assert_eq!(
+            Err(BookErr::InvalidPageLink { page: 2, target: 4 }),
+            book.validate()
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let long = "a".repeat(Book::MAX_PAGE_LENGTH);
+        let book = Book::new("Long", "Steve").page(Chat::text(&long[1..]).child(Chat::text("b")));
+        assert_eq!(Ok(()), book.validate());
+
+        let book = book.page(Chat::component(
+            TranslationComponent::new("chat.type.text")
+                .argument(Chat::text(&*long).child(Chat::text("!"))),
+        ));
+        assert_eq!(
+            Err(BookErr::PageTooLong {
+                page: 1,
+                length: Book::MAX_PAGE_LENGTH + 1
+            }),
+            book.validate()
+        );
+
+        let mut book = Book::new("Pages", "Steve");
+        book.pages = vec![Chat::text("Page"); Book::MAX_PAGES + 1];
+        assert_eq!(Err(BookErr::TooManyPages(101)), book.validate());
+
+        let book = Book::new("a".repeat(Book::MAX_TITLE_LENGTH + 1), "Steve");
+        assert_eq!(Err(BookErr::TitleTooLong(33)), book.validate());
+    }
+
+    #[test]
+    fn paginate_lines() {
+        let text = (1..=20)
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let pages = Book::paginate(&Chat::text(text), &Monospace);
+        assert_eq!(2, pages.len());
+        assert_eq!(
+            (1..=14)
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            page_text(&pages[0])
+        );
+        assert_eq!("15\n16\n17\n18\n19\n20", page_text(&pages[1]));
+    }
+
+    #[test]
+    fn paginate_wrapping() {
+        // 19 characters fit on a line, the space at the line break is consumed
+        let line = "aaaaaaaaa bbbbbbbbb";
+        let pages = Book::paginate(&Chat::text(format!("{} {}", line, line)), &Monospace);
+        assert_eq!(1, pages.len());
+        assert_eq!(format!("{}{}", line, line), page_text(&pages[0]));
+
+        let words = "word ".repeat(200);
+        let pages = Book::paginate(&Chat::text(&*words), &Monospace);
+        let mut book = Book::new("Words", "Steve");
+        book.pages = pages;
+        assert_eq!(Ok(()), book.validate());
+        assert_eq!(
+            words.replace(' ', ""),
+            book.pages
+                .iter()
+                .map(page_text)
+                .collect::<String>()
+                .replace(' ', "")
+        );
+    }
+
+    #[test]
+    fn paginate_styles() {
+        let chat = Chat::text("Title ")
+            .color(TextColor::Red)
+            .child(Chat::text("bold ".repeat(100)).bold(true))
+            .child(Chat::translate("item.minecraft.book"));
+        let pages = Book::paginate(&chat, &Monospace);
+        assert_eq!(3, pages.len());
+        let first = &pages[0].children;
+        assert_eq!(Some(TextColor::Red), first[0].style.color);
+        assert_eq!(None, first[0].style.bold);
+        let second = &pages[1].children[0];
+        assert_eq!(Some(TextColor::Red), second.style.color);
+        assert_eq!(Some(true), second.style.bold);
+        let last = pages[2].children.last().unwrap();
+        assert!(matches!(last.kind, ComponentKind::Translation(_)));
+        assert_eq!(Some(TextColor::Red), last.style.color);
+    }
+}
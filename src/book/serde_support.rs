@@ -0,0 +1,112 @@
+use serde::Serialize;
+
+use crate::component::serde_support::{SerializeChat, SerializeCtx};
+use crate::freeze::FrozenStr;
+use crate::{ProtocolVersion, SerializeOpts};
+
+use super::{Book, BookErr};
+
+/// The `written_book_content` item component, the `tag` of the item before it.
+#[derive(Serialize)]
+struct SerializeBook<'a, T, P> {
+    title: T,
+    author: &'a FrozenStr,
+    generation: i32,
+    resolved: bool,
+    pages: Vec<P>,
+}
+
+/// Text that can be filtered for players with chat filtering enabled.
+#[derive(Serialize)]
+struct Filterable<T> {
+    raw: T,
+}
+
+impl Book {
+    /// Serialize this book to SNBT after checking it with [`Book::validate()`].
+    ///
+    /// Before 1.20.5 (protocol 766), this is the `tag` of a `minecraft:written_book`
+    /// item with every page as a JSON string. Since then, it's the value of the
+    /// `minecraft:written_book_content` component. Since 1.21.5 (protocol 770),
+    /// pages are written as NBT instead of JSON.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Book, Chat};
+    ///
+    /// let book = Book::new("Adventures", "Steve").page(Chat::text("The end."));
+    /// let tag = book.to_nbt(340).unwrap();
+    /// let component = book.to_nbt(766).unwrap();
+    /// ```
+    pub fn to_nbt<V: Into<ProtocolVersion>>(&self, version: V) -> Result<String, BookErr> {
+        self.validate()?;
+        let version = version.into();
+        let result = if version < ProtocolVersion::V1_20_5 {
+            let pages = self
+                .pages
+                .iter()
+                .map(|page| page.serialize_str(version))
+                .collect::<serde_json::Result<Vec<_>>>()
+                .map_err(|err| BookErr::Nbt(err.to_string()))?;
+            fastsnbt::to_string(&self.serializable(&self.title, pages))
+        } else if version < ProtocolVersion::V1_21_5 {
+            let pages = self
+                .pages
+                .iter()
+                .map(|page| page.serialize_str(version).map(|raw| Filterable { raw }))
+                .collect::<serde_json::Result<Vec<_>>>()
+                .map_err(|err| BookErr::Nbt(err.to_string()))?;
+            fastsnbt::to_string(&self.serializable(Filterable { raw: &self.title }, pages))
+        } else {
+            let opts = SerializeOpts::default();
+            let ctx = SerializeCtx {
+                version,
+                opts: &opts,
+            };
+            let pages = self
+                .pages
+                .iter()
+                .map(|page| Filterable {
+                    raw: SerializeChat::new(&ctx, page),
+                })
+                .collect();
+            fastsnbt::to_string(&self.serializable(Filterable { raw: &self.title }, pages))
+        };
+        result.map_err(|err| BookErr::Nbt(err.to_string()))
+    }
+
+    fn serializable<T, P>(&self, title: T, pages: Vec<P>) -> SerializeBook<'_, T, P> {
+        SerializeBook {
+            title,
+            author: &self.author,
+            generation: self.generation.id(),
+            resolved: true,
+            pages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Book, BookErr, BookGeneration, Chat};
+
+    #[test]
+    fn nbt() {
+        let book = Book::new("Adventures", "Steve")
+            .generation(BookGeneration::CopyOfCopy)
+            .page(Chat::text("The end."));
+        let tag = book.to_nbt(340).unwrap();
+        assert!(tag.contains("Adventures"));
+        assert!(tag.contains(r#"{\"text\":\"The end.\"}"#));
+
+        let component = book.to_nbt(766).unwrap();
+        assert!(component.contains("raw"));
+        assert!(component.contains(r#"{\"text\":\"The end.\"}"#));
+
+        let component = book.to_nbt(770).unwrap();
+        assert!(!component.contains(r#"\"text\""#));
+
+        let book = Book::new("a".repeat(Book::MAX_TITLE_LENGTH + 1), "Steve");
+        assert_eq!(Err(BookErr::TitleTooLong(33)), book.to_nbt(340));
+    }
+}
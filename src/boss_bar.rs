@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter};
+
+use crate::metrics::{FontMetrics, BOSS_BAR_WIDTH};
+use crate::resolve::for_each_mut;
+use crate::Chat;
+#[cfg(feature = "serde")]
+use crate::ProtocolVersion;
+
+/// The title of a boss bar, since 1.9.
+///
+/// The client doesn't react to clicking or hovering over a boss bar, so
+/// click events, hover events and insertions are removed from the title.
+///
+/// # Example
+/// ```
+/// use mc_chat::metrics::DefaultFont;
+/// use mc_chat::{BossBarName, Chat, ClickEvent, TextColor};
+///
+/// let name = BossBarName::new(
+///     Chat::text("Ender Dragon")
+///         .color(TextColor::Purple)
+///         .click(Some(ClickEvent::command("/kill"))),
+/// );
+/// assert_eq!(&Chat::text("Ender Dragon").color(TextColor::Purple), name.name());
+/// assert!(name.validate(&DefaultFont).is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BossBarName {
+    name: Chat,
+}
+
+impl BossBarName {
+    pub fn new<C: Into<Chat>>(name: C) -> Self {
+        let mut name = name.into();
+        for_each_mut(&mut name, &mut |chat| {
+            chat.style.click_event = None;
+            chat.style.hover_event = None;
+            chat.style.insertion = None;
+        });
+        Self { name }
+    }
+
+    pub fn name(&self) -> &Chat {
+        &self.name
+    }
+
+    /// Checks that the title, measured with the given font, isn't wider
+    /// than the bar below it.
+    pub fn validate<M: FontMetrics + ?Sized>(&self, metrics: &M) -> Result<(), BossBarErr> {
+        let width = metrics.chat_width(&self.name);
+        if width > BOSS_BAR_WIDTH {
+            return Err(BossBarErr::TooWide(width));
+        }
+        Ok(())
+    }
+
+    /// Serialize the title for the boss bar packet, see
+    /// [`Chat::serialize_str()`].
+    #[cfg(feature = "serde")]
+    pub fn serialize_str<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<String> {
+        self.name.serialize_str(version)
+    }
+}
+
+impl From<BossBarName> for Chat {
+    fn from(name: BossBarName) -> Self {
+        name.name
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BossBarErr {
+    /// The width of the title in pixels.
+    TooWide(u32),
+}
+
+impl Display for BossBarErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BossBarErr::TooWide(width) => write!(
+                f,
+                "the title is {} pixels wide, the limit is {}",
+                width, BOSS_BAR_WIDTH
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BossBarErr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HoverEvent, TranslationComponent};
+
+    #[test]
+    fn strips_events() {
+        let hover = Some(HoverEvent::ShowText(Box::new(Chat::text("tip"))));
+        let name = BossBarName::new(Chat::component(
+            TranslationComponent::new("%s").argument(Chat::text("a").hover(hover)),
+        ));
+        assert_eq!(
+            &Chat::component(TranslationComponent::new("%s").argument(Chat::text("a"))),
+            name.name()
+        );
+        let name = BossBarName::new(Chat::text("W".repeat(40)));
+        assert_eq!(
+            Err(BossBarErr::TooWide(240)),
+            name.validate(&crate::metrics::DefaultFont)
+        );
+    }
+}
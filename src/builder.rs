@@ -0,0 +1,183 @@
+use crate::legacy::{parse_format, FormatPart, LegacyParseErr};
+use crate::{Chat, ClickEvent, ComponentKind, HoverEvent, Style, TextColor, TextComponent};
+
+/// Builds a [`Chat`] component piece by piece, for code that produces
+/// text incrementally like parsers and bridges to other chat formats.
+///
+/// Text is added with the builder's current style, which only changes
+/// when it's set and applies to everything pushed after it. Text pushed
+/// with the same style ends up in the same component.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, ChatBuilder, TextColor};
+///
+/// let mut builder = ChatBuilder::new();
+/// builder.set_color(TextColor::Gray).push_str("[").push_str("Server");
+/// builder.reset().push_str("] ");
+/// builder.push_legacy("§aWelcome!", "§").unwrap();
+/// assert_eq!(
+///     Chat::text("")
+///         .child(Chat::text("[Server").color(TextColor::Gray))
+///         .child(Chat::text("] "))
+///         .child(Chat::text("Welcome!").color(TextColor::Green)),
+///     builder.build()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChatBuilder {
+    children: Vec<Chat>,
+    style: Style,
+}
+
+impl ChatBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The style of the text pushed next.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    pub fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    pub fn set_style(&mut self, style: Style) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Clears the current style, like `§r`.
+    pub fn reset(&mut self) -> &mut Self {
+        self.style = Style::default();
+        self
+    }
+
+    pub fn set_color<I: Into<Option<TextColor>>>(&mut self, color: I) -> &mut Self {
+        self.style.color(color);
+        self
+    }
+
+    pub fn set_bold<I: Into<Option<bool>>>(&mut self, bold: I) -> &mut Self {
+        self.style.bold(bold);
+        self
+    }
+
+    pub fn set_italic<I: Into<Option<bool>>>(&mut self, italic: I) -> &mut Self {
+        self.style.italic(italic);
+        self
+    }
+
+    pub fn set_underlined<I: Into<Option<bool>>>(&mut self, underlined: I) -> &mut Self {
+        self.style.underlined(underlined);
+        self
+    }
+
+    pub fn set_strikethrough<I: Into<Option<bool>>>(&mut self, strikethrough: I) -> &mut Self {
+        self.style.strikethrough(strikethrough);
+        self
+    }
+
+    pub fn set_obfuscated<I: Into<Option<bool>>>(&mut self, obfuscated: I) -> &mut Self {
+        self.style.obfuscated(obfuscated);
+        self
+    }
+
+    pub fn set_click(&mut self, click_event: Option<ClickEvent>) -> &mut Self {
+        self.style.click(click_event);
+        self
+    }
+
+    pub fn set_hover(&mut self, hover_event: Option<HoverEvent>) -> &mut Self {
+        self.style.hover(hover_event);
+        self
+    }
+
+    /// Adds text with the current style.
+    pub fn push_str(&mut self, text: &str) -> &mut Self {
+        if text.is_empty() {
+            return self;
+        }
+        if let Some(last) = self.children.last_mut() {
+            if let ComponentKind::Text(ref mut previous) = last.kind {
+                if last.children.is_empty() && last.style == self.style {
+                    *previous = TextComponent::new(format!("{}{}", &*previous.text, text));
+                    return self;
+                }
+            }
+        }
+        let mut chat = Chat::text(text.to_string());
+        chat.style = self.style.clone();
+        self.children.push(chat);
+        self
+    }
+
+    /// Adds a component as is, the current style doesn't apply to it.
+    pub fn push_child<C: Into<Chat>>(&mut self, child: C) -> &mut Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// Adds legacy formatted text, parsed like [`parse_format()`] except that
+    /// braces are plain text. Its formatting codes start from the current style
+    /// and don't change it.
+    pub fn push_legacy(&mut self, text: &str, pattern: &str) -> Result<&mut Self, LegacyParseErr> {
+        let escaped = text.replace('{', "{{").replace('}', "}}");
+        let mut chat = parse_format(pattern, vec![FormatPart::Literal(&escaped)])?;
+        chat.style = chat.style.inherit(&self.style);
+        self.children.push(chat);
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Finishes the component: an empty text component
+    /// with everything that was pushed as children.
+    pub fn build(self) -> Chat {
+        let mut chat = Chat::text("");
+        chat.children = self.children;
+        chat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_text() {
+        let mut builder = ChatBuilder::new();
+        builder
+            .push_str("a")
+            .push_str("")
+            .set_bold(true)
+            .push_str("b")
+            .push_str("c")
+            .push_child(Chat::text("d").bold(true))
+            .push_str("e");
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("a"))
+                .child(Chat::text("bc").bold(true))
+                .child(Chat::text("de").bold(true)),
+            builder.build()
+        );
+    }
+
+    #[test]
+    fn legacy() {
+        let mut builder = ChatBuilder::new();
+        builder.set_color(TextColor::Red);
+        builder.push_legacy("{a} §lb", "&").unwrap();
+        assert!(builder.push_legacy("§", "§").is_err());
+        assert_eq!(Some(TextColor::Red), builder.style().color);
+        assert_eq!(
+            Chat::text("").child(Chat::text("{a} §lb").color(TextColor::Red)),
+            builder.build()
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::TextColor;
+
+impl TextColor {
+    /// How different two colors look, as the CIEDE2000 color difference.
+    ///
+    /// A difference below 1 isn't noticeable, black and white are 100 apart.
+    /// `None` if either color is [`TextColor::Reset`].
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::TextColor;
+    ///
+    /// let orange = TextColor::from_rgb(0xff, 0xaa, 0x10);
+    /// assert!(orange.distance(&TextColor::Gold).unwrap() < 2.0);
+    /// assert!(orange.distance(&TextColor::Blue).unwrap() > 50.0);
+    /// ```
+    pub fn distance(&self, other: &TextColor) -> Option<f64> {
+        Some(ciede2000(lab(self.rgb()?), lab(other.rgb()?)))
+    }
+
+    /// The contrast ratio between two colors as defined by WCAG, from 1 for
+    /// the same luminance up to 21 for black and white.
+    pub fn contrast(&self, other: &TextColor) -> Option<f64> {
+        let mut first = luminance(self.rgb()?);
+        let mut second = luminance(other.rgb()?);
+        if first < second {
+            std::mem::swap(&mut first, &mut second);
+        }
+        Some((first + 0.05) / (second + 0.05))
+    }
+
+    /// The contrast ratio of text in this color against the chat background.
+    ///
+    /// The background is translucent black, what's behind it is ignored.
+    /// WCAG asks for a ratio of at least 4.5 for readable text.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::TextColor;
+    ///
+    /// let readable = |color: &TextColor| color.chat_contrast().unwrap_or(0.0) >= 4.5;
+    /// assert!(readable(&TextColor::Yellow));
+    /// assert!(!readable(&TextColor::DarkBlue));
+    /// assert!(!readable(&TextColor::from_rgb(0x30, 0x20, 0x20)));
+    /// ```
+    pub fn chat_contrast(&self) -> Option<f64> {
+        self.contrast(&TextColor::Black)
+    }
+
+    /// The color the client draws the shadow of text in this color with,
+    /// every channel divided by 4.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::TextColor;
+    ///
+    /// assert_eq!(Some(TextColor::from_rgb(0x3f, 0x3f, 0x15)), TextColor::Yellow.shadow());
+    /// assert_eq!(Some(TextColor::from_rgb(0x3f, 0x20, 0x04)), TextColor::from_rgb(0xff, 0x80, 0x10).shadow());
+    /// ```
+    pub fn shadow(&self) -> Option<TextColor> {
+        let [r, g, b] = self.rgb()?;
+        Some(TextColor::from_rgb(r / 4, g / 4, b / 4))
+    }
+}
+
+/// The sRGB channel without gamma correction, from 0 to 1.
+fn linear(channel: u8) -> f64 {
+    let channel = channel as f64 / 255.0;
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn luminance([r, g, b]: [u8; 3]) -> f64 {
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// The color in CIELAB with a D65 white point.
+fn lab([r, g, b]: [u8; 3]) -> [f64; 3] {
+    let (r, g, b) = (linear(r), linear(g), linear(b));
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// The hue angle in degrees, from 0 to 360.
+fn hue(a: f64, b: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    }
+}
+
+fn ciede2000([l1, a1, b1]: [f64; 3], [l2, a2, b2]: [f64; 3]) -> f64 {
+    let pow7 = |c: f64| c.powi(7) / (c.powi(7) + 25f64.powi(7));
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - pow7(c_mean).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 > h1 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t =
+        1.0 - 0.17 * cos(h_mean - 30.0) + 0.24 * cos(2.0 * h_mean) + 0.32 * cos(3.0 * h_mean + 6.0)
+            - 0.20 * cos(4.0 * h_mean - 63.0);
+    let rotation = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_t = -2.0 * pow7(c_mean).sqrt() * (2.0 * rotation).to_radians().sin();
+    let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_values() {
+        // pairs from the test data of Sharma, Wu and Dalal
+        let cases = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+        ];
+        for (first, second, expected) in cases.iter() {
+            assert!((ciede2000(*first, *second) - expected).abs() < 1e-4);
+            assert!((ciede2000(*second, *first) - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn colors() {
+        let black_white = TextColor::Black.distance(&TextColor::White).unwrap();
+        assert!((black_white - 100.0).abs() < 0.01);
+        assert_eq!(
+            Some(0.0),
+            TextColor::Red.distance(&TextColor::from_rgb(0xff, 0x55, 0x55))
+        );
+        assert_eq!(None, TextColor::Red.distance(&TextColor::Reset));
+        let contrast = TextColor::White.chat_contrast().unwrap();
+        assert!((contrast - 21.0).abs() < 1e-9);
+        assert_eq!(
+            TextColor::Gold.contrast(&TextColor::Blue),
+            TextColor::Blue.contrast(&TextColor::Gold)
+        );
+    }
+}
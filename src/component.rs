@@ -1,9 +1,19 @@
-use crate::{freeze::FrozenStr, style::Style, ClickEvent, HoverEvent, TextColor};
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    freeze::{FrozenChat, FrozenStr},
+    style::Style,
+    ClickEvent, HoverEvent, TargetSelector, TextColor, ToChat,
+};
 
 #[cfg(feature = "serde")]
 pub(crate) mod serde_support;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+pub use serde_support::{
+    ChatParseErr, ChildrenKey, DeserializeOpts, ParseWarning, SerializeOpts, UuidFormat,
+};
 
 /// A Minecraft chat/text component.
 ///
@@ -45,6 +55,10 @@ pub struct Chat {
         serde(rename = "extra", skip_serializing_if = "Vec::is_empty", default)
     )]
     pub children: Vec<Chat>,
+    /// Values attached to this component, never serialized.
+    #[cfg(feature = "extensions")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extensions: crate::Extensions,
 }
 
 impl Chat {
@@ -66,6 +80,8 @@ impl Chat {
             kind: kind.into(),
             style: Default::default(),
             children: vec![],
+            #[cfg(feature = "extensions")]
+            extensions: Default::default(),
         }
     }
 
@@ -83,6 +99,60 @@ impl Chat {
         Chat::component(TextComponent::new(text))
     }
 
+    /// Like [`Chat::text()`] for a `&'static str`, without copying it.
+    ///
+    /// This is a `const fn`, fixed messages can be kept in a `static`,
+    /// see [`FrozenStr::from_static()`].
+    pub const fn static_text(text: &'static str) -> Self {
+        Chat::static_styled(text, Style::new())
+    }
+
+    /// Like [`Chat::static_text()`] with a style, which can be built with
+    /// [`Style::new()`] and its `with_*` methods.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, Style, TextColor};
+    ///
+    /// static RESTARTING: Chat =
+    ///     Chat::static_styled("Server restarting!", Style::new().with_color(TextColor::Red));
+    ///
+    /// assert_eq!(Chat::text("Server restarting!").color(TextColor::Red), RESTARTING);
+    /// ```
+    pub const fn static_styled(text: &'static str, style: Style) -> Self {
+        Chat {
+            kind: ComponentKind::Text(TextComponent {
+                text: FrozenStr::from_static(text),
+            }),
+            style,
+            children: Vec::new(),
+            #[cfg(feature = "extensions")]
+            extensions: crate::Extensions::new(),
+        }
+    }
+
+    /// Creates a new [`TextComponent`] from [`format_args!`].
+    ///
+    /// Arguments without placeholders are copied straight into the component,
+    /// otherwise the text is formatted once. To build many messages, a
+    /// [`TextWriter`] reuses its buffer.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Chat;
+    ///
+    /// let online = 12;
+    /// let chat = Chat::text_fmt(format_args!("{} players online", online));
+    ///
+    /// assert_eq!(Chat::text("12 players online"), chat);
+    /// ```
+    pub fn text_fmt(args: std::fmt::Arguments<'_>) -> Self {
+        match args.as_str() {
+            Some(text) => Chat::text(text),
+            None => Chat::text(args.to_string()),
+        }
+    }
+
     /// Creates a new [`TranslationComponent`].
     ///
     /// # Example
@@ -98,6 +168,31 @@ impl Chat {
         Chat::component(TranslationComponent::new(key))
     }
 
+    /// Creates a new [`TranslationComponent`] with arguments.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TextColor};
+    ///
+    /// let chat = Chat::key_with(
+    ///     "chat.type.text",
+    ///     vec![Chat::text("Steve").color(TextColor::Gold), Chat::text("Hi!")],
+    /// );
+    ///
+    /// assert_eq!(
+    ///     r#"{"translate":"chat.type.text","with":[{"text":"Steve","color":"gold"},{"text":"Hi!"}]}"#,
+    ///     chat.serialize_str(47).unwrap()
+    /// );
+    /// ```
+    pub fn key_with<T, I, C>(key: T, args: I) -> Self
+    where
+        T: Into<FrozenStr>,
+        I: IntoIterator<Item = C>,
+        C: Into<TranslationArg>,
+    {
+        Chat::component(TranslationComponent::new(key).args(args))
+    }
+
     /// Creates a new [`ScoreComponent`].
     ///
     /// # Example
@@ -145,8 +240,86 @@ impl Chat {
         Chat::component(KeybindComponent::new(keybind))
     }
 
+    /// Creates a new [`NbtComponent`] that reads from command storage.
+    ///
+    /// Storage components were added in 1.15, older versions get an empty
+    /// text component.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Chat;
+    ///
+    /// let chat = Chat::storage("mypack:messages", "greeting");
+    ///
+    /// assert_eq!("{\"nbt\":\"greeting\",\"storage\":\"mypack:messages\"}", chat.serialize_str(573).unwrap());
+    /// ```
+    pub fn storage<T, U>(id: T, nbt: U) -> Self
+    where
+        T: Into<FrozenStr>,
+        U: Into<FrozenStr>,
+    {
+        Chat::component(NbtComponent::storage(id, nbt))
+    }
+
+    /// Creates an empty text component with a text child for every segment,
+    /// the usual output of parsers for other formats.
+    ///
+    /// Segments are kept as they are, [`Chat::merge_adjacent()`] merges the
+    /// ones with the same style.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, Style};
+    ///
+    /// let mut bold = Style::new();
+    /// bold.bold(true);
+    /// let chat = Chat::from_segments(vec![("Hello ", Style::new()), ("world", bold)]);
+    /// assert_eq!(
+    ///     Chat::text("")
+    ///         .child(Chat::text("Hello "))
+    ///         .child(Chat::text("world").bold(true)),
+    ///     chat
+    /// );
+    /// ```
+    pub fn from_segments<I, T>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = (T, Style)>,
+        T: Into<FrozenStr>,
+    {
+        let mut chat = Chat::text("");
+        chat.children = segments
+            .into_iter()
+            .map(|(text, style)| {
+                let mut child = Chat::text(text);
+                child.style = style;
+                child
+            })
+            .collect();
+        chat
+    }
+
+    /// Freezes this component into a cheaply cloneable, shared [`FrozenChat`].
+    ///
+    /// The whole tree is put behind a single reference count, strings inside
+    /// of it stay owned. Use [`FrozenChat::thaw()`] to get an owned [`Chat`] back.
+    /// See the [`freeze`](crate::freeze) module for more details.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Chat;
+    ///
+    /// let frozen = Chat::text("Welcome!").freeze();
+    /// assert_eq!(r#"{"text":"Welcome!"}"#, frozen.serialize_str(47).unwrap());
+    /// ```
+    pub fn freeze(self) -> FrozenChat {
+        FrozenChat::from(self)
+    }
+
     /// Adds a child component to this chat component.
     ///
+    /// Besides a [`Chat`], a reference to anything implementing
+    /// [`ToChat`](crate::ToChat) can be added as a child.
+    ///
     /// # Example
     /// ```
     /// use mc_chat::{Chat, TextColor};
@@ -155,43 +328,158 @@ impl Chat {
     ///     .color(TextColor::Green)
     ///     .child(Chat::text(" text will also be green."));
     /// ```
-    pub fn child(mut self, child: Chat) -> Self {
-        self.children.push(child);
+    pub fn child<C: Into<Chat>>(mut self, child: C) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    /// The [`TextComponent`] of this component, if it is one.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Chat;
+    ///
+    /// let mut chat = Chat::text("Hello");
+    /// if let Some(text) = chat.as_text_mut() {
+    ///     text.text = "Bye".into();
+    /// }
+    /// assert_eq!("Bye", &*chat.as_text().unwrap().text);
+    /// assert!(chat.as_keybind().is_none());
+    /// ```
+    pub fn as_text(&self) -> Option<&TextComponent> {
+        match self.kind {
+            ComponentKind::Text(ref component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// See [`Chat::as_text()`].
+    pub fn as_text_mut(&mut self) -> Option<&mut TextComponent> {
+        match self.kind {
+            ComponentKind::Text(ref mut component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// The [`TranslationComponent`] of this component, if it is one.
+    pub fn as_translation(&self) -> Option<&TranslationComponent> {
+        match self.kind {
+            ComponentKind::Translation(ref component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// See [`Chat::as_translation()`].
+    pub fn as_translation_mut(&mut self) -> Option<&mut TranslationComponent> {
+        match self.kind {
+            ComponentKind::Translation(ref mut component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// The [`ScoreComponent`] of this component, if it is one.
+    pub fn as_score(&self) -> Option<&ScoreComponent> {
+        match self.kind {
+            ComponentKind::Score(ref component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// See [`Chat::as_score()`].
+    pub fn as_score_mut(&mut self) -> Option<&mut ScoreComponent> {
+        match self.kind {
+            ComponentKind::Score(ref mut component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// The [`SelectorComponent`] of this component, if it is one.
+    pub fn as_selector(&self) -> Option<&SelectorComponent> {
+        match self.kind {
+            ComponentKind::Selector(ref component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// See [`Chat::as_selector()`].
+    pub fn as_selector_mut(&mut self) -> Option<&mut SelectorComponent> {
+        match self.kind {
+            ComponentKind::Selector(ref mut component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// The [`KeybindComponent`] of this component, if it is one.
+    pub fn as_keybind(&self) -> Option<&KeybindComponent> {
+        match self.kind {
+            ComponentKind::Keybind(ref component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// See [`Chat::as_keybind()`].
+    pub fn as_keybind_mut(&mut self) -> Option<&mut KeybindComponent> {
+        match self.kind {
+            ComponentKind::Keybind(ref mut component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// The [`NbtComponent`] of this component, if it is one.
+    pub fn as_nbt(&self) -> Option<&NbtComponent> {
+        match self.kind {
+            ComponentKind::Nbt(ref component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// See [`Chat::as_nbt()`].
+    pub fn as_nbt_mut(&mut self) -> Option<&mut NbtComponent> {
+        match self.kind {
+            ComponentKind::Nbt(ref mut component) => Some(component),
+            _ => None,
+        }
+    }
+
+    /// Attaches a value to this component, see [`Extensions`](crate::Extensions).
+    #[cfg(feature = "extensions")]
+    pub fn extension<T: Clone + Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
         self
     }
 
     /// See [`Style`].
-    pub fn color(mut self, color: TextColor) -> Self {
+    pub fn color<I: Into<Option<TextColor>>>(mut self, color: I) -> Self {
         self.style.color(color);
         self
     }
 
     /// See [`Style`].
-    pub fn bold(mut self, bold: bool) -> Self {
+    pub fn bold<I: Into<Option<bool>>>(mut self, bold: I) -> Self {
         self.style.bold(bold);
         self
     }
 
     /// See [`Style`].
-    pub fn italic(mut self, italic: bool) -> Self {
+    pub fn italic<I: Into<Option<bool>>>(mut self, italic: I) -> Self {
         self.style.italic(italic);
         self
     }
 
     /// See [`Style`].
-    pub fn underlined(mut self, underlined: bool) -> Self {
+    pub fn underlined<I: Into<Option<bool>>>(mut self, underlined: I) -> Self {
         self.style.underlined(underlined);
         self
     }
 
     /// See [`Style`].
-    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+    pub fn strikethrough<I: Into<Option<bool>>>(mut self, strikethrough: I) -> Self {
         self.style.strikethrough(strikethrough);
         self
     }
 
     /// See [`Style`].
-    pub fn obfuscated(mut self, obfuscated: bool) -> Self {
+    pub fn obfuscated<I: Into<Option<bool>>>(mut self, obfuscated: I) -> Self {
         self.style.obfuscated(obfuscated);
         self
     }
@@ -221,12 +509,73 @@ impl Chat {
     }
 }
 
+/// A borrowed view of a component, for text that's stored in other
+/// structures than a [`Chat`]. It serializes exactly like a [`Chat`] with
+/// the same parts would.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, ChatRef, Style, TextComponent, VERSION_1_16};
+///
+/// struct Line {
+///     text: mc_chat::ComponentKind,
+///     style: Style,
+/// }
+///
+/// let mut style = Style::new();
+/// style.bold(true);
+/// let line = Line {
+///     text: TextComponent::new("Hi").into(),
+///     style,
+/// };
+/// let view = ChatRef::new(&line.text, &line.style, &[]);
+/// assert_eq!(
+///     Chat::text("Hi").bold(true).serialize_str(VERSION_1_16).unwrap(),
+///     view.serialize_str(VERSION_1_16).unwrap()
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChatRef<'a> {
+    pub kind: &'a ComponentKind,
+    pub style: &'a Style,
+    pub children: &'a [Chat],
+}
+
+impl<'a> ChatRef<'a> {
+    pub fn new(kind: &'a ComponentKind, style: &'a Style, children: &'a [Chat]) -> Self {
+        ChatRef {
+            kind,
+            style,
+            children,
+        }
+    }
+
+    /// Clones the parts into an owned [`Chat`].
+    pub fn to_chat(&self) -> Chat {
+        Chat {
+            kind: self.kind.clone(),
+            style: self.style.clone(),
+            children: self.children.to_vec(),
+            #[cfg(feature = "extensions")]
+            extensions: Default::default(),
+        }
+    }
+}
+
+impl<'a> From<&'a Chat> for ChatRef<'a> {
+    fn from(chat: &'a Chat) -> Self {
+        ChatRef::new(&chat.kind, &chat.style, &chat.children)
+    }
+}
+
 /// The different kinds of components Minecraft chat messages
-/// can be made up of. One component (`storage`-component, since 1.15) is missing,
-/// further research and contributions on this would be appreciated!
+/// can be made up of.
+///
+/// A component with several content keys is read like vanilla does since
+/// 1.20.3: its `type` decides the kind if it has one, otherwise the first of
+/// `text`, `translate`, `keybind`, `score`, `selector` and `nbt`. See [`DeserializeOpts::strict`](crate::DeserializeOpts::strict)
+/// to reject those instead.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum ComponentKind {
     Text(TextComponent),
     Translation(TranslationComponent),
@@ -248,8 +597,31 @@ pub enum ComponentKind {
     /// This crate does not check any version,
     /// it is up to the user to deal with this safely!
     Keybind(KeybindComponent),
-    // TODO: research the `storage` component (since 1.15)
-    // TODO: research the `nbt` values
+    /// # Warning
+    /// Since **1.14**, the storage source since **1.15**!
+    ///
+    /// Older versions get an empty text component instead.
+    Nbt(NbtComponent),
+}
+
+impl ComponentKind {
+    /// The separator of a selector or nbt component, which is put between
+    /// the names or values it's replaced by.
+    pub fn separator(&self) -> Option<&Chat> {
+        match self {
+            ComponentKind::Selector(selector) => selector.sep.as_deref(),
+            ComponentKind::Nbt(nbt) => nbt.sep.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn separator_mut(&mut self) -> Option<&mut Chat> {
+        match self {
+            ComponentKind::Selector(selector) => selector.sep.as_deref_mut(),
+            ComponentKind::Nbt(nbt) => nbt.sep.as_deref_mut(),
+            _ => None,
+        }
+    }
 }
 
 /// Simple plain text.
@@ -291,6 +663,58 @@ impl From<TextComponent> for ComponentKind {
     }
 }
 
+/// Builds text components with [`write!`], keeping its buffer between them.
+///
+/// # Example
+/// ```
+/// use std::fmt::Write;
+/// use mc_chat::{Chat, TextWriter};
+///
+/// let mut writer = TextWriter::new();
+/// let mut messages = Vec::new();
+/// for (name, kills) in [("Steve", 3), ("Alex", 5)] {
+///     write!(writer, "{} has {} kills", name, kills).unwrap();
+///     messages.push(writer.take());
+/// }
+///
+/// assert_eq!(Chat::text("Alex has 5 kills"), messages[1]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TextWriter {
+    buffer: String,
+}
+
+impl TextWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        TextWriter {
+            buffer: String::with_capacity(capacity),
+        }
+    }
+
+    /// The text written since the last [`TextWriter::take()`].
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Creates a text component of what was written and clears the buffer.
+    pub fn take(&mut self) -> Chat {
+        let chat = Chat::text(self.buffer.as_str());
+        self.buffer.clear();
+        chat
+    }
+}
+
+impl std::fmt::Write for TextWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.buffer.push_str(s);
+        Ok(())
+    }
+}
+
 /// Substitutions based on the selected language.
 ///
 /// A key should be provided that can have possible
@@ -307,7 +731,10 @@ impl From<TextComponent> for ComponentKind {
 pub struct TranslationComponent {
     #[cfg_attr(feature = "serde", serde(rename = "translate"))]
     pub key: FrozenStr,
-    pub with: Vec<Chat>,
+    pub with: Vec<TranslationArg>,
+    /// The text shown when the client doesn't know the key, since 1.19.4.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fallback: Option<FrozenStr>,
 }
 
 impl TranslationComponent {
@@ -315,6 +742,7 @@ impl TranslationComponent {
         TranslationComponent {
             key: key.into(),
             with: vec![],
+            fallback: None,
         }
     }
 
@@ -323,12 +751,165 @@ impl TranslationComponent {
         self
     }
 
-    pub fn argument(mut self, component: Chat) -> Self {
-        self.with.push(component);
+    pub fn argument<A: Into<TranslationArg>>(mut self, argument: A) -> Self {
+        self.with.push(argument.into());
+        self
+    }
+
+    /// Sets the text shown when the client doesn't know the key.
+    ///
+    /// The fallback is only sent to 1.19.4 (protocol 762) and later, older
+    /// clients show the key.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TranslationComponent};
+    ///
+    /// let chat = Chat::component(
+    ///     TranslationComponent::new("mypack.welcome").fallback("Welcome to the server!"),
+    /// );
+    /// assert_eq!(
+    ///     r#"{"translate":"mypack.welcome","fallback":"Welcome to the server!"}"#,
+    ///     chat.serialize_str(762).unwrap()
+    /// );
+    /// assert_eq!(r#"{"translate":"mypack.welcome"}"#, chat.serialize_str(761).unwrap());
+    /// ```
+    pub fn fallback<T: Into<FrozenStr>>(mut self, fallback: T) -> Self {
+        self.fallback = Some(fallback.into());
         self
     }
+
+    /// Adds several arguments at once.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TranslationComponent};
+    ///
+    /// let component = TranslationComponent::new("death.attack.arrow")
+    ///     .args(vec!["Steve", "Skeleton"]);
+    /// assert_eq!(
+    ///     vec![&Chat::text("Steve"), &Chat::text("Skeleton")],
+    ///     component.components().collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn args<I, C>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<TranslationArg>,
+    {
+        self.with.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// The arguments that are components, skipping primitive ones.
+    pub fn components(&self) -> impl Iterator<Item = &Chat> {
+        self.with.iter().filter_map(TranslationArg::as_chat)
+    }
+
+    /// Like [`TranslationComponent::components()`], to change them.
+    pub fn components_mut(&mut self) -> impl Iterator<Item = &mut Chat> {
+        self.with.iter_mut().filter_map(TranslationArg::as_chat_mut)
+    }
+}
+
+/// An argument of a [`TranslationComponent`].
+///
+/// Besides components, vanilla accepts plain numbers and booleans as
+/// arguments, which show as their text. They're kept as they are, so they
+/// serialize the way they were read.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, TranslationArg};
+///
+/// let chat = Chat::key_with("commands.xp.success", vec![TranslationArg::from(5), "Steve".into()]);
+/// assert_eq!(
+///     r#"{"translate":"commands.xp.success","with":[5,{"text":"Steve"}]}"#,
+///     chat.serialize_str(47).unwrap()
+/// );
+/// ```
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "serde_support::TranslationArgData"))]
+pub enum TranslationArg {
+    Chat(Chat),
+    /// A number as it's written, like `5` or `-2.5`.
+    Number(FrozenStr),
+    Bool(bool),
+}
+
+impl TranslationArg {
+    pub fn as_chat(&self) -> Option<&Chat> {
+        match self {
+            TranslationArg::Chat(chat) => Some(chat),
+            _ => None,
+        }
+    }
+
+    pub fn as_chat_mut(&mut self) -> Option<&mut Chat> {
+        match self {
+            TranslationArg::Chat(chat) => Some(chat),
+            _ => None,
+        }
+    }
+}
+
+impl ToChat for TranslationArg {
+    /// The component itself, or the text of a primitive.
+    fn to_chat(&self) -> Chat {
+        match self {
+            TranslationArg::Chat(chat) => chat.clone(),
+            TranslationArg::Number(number) => Chat::text(number.clone()),
+            TranslationArg::Bool(value) => value.to_chat(),
+        }
+    }
+}
+
+impl From<Chat> for TranslationArg {
+    fn from(chat: Chat) -> Self {
+        TranslationArg::Chat(chat)
+    }
+}
+
+impl From<&str> for TranslationArg {
+    fn from(text: &str) -> Self {
+        TranslationArg::Chat(Chat::text(text))
+    }
+}
+
+impl From<String> for TranslationArg {
+    fn from(text: String) -> Self {
+        TranslationArg::Chat(Chat::text(text))
+    }
+}
+
+impl From<FrozenStr> for TranslationArg {
+    fn from(text: FrozenStr) -> Self {
+        TranslationArg::Chat(Chat::text(text))
+    }
+}
+
+impl From<bool> for TranslationArg {
+    fn from(value: bool) -> Self {
+        TranslationArg::Bool(value)
+    }
 }
 
+macro_rules! translation_arg_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for TranslationArg {
+                fn from(number: $ty) -> Self {
+                    TranslationArg::Number(number.to_string().into())
+                }
+            }
+        )*
+    };
+}
+
+translation_arg_number!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
 impl From<TranslationComponent> for ComponentKind {
     fn from(value: TranslationComponent) -> Self {
         Self::Translation(value)
@@ -355,6 +936,62 @@ impl ScoreComponent {
         }
     }
 
+    /// A score of whoever reads the component, using the name `*`.
+    ///
+    /// This is different from the `@s` selector: the client fills in its own
+    /// score, even where there's no entity to execute as, like in books or
+    /// signs. The name is serialized as `*` for every version.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, ScoreComponent};
+    ///
+    /// let chat = Chat::component(ScoreComponent::reader("deaths"));
+    /// assert_eq!(
+    ///     r#"{"score":{"name":"*","objective":"deaths"}}"#,
+    ///     chat.serialize_str(47).unwrap()
+    /// );
+    /// ```
+    pub fn reader<T: Into<FrozenStr>>(objective: T) -> Self {
+        ScoreComponent::new("*", objective)
+    }
+
+    /// Whether the name is the reader placeholder `*`.
+    pub fn is_reader(&self) -> bool {
+        &*self.name == "*"
+    }
+
+    /// Checks that the name is a score holder, a target selector or `*`,
+    /// and that the objective is a valid name, made of `A-Z`, `a-z`, `0-9`
+    /// and `_.+-`.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{ScoreComponent, ScoreErr};
+    ///
+    /// assert_eq!(Ok(()), ScoreComponent::new("@p[limit=1]", "kills").validate());
+    /// assert_eq!(Ok(()), ScoreComponent::reader("kills").validate());
+    /// assert_eq!(
+    ///     Err(ScoreErr::InvalidSelector("@q".to_string())),
+    ///     ScoreComponent::new("@q", "kills").validate()
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ScoreErr> {
+        let name = &*self.name;
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            return Err(ScoreErr::InvalidName(name.to_string()));
+        }
+        if name.starts_with('@') && name.parse::<TargetSelector>().is_err() {
+            return Err(ScoreErr::InvalidSelector(name.to_string()));
+        }
+        let objective = &*self.objective;
+        let allowed = |c: char| c.is_ascii_alphanumeric() || "_.+-".contains(c);
+        if objective.is_empty() || !objective.chars().all(allowed) {
+            return Err(ScoreErr::InvalidObjective(objective.to_string()));
+        }
+        Ok(())
+    }
+
     pub fn name<T: Into<FrozenStr>>(mut self, name: T) -> Self {
         self.name = name.into();
         self
@@ -377,11 +1014,39 @@ impl From<ScoreComponent> for ComponentKind {
     }
 }
 
+/// Reasons a [`ScoreComponent`] can't be resolved, see [`ScoreComponent::validate()`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScoreErr {
+    /// The score holder is empty or contains whitespace.
+    InvalidName(String),
+    /// The name starts with `@` but isn't a target selector.
+    InvalidSelector(String),
+    /// The objective is empty or has characters other than `A-Z`, `a-z`,
+    /// `0-9` and `_.+-`.
+    InvalidObjective(String),
+}
+
+impl Display for ScoreErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreErr::InvalidName(name) => write!(f, "invalid score holder {:?}", name),
+            ScoreErr::InvalidSelector(name) => write!(f, "invalid target selector {:?}", name),
+            ScoreErr::InvalidObjective(objective) => {
+                write!(f, "invalid objective {:?}", objective)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScoreErr {}
+
 /// Substitution based on entity selection.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize))]
 pub struct SelectorComponent {
     pub selector: FrozenStr,
+    /// Since **1.17**, between the names, a gray `, ` when [`None`].
+    #[cfg_attr(feature = "serde", serde(rename = "separator"))]
     pub sep: Option<Box<Chat>>,
 }
 
@@ -435,3 +1100,111 @@ impl From<KeybindComponent> for ComponentKind {
         Self::Keybind(value)
     }
 }
+
+/// Substitution by the NBT at a path of a block entity, entities or a
+/// command storage, like `/data get` shows it.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, NbtComponent, VERSION_1_16};
+///
+/// let chat = Chat::component(NbtComponent::entity("@s", "SelectedItem.id"));
+/// assert_eq!(
+///     r#"{"nbt":"SelectedItem.id","entity":"@s"}"#,
+///     chat.serialize_str(VERSION_1_16).unwrap()
+/// );
+/// assert_eq!("SelectedItem.id", &*chat.as_nbt().unwrap().nbt);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "serde_support::NbtComponentData"))]
+pub struct NbtComponent {
+    /// The NBT path, e.g. `Inventory[0].tag.display.Name`.
+    pub nbt: FrozenStr,
+    pub source: NbtSource,
+    /// Whether the values are read as chat components instead of shown as SNBT.
+    pub interpret: bool,
+    /// Since **1.17**, between the values, `, ` when [`None`].
+    pub sep: Option<Box<Chat>>,
+}
+
+impl NbtComponent {
+    pub fn new<T: Into<FrozenStr>>(source: NbtSource, nbt: T) -> Self {
+        NbtComponent {
+            nbt: nbt.into(),
+            source,
+            interpret: false,
+            sep: None,
+        }
+    }
+
+    /// The NBT of the block entity at a position like `~ ~-1 ~`.
+    pub fn block<T: Into<FrozenStr>, U: Into<FrozenStr>>(pos: T, nbt: U) -> Self {
+        NbtComponent::new(NbtSource::Block(pos.into()), nbt)
+    }
+
+    /// The NBT of the entities a selector selects.
+    pub fn entity<T: Into<FrozenStr>, U: Into<FrozenStr>>(selector: T, nbt: U) -> Self {
+        NbtComponent::new(NbtSource::Entity(selector.into()), nbt)
+    }
+
+    /// The NBT of a command storage like `namespace:path`.
+    pub fn storage<T: Into<FrozenStr>, U: Into<FrozenStr>>(id: T, nbt: U) -> Self {
+        NbtComponent::new(NbtSource::Storage(id.into()), nbt)
+    }
+
+    pub fn nbt<T: Into<FrozenStr>>(mut self, nbt: T) -> Self {
+        self.nbt = nbt.into();
+        self
+    }
+
+    pub fn source(mut self, source: NbtSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn interpret(mut self, interpret: bool) -> Self {
+        self.interpret = interpret;
+        self
+    }
+
+    pub fn sep(mut self, sep: Chat) -> Self {
+        self.sep = Some(Box::new(sep));
+        self
+    }
+}
+
+impl From<NbtComponent> for ComponentKind {
+    fn from(value: NbtComponent) -> Self {
+        Self::Nbt(value)
+    }
+}
+
+/// Where an [`NbtComponent`] reads its NBT from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NbtSource {
+    /// The position of a block entity, e.g. `~ ~-1 ~`.
+    Block(FrozenStr),
+    /// A target selector.
+    Entity(FrozenStr),
+    /// The id of a command storage, since 1.15.
+    Storage(FrozenStr),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_objective() {
+        for objective in ["kills", "Deaths_2", "stat.mineBlock.stone", "a+b-c"] {
+            assert_eq!(Ok(()), ScoreComponent::reader(objective).validate());
+        }
+        for objective in ["", "two words", "§akills", "kills!", "kills:total", "état"] {
+            assert_eq!(
+                Err(ScoreErr::InvalidObjective(objective.to_string())),
+                ScoreComponent::reader(objective).validate()
+            );
+        }
+    }
+}
@@ -2,10 +2,17 @@ use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 
 use crate::freeze::FrozenStr;
+use crate::query::nested_steps;
 use crate::style::serde_support::StyleVersioned;
-use crate::{ComponentKind, KeybindComponent, ScoreComponent, TextComponent};
-use serde::ser::SerializeSeq;
-use serde::{Deserialize, Serialize, Serializer};
+use crate::{
+    ChatRef, ClickEvent, ComponentKind, EntityId, HoverEvent, KeybindComponent, NbtComponent,
+    NbtSource, PathStep, ProtocolVersion, SanitizePolicy, SanitizeReport, ScoreComponent, ScoreErr,
+    SelectorComponent, SelectorErr, TextColor, TextComponent, TranslationArg, TranslationComponent,
+};
+use serde::de::Error as _;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 
 use crate::style::Style;
 
@@ -46,6 +53,56 @@ pub(crate) struct SerializeScoreInner {
     pub value: Option<FrozenStr>,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct NbtComponentData {
+    nbt: FrozenStr,
+    #[serde(default)]
+    interpret: bool,
+    block: Option<FrozenStr>,
+    entity: Option<FrozenStr>,
+    storage: Option<FrozenStr>,
+    separator: Option<Chat>,
+}
+
+impl TryFrom<NbtComponentData> for NbtComponent {
+    type Error = &'static str;
+
+    fn try_from(value: NbtComponentData) -> Result<Self, Self::Error> {
+        // the client checks the sources in this order
+        let source = match (value.block, value.entity, value.storage) {
+            (Some(pos), _, _) => NbtSource::Block(pos),
+            (None, Some(selector), _) => NbtSource::Entity(selector),
+            (None, None, Some(id)) => NbtSource::Storage(id),
+            (None, None, None) => return Err("expected a block, entity or storage source"),
+        };
+        Ok(NbtComponent {
+            nbt: value.nbt,
+            source,
+            interpret: value.interpret,
+            sep: value.separator.map(Box::new),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum TranslationArgData {
+    Bool(bool),
+    Number(serde_json::Number),
+    Chat(Chat),
+}
+
+impl From<TranslationArgData> for TranslationArg {
+    fn from(value: TranslationArgData) -> Self {
+        match value {
+            TranslationArgData::Bool(value) => TranslationArg::Bool(value),
+            TranslationArgData::Number(number) => TranslationArg::Number(number.to_string().into()),
+            TranslationArgData::Chat(chat) => TranslationArg::Chat(chat),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct FakeChatComponent {
     #[serde(flatten)]
@@ -63,12 +120,49 @@ impl From<FakeChatComponent> for Chat {
             kind: component.kind,
             style: component.style,
             children: component.children,
+            #[cfg(feature = "extensions")]
+            extensions: Default::default(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ComponentKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = Map::deserialize(deserializer)?;
+        let key = match object.get("type") {
+            // the type decides since 1.20.3, other content keys are ignored
+            Some(kind) => {
+                let kind = kind.as_str().unwrap_or_default();
+                CONTENT_TYPES
+                    .iter()
+                    .find(|(name, _)| *name == kind)
+                    .map(|(_, key)| key)
+                    .ok_or_else(|| D::Error::custom(format!("unknown component type {:?}", kind)))?
+            }
+            None => CONTENT_KEYS
+                .iter()
+                .find(|key| object.contains_key(**key))
+                .ok_or_else(|| D::Error::custom(missing_content()))?,
+        };
+        let value = Value::Object(object);
+        match *key {
+            "text" => TextComponent::deserialize(value).map(ComponentKind::Text),
+            "translate" => TranslationComponent::deserialize(value).map(ComponentKind::Translation),
+            "keybind" => KeybindComponent::deserialize(value).map(ComponentKind::Keybind),
+            "score" => ScoreComponent::deserialize(value).map(ComponentKind::Score),
+            "selector" => SelectorComponent::deserialize(value).map(ComponentKind::Selector),
+            _ => NbtComponent::deserialize(value).map(ComponentKind::Nbt),
         }
+        .map_err(D::Error::custom)
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize)]
-#[serde(untagged)]
+#[serde(
+    untagged,
+    expecting = "a string, a non-empty array or an object with a text, translate, keybind, score, selector or nbt key"
+)]
 pub(crate) enum ChatComponentType {
     Primitive(String),
     Array(Vec<Chat>),
@@ -107,6 +201,697 @@ impl TryFrom<ChatComponentType> for Chat {
     }
 }
 
+/// An error while parsing a component, with the path to the part that's invalid.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChatParseErr {
+    /// A JSON pointer to the invalid part, like `/extra/3/hoverEvent/contents`,
+    /// empty for the root or invalid JSON.
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for ChatParseErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} at {}", self.message, self.path)
+        }
+    }
+}
+
+impl std::error::Error for ChatParseErr {}
+
+/// Options for [`Chat::parse_checked()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeserializeOpts {
+    /// The longest JSON that's parsed, in bytes.
+    pub max_length: usize,
+    /// What's removed from the component, nothing when [`None`].
+    pub sanitize: Option<SanitizePolicy>,
+    /// Whether the component is parsed with [`Chat::from_json_lenient()`],
+    /// accepting components without content and JSON converted from NBT.
+    pub lenient: bool,
+    /// Whether the component is parsed with [`Chat::from_json_strict()`],
+    /// rejecting components with more than one content key.
+    pub strict: bool,
+}
+
+impl DeserializeOpts {
+    /// The longest chat component the server accepts, since 1.13.
+    pub const MAX_LENGTH: usize = 262144;
+}
+
+impl Default for DeserializeOpts {
+    fn default() -> Self {
+        Self {
+            max_length: Self::MAX_LENGTH,
+            sanitize: Some(SanitizePolicy::default()),
+            lenient: false,
+            strict: false,
+        }
+    }
+}
+
+/// Something [`Chat::parse_checked()`] found in a component that's still
+/// accepted, the paths point into the returned component.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// A feature that doesn't exist in the version, the client ignores it.
+    Unsupported {
+        path: Vec<PathStep>,
+        feature: &'static str,
+    },
+    InvalidSelector {
+        path: Vec<PathStep>,
+        err: SelectorErr,
+    },
+    InvalidScore {
+        path: Vec<PathStep>,
+        err: ScoreErr,
+    },
+    /// What the sanitization removed.
+    Sanitized(SanitizeReport),
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseWarning::Unsupported { path, feature } => {
+                write!(f, "{} aren't supported at {:?}", feature, path)
+            }
+            ParseWarning::InvalidSelector { path, err } => write!(f, "{} at {:?}", err, path),
+            ParseWarning::InvalidScore { path, err } => write!(f, "{} at {:?}", err, path),
+            ParseWarning::Sanitized(report) => write!(f, "sanitized the component: {:?}", report),
+        }
+    }
+}
+
+/// Checks a component against a version, adding a warning for every problem.
+fn check(
+    chat: &Chat,
+    version: ProtocolVersion,
+    path: &mut Vec<PathStep>,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    let mut unsupported = |feature| {
+        warnings.push(ParseWarning::Unsupported {
+            path: path.clone(),
+            feature,
+        })
+    };
+    match chat.kind {
+        ComponentKind::Score(_) | ComponentKind::Selector(_) if version < ProtocolVersion::V1_8 => {
+            unsupported("score and selector components")
+        }
+        ComponentKind::Keybind(_) if !version.supports_keybinds() => {
+            unsupported("keybind components")
+        }
+        ComponentKind::Translation(TranslationComponent {
+            fallback: Some(_), ..
+        }) if !version.supports_translation_fallback() => unsupported("translation fallbacks"),
+        ComponentKind::Nbt(_) if !version.supports_nbt_components() => {
+            unsupported("nbt components")
+        }
+        ComponentKind::Nbt(NbtComponent {
+            source: NbtSource::Storage(_),
+            ..
+        }) if !version.supports_storage() => unsupported("storage nbt components"),
+        _ => {}
+    }
+    if chat.kind.separator().is_some() && !version.supports_separators() {
+        unsupported("separators");
+    }
+    if let Some(TextColor::Custom(_)) = chat.style.color {
+        if !version.supports_hex_colors() {
+            unsupported("hex colors");
+        }
+    }
+    if let Some(HoverEvent::ShowAchievement(_)) = chat.style.hover_event {
+        if version.supports_keybinds() {
+            unsupported("show_achievement hover events");
+        }
+    }
+    if chat.style.font.is_some() && !version.supports_fonts() {
+        unsupported("fonts");
+    }
+    match chat.style.click_event {
+        Some(ClickEvent::CopyToClipBoard(_)) if !version.supports_clipboard() => {
+            unsupported("copy_to_clipboard click events")
+        }
+        Some(ClickEvent::Custom { .. }) if !version.supports_dialogs() => {
+            unsupported("custom click events")
+        }
+        Some(ClickEvent::ShowDialog(_)) if !version.supports_dialogs() => {
+            unsupported("show_dialog click events")
+        }
+        _ => {}
+    }
+    match chat.kind {
+        ComponentKind::Score(ref score) => {
+            if let Err(err) = score.validate() {
+                warnings.push(ParseWarning::InvalidScore {
+                    path: path.clone(),
+                    err,
+                });
+            }
+        }
+        ComponentKind::Selector(ref selector) => {
+            if let Err(err) = selector.target() {
+                warnings.push(ParseWarning::InvalidSelector {
+                    path: path.clone(),
+                    err,
+                });
+            }
+        }
+        _ => {}
+    }
+    for (step, nested) in nested_steps(chat) {
+        path.push(step);
+        check(nested, version, path, warnings);
+        path.pop();
+    }
+}
+
+/// The keys of an object that decide the kind of a component, in the order
+/// vanilla picks one when there are several.
+const CONTENT_KEYS: [&str; 6] = ["text", "translate", "keybind", "score", "selector", "nbt"];
+
+/// The `type` of each kind of component and its content key.
+const CONTENT_TYPES: [(&str, &str); 6] = [
+    ("text", "text"),
+    ("translatable", "translate"),
+    ("keybind", "keybind"),
+    ("score", "score"),
+    ("selector", "selector"),
+    ("nbt", "nbt"),
+];
+
+/// The keys of a component that hold a boolean.
+const BOOLEAN_KEYS: [&str; 6] = [
+    "bold",
+    "italic",
+    "underlined",
+    "strikethrough",
+    "obfuscated",
+    "interpret",
+];
+
+/// Rewrites what NBT turns into when it's converted to JSON: byte booleans
+/// (`1b`, or just `1`) and UUIDs as int arrays (`[I;1,2,3,4]`).
+fn normalize_nbt(value: &mut Value) {
+    match value {
+        Value::Array(array) => array.iter_mut().for_each(normalize_nbt),
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if BOOLEAN_KEYS.contains(&key.as_str()) {
+                    let flag = match value {
+                        Value::String(flag) => match flag.as_str() {
+                            "1b" | "true" => Some(true),
+                            "0b" | "false" => Some(false),
+                            _ => None,
+                        },
+                        Value::Number(flag) => match flag.as_i64() {
+                            Some(1) => Some(true),
+                            Some(0) => Some(false),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    if let Some(flag) = flag {
+                        *value = Value::Bool(flag);
+                    }
+                } else if key == "id" || key == "uuid" {
+                    if let Some(id) = int_array_uuid(value) {
+                        *value = Value::String(id);
+                    }
+                } else {
+                    normalize_nbt(value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Adds empty text to every component object without content, like `{}` or
+/// `{"color":"red"}`, following the places components are nested in.
+fn fill_content(value: &mut Value) {
+    match value {
+        Value::Array(array) => array.iter_mut().for_each(fill_content),
+        Value::Object(object) => {
+            if !CONTENT_KEYS.iter().any(|key| object.contains_key(*key)) {
+                object.insert("text".to_string(), Value::String(String::new()));
+            }
+            for key in ["extra", "with", "separator"] {
+                if let Some(nested) = object.get_mut(key) {
+                    fill_content(nested);
+                }
+            }
+            for key in ["hoverEvent", "hover_event"] {
+                if let Some(Value::Object(hover)) = object.get_mut(key) {
+                    match hover.get("action").and_then(Value::as_str) {
+                        Some("show_text") => {
+                            for key in ["contents", "value"] {
+                                if let Some(text) = hover.get_mut(key) {
+                                    fill_content(text);
+                                }
+                            }
+                        }
+                        Some("show_entity") => {
+                            if let Some(Value::Object(contents)) = hover.get_mut("contents") {
+                                if let Some(name) = contents.get_mut("name") {
+                                    fill_content(name);
+                                }
+                            } else if let Some(name) = hover.get_mut("name") {
+                                fill_content(name);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The hyphenated form of a UUID written as four ints, most significant first.
+fn int_array_uuid(value: &Value) -> Option<String> {
+    let ints = match value {
+        Value::Array(array) => array
+            .iter()
+            .map(Value::as_i64)
+            .collect::<Option<Vec<_>>>()?,
+        Value::String(array) => array
+            .strip_prefix("[I;")?
+            .strip_suffix(']')?
+            .split(',')
+            .map(|int| int.trim().parse().ok())
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    if ints.len() != 4 || ints.iter().any(|&int| i32::try_from(int).is_err()) {
+        return None;
+    }
+    let bits = ints.iter().fold(0u128, |bits, &int| {
+        bits << 32 | u128::from(int as i32 as u32)
+    });
+    let hex = format!("{:032x}", bits);
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+/// Finds the deepest part of a component that doesn't deserialize.
+fn locate_error(value: &Value, path: &str) -> Option<ChatParseErr> {
+    let error = Chat::deserialize(value).err()?;
+    let nested = match value {
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .find_map(|(index, element)| locate_error(element, &format!("{}/{}", path, index))),
+        Value::Object(object) => {
+            let components = ["extra", "with"].iter().flat_map(|key| {
+                object
+                    .get(*key)
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    // primitive translation arguments that aren't text
+                    .filter(|(_, element)| !element.is_number() && !element.is_boolean())
+                    .map(move |(index, element)| (format!("{}/{}", key, index), element))
+            });
+            components
+                .chain(
+                    object
+                        .get("separator")
+                        .map(|sep| ("separator".to_string(), sep)),
+                )
+                .find_map(|(key, element)| locate_error(element, &format!("{}/{}", path, key)))
+                .or_else(|| locate_style_error(object, path))
+        }
+        _ => None,
+    };
+    nested.or_else(|| {
+        let message = match value {
+            Value::Object(object) if !CONTENT_KEYS.iter().any(|key| object.contains_key(*key)) => {
+                missing_content()
+            }
+            _ => error.to_string(),
+        };
+        Some(ChatParseErr {
+            path: path.to_string(),
+            message,
+        })
+    })
+}
+
+fn missing_content() -> String {
+    format!("missing content, expected one of {:?}", CONTENT_KEYS)
+}
+
+/// Finds the first component object with more than one content key and
+/// no `type` to pick one, following the places components are nested in.
+fn find_conflict(value: &Value, path: &str) -> Option<ChatParseErr> {
+    match value {
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .find_map(|(index, element)| find_conflict(element, &format!("{}/{}", path, index))),
+        Value::Object(object) => {
+            let mut keys = CONTENT_KEYS.iter().filter(|key| object.contains_key(**key));
+            if let (Some(first), Some(second), false) =
+                (keys.next(), keys.next(), object.contains_key("type"))
+            {
+                return Some(ChatParseErr {
+                    path: path.to_string(),
+                    message: format!("conflicting content keys {:?} and {:?}", first, second),
+                });
+            }
+            let mut nested = ["extra", "with", "separator"]
+                .iter()
+                .filter_map(|key| Some((format!("{}/{}", path, key), object.get(*key)?)))
+                .collect::<Vec<_>>();
+            for key in ["hoverEvent", "hover_event"] {
+                if let Some(Value::Object(hover)) = object.get(key) {
+                    let path = format!("{}/{}", path, key);
+                    match hover.get("action").and_then(Value::as_str) {
+                        Some("show_text") => {
+                            nested.extend(["contents", "value"].iter().filter_map(|key| {
+                                Some((format!("{}/{}", path, key), hover.get(*key)?))
+                            }))
+                        }
+                        Some("show_entity") => nested.extend(
+                            hover
+                                .get("contents")
+                                .and_then(|contents| contents.get("name"))
+                                .map(|name| (format!("{}/contents/name", path), name))
+                                .or_else(|| Some((format!("{}/name", path), hover.get("name")?))),
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            nested
+                .into_iter()
+                .find_map(|(path, nested)| find_conflict(nested, &path))
+        }
+        _ => None,
+    }
+}
+
+/// Finds an invalid style setting of a component.
+fn locate_style_error(object: &Map<String, Value>, path: &str) -> Option<ChatParseErr> {
+    let invalid = |key: &str, error: serde_json::Error| ChatParseErr {
+        path: format!("{}/{}", path, key),
+        message: error.to_string(),
+    };
+    if let Some(Err(error)) = object.get("color").map(TextColor::deserialize) {
+        return Some(invalid("color", error));
+    }
+    for key in ["clickEvent", "click_event"] {
+        if let Some(Err(error)) = object.get(key).map(ClickEvent::deserialize) {
+            return Some(invalid(key, error));
+        }
+    }
+    let (hover_key, hover) = ["hoverEvent", "hover_event"]
+        .iter()
+        .find_map(|key| Some((*key, object.get(*key)?)))?;
+    let error = HoverEvent::deserialize(hover).err()?;
+    if hover.get("action").and_then(Value::as_str) == Some("show_text") {
+        for key in ["contents", "value"] {
+            if let Some(text) = hover.get(key) {
+                let nested = locate_error(text, &format!("{}/{}/{}", path, hover_key, key));
+                if nested.is_some() {
+                    return nested;
+                }
+            }
+        }
+    }
+    Some(invalid(hover_key, error))
+}
+
+impl Chat {
+    /// Parses a component from JSON like deserializing it with [`serde_json`],
+    /// but the error points to the part that's invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TextColor};
+    ///
+    /// let chat = Chat::from_json(r#"{"text":"Hi","color":"red"}"#).unwrap();
+    /// assert_eq!(Chat::text("Hi").color(TextColor::Red), chat);
+    ///
+    /// let json = r#"{"text":"","extra":["a",{"text":"b","hoverEvent":{"action":"show_text","contents":{"color":"red"}}}]}"#;
+    /// let err = Chat::from_json(json).unwrap_err();
+    /// assert_eq!("/extra/1/hoverEvent/contents", err.path);
+    /// ```
+    pub fn from_json(json: &str) -> Result<Chat, ChatParseErr> {
+        let value = parse_value(json)?;
+        from_value(&value)
+    }
+
+    /// Like [`Chat::from_json()`], for JSON that was converted from NBT, like
+    /// the text of signs and books in world files or data pack dumps.
+    ///
+    /// - Booleans written as bytes, `"1b"` or `1`, are read as `true` and
+    ///   `"0b"` or `0` as `false`.
+    /// - An entity id written as an int array, `[1,2,3,4]` or the string
+    ///   `"[I;1,2,3,4]"`, is read as the UUID it stores.
+    /// - A component without content, like `{}` or `{"color":"red"}`, is
+    ///   read as empty text. [`Chat::from_json()`] rejects it, naming the
+    ///   keys it expected.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Chat;
+    ///
+    /// let json = r#"{"text":"Loot","bold":"1b","italic":0}"#;
+    /// assert!(Chat::from_json(json).is_err());
+    /// assert_eq!(
+    ///     Chat::text("Loot").bold(true).italic(false),
+    ///     Chat::from_json_lenient(json).unwrap()
+    /// );
+    /// ```
+    pub fn from_json_lenient(json: &str) -> Result<Chat, ChatParseErr> {
+        let mut value = parse_value(json)?;
+        normalize_nbt(&mut value);
+        fill_content(&mut value);
+        from_value(&value)
+    }
+
+    /// Like [`Chat::from_json()`], but a component with more than one content
+    /// key, like `{"text":"a","translate":"b"}`, is an error instead of read
+    /// by vanilla's priority, where `text` comes first.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Chat;
+    ///
+    /// let json = r#"{"text":"","extra":[{"keybind":"key.jump","score":{"name":"@s","objective":"kills"}}]}"#;
+    /// assert_eq!(Chat::keybind("key.jump"), Chat::from_json(json).unwrap().children[0]);
+    /// let err = Chat::from_json_strict(json).unwrap_err();
+    /// assert_eq!("/extra/0", err.path);
+    /// ```
+    pub fn from_json_strict(json: &str) -> Result<Chat, ChatParseErr> {
+        let value = parse_value(json)?;
+        if let Some(err) = find_conflict(&value, "") {
+            return Err(err);
+        }
+        from_value(&value)
+    }
+
+    /// Parses a component sent by a client, like in a book or a chat packet,
+    /// with [`Chat::from_json()`] and sanitizes it.
+    ///
+    /// The component is also checked against the version of the client:
+    /// features it doesn't support as well as invalid selectors and scores
+    /// are reported as warnings.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, DeserializeOpts, ParseWarning, PathStep, VERSION_1_8};
+    ///
+    /// let json = r##"{"text":"Hi ","extra":[{"text":"all","color":"#ff8000","clickEvent":{"action":"run_command","value":"/op Steve"}}]}"##;
+    /// let (chat, warnings) = Chat::parse_checked(json, VERSION_1_8, &DeserializeOpts::default()).unwrap();
+    /// assert_eq!(None, chat.children[0].style.click_event);
+    /// assert_eq!(
+    ///     ParseWarning::Unsupported {
+    ///         path: vec![PathStep::Child(0)],
+    ///         feature: "hex colors"
+    ///     },
+    ///     warnings[1]
+    /// );
+    /// assert!(matches!(warnings[0], ParseWarning::Sanitized(ref report) if report.click_events == 1));
+    /// ```
+    pub fn parse_checked<V: Into<ProtocolVersion>>(
+        json: &str,
+        version: V,
+        opts: &DeserializeOpts,
+    ) -> Result<(Chat, Vec<ParseWarning>), ChatParseErr> {
+        if json.len() > opts.max_length {
+            return Err(ChatParseErr {
+                path: String::new(),
+                message: format!(
+                    "the component is {} bytes long, the limit is {}",
+                    json.len(),
+                    opts.max_length
+                ),
+            });
+        }
+        let mut value = parse_value(json)?;
+        if opts.strict {
+            if let Some(err) = find_conflict(&value, "") {
+                return Err(err);
+            }
+        }
+        if opts.lenient {
+            normalize_nbt(&mut value);
+            fill_content(&mut value);
+        }
+        let mut chat = from_value(&value)?;
+        let mut warnings = Vec::new();
+        if let Some(ref policy) = opts.sanitize {
+            let report = chat.sanitize(policy);
+            if !report.is_clean() {
+                warnings.push(ParseWarning::Sanitized(report));
+            }
+        }
+        check(&chat, version.into(), &mut Vec::new(), &mut warnings);
+        Ok((chat, warnings))
+    }
+}
+
+fn parse_value(json: &str) -> Result<Value, ChatParseErr> {
+    serde_json::from_str(json).map_err(|error| ChatParseErr {
+        path: String::new(),
+        message: error.to_string(),
+    })
+}
+
+pub(crate) fn from_value(value: &Value) -> Result<Chat, ChatParseErr> {
+    Chat::deserialize(value).map_err(|error| {
+        locate_error(value, "").unwrap_or_else(|| ChatParseErr {
+            path: String::new(),
+            message: error.to_string(),
+        })
+    })
+}
+
+/// Options for serializing a component beyond what the protocol version decides.
+///
+/// See [`Chat::serialize_str_with()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SerializeOpts {
+    /// How the id of an [`EntityTooltip`](crate::EntityTooltip) is written,
+    /// hyphenated by default.
+    pub uuid_format: UuidFormat,
+    /// Where the children of a component are written, under `extra` by default.
+    pub children_key: ChildrenKey,
+    /// Whether a component with children but without any style is written
+    /// as an array: `["a",{"text":"b","color":"red"}]` instead of
+    /// `{"text":"a","extra":[{"text":"b","color":"red"}]}`.
+    pub compact_arrays: bool,
+    /// Whether text without any style or children is written as a string:
+    /// `"a"` instead of `{"text":"a"}`.
+    pub compact_strings: bool,
+    /// Whether a value the client can't read is an error instead of being
+    /// left out, e.g. a [`TextColor::Custom`] before 1.16.
+    pub strict: bool,
+    /// Whether every component object names its kind, like `"type":"text"`
+    /// or `"type":"translatable"`. Only written since 1.20.3 (protocol 765),
+    /// deserializing always reads it.
+    pub typed: bool,
+}
+
+/// Where [`SerializeOpts`] puts the children of a component.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChildrenKey {
+    /// `{"text":"a","extra":["b"]}`, like vanilla.
+    #[default]
+    Extra,
+    /// Under a different key, for tools that don't use vanilla's format.
+    Custom(FrozenStr),
+    /// `[{"text":"a"},{"text":"b"}]`, the children follow their parent in an array,
+    /// which vanilla reads the same way.
+    Array,
+}
+
+/// The ways a UUID is written in chat components and NBT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UuidFormat {
+    /// `"f81d4fae-7dec-11d0-a765-00a0c91e6bf6"`
+    #[default]
+    Hyphenated,
+    /// `"f81d4fae7dec11d0a76500a0c91e6bf6"`
+    Simple,
+    /// Four integers, most significant first, like the `UUID` tag of entities.
+    IntArray,
+}
+
+/// What a versioned serialization writes for, shared by reference with
+/// everything that's nested.
+pub(crate) struct SerializeCtx<'a> {
+    pub version: ProtocolVersion,
+    pub opts: &'a SerializeOpts,
+}
+
+/// An entity id in the [`UuidFormat`] of the serialization.
+pub(crate) struct SerializeUuid<'a> {
+    pub format: UuidFormat,
+    pub id: &'a EntityId,
+}
+
+impl Serialize for SerializeUuid<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bits = match uuid_bits(self.id) {
+            Some(bits) => bits,
+            // an id that isn't a UUID is written as it is
+            None => return serializer.collect_str(self.id),
+        };
+        match self.format {
+            UuidFormat::Hyphenated => serializer.collect_str(&format_args!(
+                "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                bits >> 96,
+                (bits >> 80) & 0xffff,
+                (bits >> 64) & 0xffff,
+                (bits >> 48) & 0xffff,
+                bits & 0xffff_ffff_ffff
+            )),
+            UuidFormat::Simple => serializer.collect_str(&format_args!("{:032x}", bits)),
+            UuidFormat::IntArray => {
+                let mut array = serializer.serialize_seq(Some(4))?;
+                for shift in [96, 64, 32, 0].iter() {
+                    array.serialize_element(&((bits >> shift) as u32 as i32))?;
+                }
+                array.end()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+fn uuid_bits(id: &EntityId) -> Option<u128> {
+    Some(id.as_u128())
+}
+
+/// The bits of a UUID string, hyphenated or not.
+#[cfg(not(feature = "uuid"))]
+fn uuid_bits(id: &EntityId) -> Option<u128> {
+    let digits = id.chars().filter(|&c| c != '-').collect::<String>();
+    if digits.len() != 32 {
+        return None;
+    }
+    u128::from_str_radix(&digits, 16).ok()
+}
+
 impl Chat {
     /// Serialize this chat component to a JSON string.
     ///
@@ -126,12 +911,11 @@ impl Chat {
     /// let serialized_new = chat.serialize_str(VERSION_1_16).unwrap();
     /// assert_eq!(r#"{"text":"Sample text","font":"example_font"}"#, serialized_new);
     /// ```
-    pub fn serialize_str(&self, version: i32) -> serde_json::Result<String> {
-        serde_json::to_string(&SerializeChat {
-            kind: (version, &self.kind).into(),
-            style: (version, &self.style).into(),
-            children: (version, &self.children),
-        })
+    pub fn serialize_str<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<String> {
+        self.serialize_str_with(version, &SerializeOpts::default())
     }
 
     /// Serialize this chat component to JSON bytes.
@@ -154,12 +938,156 @@ impl Chat {
     /// 101, 120, 116, 34, 44, 34, 102, 111, 110, 116, 34, 58, 34, 101, 120, 97, 109, 112, 108,
     /// 101, 95, 102, 111, 110, 116, 34, 125], &serialized_new[..]);
     /// ```
-    pub fn serialize_vec(&self, version: i32) -> serde_json::Result<Vec<u8>> {
-        serde_json::to_vec(&SerializeChat {
-            kind: (version, &self.kind).into(),
-            style: (version, &self.style).into(),
-            children: (version, &self.children),
-        })
+    pub fn serialize_vec<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<Vec<u8>> {
+        self.serialize_vec_with(version, &SerializeOpts::default())
+    }
+
+    /// Like [`Chat::serialize_str()`], with [`SerializeOpts`] to change
+    /// the output where vanilla accepts more than one form.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, EntityTooltip, HoverEvent, SerializeOpts, UuidFormat, VERSION_1_16};
+    /// use uuid::Uuid;
+    ///
+    /// let id = Uuid::from_u128(0x0000000100000002_0000000300000004);
+    /// let chat = Chat::text("Steve").hover(Some(HoverEvent::ShowEntity(EntityTooltip::new(
+    ///     None,
+    ///     Some("minecraft:player"),
+    ///     Some(id),
+    /// ))));
+    /// let opts = SerializeOpts {
+    ///     uuid_format: UuidFormat::IntArray,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     r#"{"text":"Steve","hoverEvent":{"action":"show_entity","contents":{"type":"minecraft:player","id":[1,2,3,4]}}}"#,
+    ///     chat.serialize_str_with(VERSION_1_16, &opts).unwrap()
+    /// );
+    /// ```
+    pub fn serialize_str_with<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+        opts: &SerializeOpts,
+    ) -> serde_json::Result<String> {
+        ChatRef::from(self).serialize_str_with(version, opts)
+    }
+
+    /// Like [`Chat::serialize_vec()`], with [`SerializeOpts`].
+    pub fn serialize_vec_with<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+        opts: &SerializeOpts,
+    ) -> serde_json::Result<Vec<u8>> {
+        ChatRef::from(self).serialize_vec_with(version, opts)
+    }
+
+    /// Serializes this component in a canonical form, for signatures
+    /// that have to match the JSON another party produces, like chat
+    /// signing since 1.19.
+    ///
+    /// The canonical form is guaranteed not to change between versions of
+    /// this crate:
+    /// - the keys of every object are sorted by their UTF-8 bytes,
+    /// - there's no whitespace and integers are written without exponent or
+    ///   fraction,
+    /// - strings escape only `"`, `\` and control characters,
+    /// - the default [`SerializeOpts`] are used: every component is an
+    ///   object and children are under `extra`.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, ClickEvent, TextColor, VERSION_1_16};
+    ///
+    /// let chat = Chat::text("Hi")
+    ///     .color(TextColor::Red)
+    ///     .bold(true)
+    ///     .click(Some(ClickEvent::page(2u32)))
+    ///     .child(Chat::text("!"));
+    /// assert_eq!(
+    ///     r#"{"bold":true,"clickEvent":{"action":"change_page","value":2},"color":"red","extra":[{"text":"!"}],"text":"Hi"}"#,
+    ///     chat.serialize_canonical(VERSION_1_16).unwrap()
+    /// );
+    /// ```
+    pub fn serialize_canonical<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<String> {
+        let opts = SerializeOpts::default();
+        let ctx = SerializeCtx {
+            version: version.into(),
+            opts: &opts,
+        };
+        let value = serde_json::to_value(SerializeChat::new(&ctx, self))?;
+        serde_json::to_string(&sort_keys(value))
+    }
+}
+
+impl ChatRef<'_> {
+    /// Like [`Chat::serialize_str()`].
+    pub fn serialize_str<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<String> {
+        self.serialize_str_with(version, &SerializeOpts::default())
+    }
+
+    /// Like [`Chat::serialize_vec()`].
+    pub fn serialize_vec<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<Vec<u8>> {
+        self.serialize_vec_with(version, &SerializeOpts::default())
+    }
+
+    /// Like [`Chat::serialize_str_with()`].
+    pub fn serialize_str_with<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+        opts: &SerializeOpts,
+    ) -> serde_json::Result<String> {
+        let ctx = SerializeCtx {
+            version: version.into(),
+            opts,
+        };
+        serde_json::to_string(&SerializeChat::new(&ctx, *self))
+    }
+
+    /// Like [`Chat::serialize_vec_with()`].
+    pub fn serialize_vec_with<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+        opts: &SerializeOpts,
+    ) -> serde_json::Result<Vec<u8>> {
+        let ctx = SerializeCtx {
+            version: version.into(),
+            opts,
+        };
+        serde_json::to_vec(&SerializeChat::new(&ctx, *self))
+    }
+}
+
+/// Sorts the keys of every object, which `serde_json` doesn't do itself when
+/// its `preserve_order` feature is enabled.
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(first, _), (second, _)| first.cmp(second));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sort_keys).collect())
+        }
+        value => value,
     }
 }
 
@@ -167,37 +1095,62 @@ impl Chat {
 pub(crate) struct SerializeTranslation<'a> {
     #[serde(rename = "translate")]
     key: &'a FrozenStr,
-    #[serde(skip_serializing_if = "children_is_empty", default)]
-    #[serde(serialize_with = "serialize_children")]
-    with: (i32, &'a Vec<Chat>),
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fallback: Option<&'a FrozenStr>,
+    #[serde(skip_serializing_if = "SerializeArgs::is_empty")]
+    with: SerializeArgs<'a>,
 }
 
 #[derive(Serialize)]
 pub(crate) struct SerializeSelector<'a> {
     selector: &'a FrozenStr,
-    #[serde(rename = "separator")]
-    #[serde(skip_serializing_if = "version_option_none")]
-    #[serde(serialize_with = "serialize_chat_option")]
-    sep: (i32, &'a Option<Box<Chat>>),
+    #[serde(rename = "separator", skip_serializing_if = "Option::is_none")]
+    sep: Option<SerializeChat<'a>>,
 }
 
-pub(crate) fn version_option_none((_, value): &(i32, &Option<Box<Chat>>)) -> bool {
-    value.is_none()
+#[derive(Serialize)]
+pub(crate) struct SerializeScoreVersioned<'a> {
+    score: SerializeScoreFields<'a>,
 }
 
-pub(crate) fn serialize_chat_option<S: Serializer>(
-    (version, chat): &(i32, &Option<Box<Chat>>),
-    serializer: S,
-) -> Result<S::Ok, S::Error> {
-    match chat {
-        Some(c) => SerializeChat {
-            kind: (*version, &c.kind).into(),
-            style: (*version, &c.style).into(),
-            children: (*version, &c.children),
-        }
-        .serialize(serializer),
-        None => serializer.serialize_none(),
-    }
+#[derive(Serialize)]
+pub(crate) struct SerializeScoreFields<'a> {
+    name: &'a FrozenStr,
+    objective: &'a FrozenStr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<&'a FrozenStr>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SerializeNbt<'a> {
+    nbt: &'a FrozenStr,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    interpret: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<&'a FrozenStr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity: Option<&'a FrozenStr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<&'a FrozenStr>,
+    #[serde(rename = "separator", skip_serializing_if = "Option::is_none")]
+    sep: Option<SerializeChat<'a>>,
+}
+
+/// The separator of a selector or nbt component, since 1.17.
+fn separator<'a>(
+    ctx: &'a SerializeCtx<'a>,
+    sep: &'a Option<Box<Chat>>,
+) -> Option<SerializeChat<'a>> {
+    sep.as_deref()
+        .filter(|_| ctx.version.supports_separators())
+        .map(|sep| SerializeChat::new(ctx, sep))
+}
+
+/// An empty text component in place of a kind the version doesn't know,
+/// the client would reject the whole message otherwise.
+#[derive(Serialize)]
+pub(crate) struct SerializeEmpty {
+    text: &'static str,
 }
 
 #[derive(Serialize)]
@@ -205,67 +1158,473 @@ pub(crate) fn serialize_chat_option<S: Serializer>(
 pub(crate) enum SerializeComponent<'a> {
     Text(&'a TextComponent),
     Translation(SerializeTranslation<'a>),
-    Score(&'a ScoreComponent),
+    Score(SerializeScoreVersioned<'a>),
     Selector(SerializeSelector<'a>),
     Keybind(&'a KeybindComponent),
+    Nbt(SerializeNbt<'a>),
+    Empty(SerializeEmpty),
 }
 
-impl<'a> From<(i32, &'a ComponentKind)> for SerializeComponent<'a> {
-    fn from((version, component): (i32, &'a ComponentKind)) -> Self {
+impl<'a> SerializeComponent<'a> {
+    /// The `type` of the component since 1.20.3.
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Text(_) | Self::Empty(_) => "text",
+            Self::Translation(_) => "translatable",
+            Self::Score(_) => "score",
+            Self::Selector(_) => "selector",
+            Self::Keybind(_) => "keybind",
+            Self::Nbt(_) => "nbt",
+        }
+    }
+
+    fn new(ctx: &'a SerializeCtx<'a>, component: &'a ComponentKind) -> Self {
+        let version = ctx.version;
         match component {
             ComponentKind::Text(v) => Self::Text(v),
             ComponentKind::Translation(v) => Self::Translation(SerializeTranslation {
                 key: &v.key,
-                with: (version, &v.with),
+                fallback: v
+                    .fallback
+                    .as_ref()
+                    .filter(|_| version.supports_translation_fallback()),
+                with: SerializeArgs { ctx, args: &v.with },
+            }),
+            ComponentKind::Score(v) => Self::Score(SerializeScoreVersioned {
+                score: SerializeScoreFields {
+                    name: &v.name,
+                    objective: &v.objective,
+                    value: v.value.as_ref().filter(|_| version.supports_score_values()),
+                },
             }),
-            ComponentKind::Score(v) => Self::Score(v),
             ComponentKind::Selector(v) => Self::Selector(SerializeSelector {
                 selector: &v.selector,
-                sep: (version, &v.sep),
+                sep: separator(ctx, &v.sep),
             }),
             ComponentKind::Keybind(v) => Self::Keybind(v),
+            ComponentKind::Nbt(_) if !version.supports_nbt_components() => {
+                Self::Empty(SerializeEmpty { text: "" })
+            }
+            ComponentKind::Nbt(NbtComponent {
+                source: NbtSource::Storage(_),
+                ..
+            }) if !version.supports_storage() => Self::Empty(SerializeEmpty { text: "" }),
+            ComponentKind::Nbt(v) => {
+                let (block, entity, storage) = match v.source {
+                    NbtSource::Block(ref pos) => (Some(pos), None, None),
+                    NbtSource::Entity(ref selector) => (None, Some(selector), None),
+                    NbtSource::Storage(ref id) => (None, None, Some(id)),
+                };
+                Self::Nbt(SerializeNbt {
+                    nbt: &v.nbt,
+                    interpret: v.interpret,
+                    block,
+                    entity,
+                    storage,
+                    sep: separator(ctx, &v.sep),
+                })
+            }
         }
     }
 }
 
-#[derive(Serialize)]
+/// A component serialized for the version and options of a [`SerializeCtx`].
 pub(crate) struct SerializeChat<'a> {
+    ctx: &'a SerializeCtx<'a>,
+    chat: ChatRef<'a>,
+}
+
+impl<'a> SerializeChat<'a> {
+    pub fn new<C: Into<ChatRef<'a>>>(ctx: &'a SerializeCtx<'a>, chat: C) -> Self {
+        SerializeChat {
+            ctx,
+            chat: chat.into(),
+        }
+    }
+}
+
+impl Serialize for SerializeChat<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SerializeChat { ctx, chat } = *self;
+        let opts = ctx.opts;
+        let unstyled = chat.style.is_empty(ctx.version);
+        if let ComponentKind::Text(ref text) = *chat.kind {
+            if opts.compact_strings && unstyled && chat.children.is_empty() {
+                return serializer.serialize_str(&text.text);
+            }
+        }
+        let kind = SerializeComponent::new(ctx, chat.kind);
+        let kind_type =
+            Some(kind.type_name()).filter(|_| opts.typed && ctx.version.supports_component_types());
+        let style = StyleVersioned {
+            ctx,
+            style: chat.style,
+        };
+        let plain = opts.compact_arrays && unstyled;
+        if !chat.children.is_empty() && (plain || opts.children_key == ChildrenKey::Array) {
+            let mut array = serializer.serialize_seq(Some(chat.children.len() + 1))?;
+            match kind {
+                SerializeComponent::Text(text) if plain => array.serialize_element(&text.text)?,
+                _ => array.serialize_element(&SerializeChatObject {
+                    kind_type,
+                    kind: &kind,
+                    style: &style,
+                    children: ChildrenEntry {
+                        key: "",
+                        children: SerializeChildren { ctx, children: &[] },
+                    },
+                })?,
+            }
+            for child in chat.children {
+                array.serialize_element(&SerializeChat::new(ctx, child))?;
+            }
+            return array.end();
+        }
+        let key = match opts.children_key {
+            ChildrenKey::Custom(ref key) => key,
+            ChildrenKey::Extra | ChildrenKey::Array => "extra",
+        };
+        SerializeChatObject {
+            kind_type,
+            kind: &kind,
+            style: &style,
+            children: ChildrenEntry {
+                key,
+                children: SerializeChildren {
+                    ctx,
+                    children: chat.children,
+                },
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Serialize)]
+struct SerializeChatObject<'a, 'b> {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind_type: Option<&'static str>,
+    #[serde(flatten)]
+    kind: &'b SerializeComponent<'a>,
     #[serde(flatten)]
-    pub kind: SerializeComponent<'a>,
+    style: &'b StyleVersioned<'a>,
     #[serde(flatten)]
-    pub style: StyleVersioned<'a>,
-    #[serde(rename = "extra", skip_serializing_if = "children_is_empty", default)]
-    #[serde(serialize_with = "serialize_children")]
-    pub children: (i32, &'a Vec<Chat>),
+    children: ChildrenEntry<'a>,
+}
+
+/// The children of a component under the key of the [`SerializeOpts`],
+/// nothing when there are none.
+struct ChildrenEntry<'a> {
+    key: &'a str,
+    children: SerializeChildren<'a>,
 }
 
-fn serialize_children<S: Serializer>(
-    (version, children): &(i32, &Vec<Chat>),
-    serializer: S,
-) -> Result<S::Ok, S::Error> {
-    let mut serializer = serializer.serialize_seq(Some(children.len()))?;
-    for child in *children {
-        serializer.serialize_element(&SerializeChat {
-            kind: (*version, &child.kind).into(),
-            style: (*version, &child.style).into(),
-            children: (*version, &child.children),
-        })?;
+impl Serialize for ChildrenEntry<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if !self.children.is_empty() {
+            map.serialize_entry(self.key, &self.children)?;
+        }
+        map.end()
     }
-    serializer.end()
 }
 
-fn children_is_empty((_, children): &(i32, &Vec<Chat>)) -> bool {
-    children.is_empty()
+pub(crate) struct SerializeChildren<'a> {
+    ctx: &'a SerializeCtx<'a>,
+    children: &'a [Chat],
+}
+
+impl SerializeChildren<'_> {
+    fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl Serialize for SerializeChildren<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.children
+                .iter()
+                .map(|child| SerializeChat::new(self.ctx, child)),
+        )
+    }
+}
+
+pub(crate) struct SerializeArgs<'a> {
+    ctx: &'a SerializeCtx<'a>,
+    args: &'a [TranslationArg],
+}
+
+impl SerializeArgs<'_> {
+    fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+}
+
+impl Serialize for SerializeArgs<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.args.len()))?;
+        for arg in self.args {
+            match arg {
+                TranslationArg::Chat(chat) => {
+                    seq.serialize_element(&SerializeChat::new(self.ctx, chat))?
+                }
+                TranslationArg::Number(number) => {
+                    match number.parse::<serde_json::Number>() {
+                        Ok(number) => seq.serialize_element(&number)?,
+                        // not a JSON number, like `NaN`
+                        Err(_) => seq.serialize_element(&**number)?,
+                    }
+                }
+                TranslationArg::Bool(value) => seq.serialize_element(value)?,
+            }
+        }
+        seq.end()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use serde_json::Value;
-
-    use crate::VERSION_1_8;
+    use crate::{HoverEvent, TranslationComponent, VERSION_1_16, VERSION_1_8};
 
     use super::*;
 
+    #[test]
+    pub fn serialize_canonical_nested() {
+        let chat = Chat::component(
+            TranslationComponent::new("chat.type.text")
+                .argument(Chat::text("Steve").insertion(Some("Steve")))
+                .argument(Chat::text("hi")),
+        )
+        .hover(Some(HoverEvent::ShowText(Box::new(
+            Chat::text("tip").italic(true),
+        ))));
+        let canonical = chat.serialize_canonical(VERSION_1_16).unwrap();
+        assert_eq!(
+            r#"{"hoverEvent":{"action":"show_text","contents":{"italic":true,"text":"tip"}},"translate":"chat.type.text","with":[{"insertion":"Steve","text":"Steve"},{"text":"hi"}]}"#,
+            canonical
+        );
+    }
+
+    #[test]
+    pub fn serialize_score_value() {
+        let chat = Chat::component(ScoreComponent::reader("kills").value(Some("3")));
+        assert_eq!(
+            r#"{"score":{"name":"*","objective":"kills","value":"3"}}"#,
+            chat.serialize_str(764).unwrap()
+        );
+        assert_eq!(
+            r#"{"score":{"name":"*","objective":"kills"}}"#,
+            chat.serialize_str(765).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn nbt_components() {
+        let chat =
+            Chat::component(NbtComponent::storage("mc:msgs", "last").interpret(true)).bold(true);
+        let json = r#"{"nbt":"last","interpret":true,"storage":"mc:msgs","bold":true}"#;
+        assert_eq!(json, chat.serialize_str(VERSION_1_16).unwrap());
+        assert_eq!(chat, Chat::from_json(json).unwrap());
+        // storage is since 1.15, nbt components since 1.14
+        assert_eq!(
+            r#"{"text":"","bold":true}"#,
+            chat.serialize_str(498).unwrap()
+        );
+        let chat = Chat::component(NbtComponent::block("~ ~-1 ~", "Items"));
+        assert_eq!(r#"{"text":""}"#, chat.serialize_str(404).unwrap());
+        assert_eq!(
+            chat,
+            Chat::from_json(r#"{"nbt":"Items","block":"~ ~-1 ~","storage":"mc:a"}"#).unwrap()
+        );
+        assert!(Chat::from_json(r#"{"nbt":"Items"}"#).is_err());
+
+        let chat = Chat::storage("mypack:messages", "greeting.text")
+            .child(Chat::text("!"))
+            .color(TextColor::Gold);
+        let json = chat.serialize_str(573).unwrap();
+        assert_eq!(
+            r#"{"nbt":"greeting.text","storage":"mypack:messages","color":"gold","extra":[{"text":"!"}]}"#,
+            json
+        );
+        assert_eq!(chat, Chat::from_json(&json).unwrap());
+    }
+
+    #[test]
+    pub fn translation_fallback() {
+        let chat = Chat::component(
+            TranslationComponent::new("custom.key")
+                .fallback("Hi %s")
+                .argument(Chat::text("Steve")),
+        );
+        let json = r#"{"translate":"custom.key","fallback":"Hi %s","with":[{"text":"Steve"}]}"#;
+        assert_eq!(json, chat.serialize_str(762).unwrap());
+        assert_eq!(chat, Chat::from_json(json).unwrap());
+        assert_eq!(
+            r#"{"translate":"custom.key","with":[{"text":"Steve"}]}"#,
+            chat.serialize_str(761).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn primitive_arguments() {
+        let json = r#"{"translate":"x","with":[5,{"text":"hi"},true,-2.5]}"#;
+        let chat = Chat::from_json(json).unwrap();
+        assert_eq!(
+            Chat::key_with(
+                "x",
+                vec![
+                    TranslationArg::from(5),
+                    "hi".into(),
+                    true.into(),
+                    (-2.5).into()
+                ]
+            ),
+            chat
+        );
+        assert_eq!(json, chat.serialize_str(47).unwrap());
+    }
+
+    #[test]
+    pub fn content_priority() {
+        let json = r#"{"translate":"x","with":[],"text":"a","nbt":"b","block":"0 0 0"}"#;
+        assert_eq!(Chat::text("a"), Chat::from_json(json).unwrap());
+        let err = Chat::from_json_strict(json).unwrap_err();
+        assert_eq!("", err.path);
+        assert_eq!(
+            r#"conflicting content keys "text" and "translate""#,
+            err.message
+        );
+
+        let json = r#"{"text":"","hoverEvent":{"action":"show_text","contents":{"selector":"@p","keybind":"key.jump"}}}"#;
+        let chat = Chat::from_json(json).unwrap();
+        assert_eq!(
+            Some(&Chat::keybind("key.jump")),
+            chat.style.hover_event.as_ref().and_then(HoverEvent::text)
+        );
+        let opts = DeserializeOpts {
+            strict: true,
+            ..DeserializeOpts::default()
+        };
+        assert_eq!(
+            "/hoverEvent/contents",
+            Chat::parse_checked(json, VERSION_1_16, &opts)
+                .unwrap_err()
+                .path
+        );
+    }
+
+    #[test]
+    pub fn typed() {
+        let chat = Chat::text("")
+            .child(Chat::key_with("x", vec!["a"]))
+            .child(Chat::keybind("key.jump").bold(true));
+        let opts = SerializeOpts {
+            typed: true,
+            ..SerializeOpts::default()
+        };
+        let json = r#"{"type":"text","text":"","extra":[{"type":"translatable","translate":"x","with":[{"type":"text","text":"a"}]},{"type":"keybind","keybind":"key.jump","bold":true}]}"#;
+        assert_eq!(json, chat.serialize_str_with(765, &opts).unwrap());
+        assert_eq!(chat, Chat::from_json(json).unwrap());
+        assert_eq!(
+            chat.serialize_str(764).unwrap(),
+            chat.serialize_str_with(764, &opts).unwrap()
+        );
+
+        let json = r#"{"type":"keybind","text":"a","keybind":"key.jump"}"#;
+        assert_eq!(Chat::keybind("key.jump"), Chat::from_json(json).unwrap());
+        assert!(Chat::from_json_strict(json).is_ok());
+        assert!(Chat::from_json(r#"{"type":"object","text":"a"}"#).is_err());
+    }
+
+    #[test]
+    pub fn separators() {
+        let chat = Chat::selector("@a", Some(Chat::text(" | "))).child(Chat::component(
+            NbtComponent::entity("@a", "Health").sep(Chat::text("/")),
+        ));
+        let json = r#"{"selector":"@a","separator":{"text":" | "},"extra":[{"nbt":"Health","entity":"@a","separator":{"text":"/"}}]}"#;
+        assert_eq!(json, chat.serialize_str(755).unwrap());
+        assert_eq!(chat, Chat::from_json(json).unwrap());
+        assert_eq!(
+            r#"{"selector":"@a","extra":[{"nbt":"Health","entity":"@a"}]}"#,
+            chat.serialize_str(754).unwrap()
+        );
+        let (_, warnings) = Chat::parse_checked(
+            json,
+            754,
+            &DeserializeOpts {
+                sanitize: None,
+                ..DeserializeOpts::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(2, warnings.len());
+    }
+
+    #[test]
+    pub fn lenient_nbt() {
+        let json = r#"{"text":"","extra":[{"nbt":"a","storage":"b","interpret":"1b","underlined":"0b"}],"hoverEvent":{"action":"show_entity","contents":{"type":"minecraft:pig","id":[-132296786,2112623056,-1486552928,-920753162]}}}"#;
+        assert!(Chat::from_json(json).is_err());
+        let chat = Chat::from_json_lenient(json).unwrap();
+        assert_eq!(
+            Chat::component(NbtComponent::storage("b", "a").interpret(true)).underlined(false),
+            chat.children[0]
+        );
+        let json = r#"{"text":"","hoverEvent":{"action":"show_entity","contents":{"type":"minecraft:pig","id":"[I;-132296786,2112623056,-1486552928,-920753162]"}}}"#;
+        match Chat::from_json_lenient(json).unwrap().style.hover_event {
+            Some(HoverEvent::ShowEntity(entity)) => assert_eq!(
+                "f81d4fae-7dec-11d0-a765-00a0c91e6bf6",
+                entity.id.unwrap().to_string()
+            ),
+            event => panic!("expected an entity tooltip, got {:?}", event),
+        }
+        // text is never changed
+        assert_eq!(
+            Chat::text("1b"),
+            Chat::from_json_lenient(r#"{"text":"1b"}"#).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn empty_objects() {
+        let json = r#"{"text":"a","extra":[{},{"color":"red","extra":["b"]}],"hoverEvent":{"action":"show_text","contents":{"bold":true}}}"#;
+        let err = Chat::from_json(json).unwrap_err();
+        assert_eq!("/extra/0", err.path);
+        assert!(err.message.contains("missing content"));
+        let err = serde_json::from_str::<Chat>("{}").unwrap_err();
+        assert!(err.to_string().contains("translate"));
+
+        assert_eq!(
+            Chat::text("a")
+                .hover(Some(HoverEvent::ShowText(Box::new(
+                    Chat::text("").bold(true)
+                ))))
+                .child(Chat::text(""))
+                .child(Chat::text("").color(TextColor::Red).child(Chat::text("b"))),
+            Chat::from_json_lenient(json).unwrap()
+        );
+        assert_eq!(Chat::text(""), Chat::from_json_lenient("{}").unwrap());
+        // click events aren't components
+        assert!(Chat::from_json_lenient(r#"{"clickEvent":{}}"#).is_err());
+    }
+
+    #[test]
+    pub fn chat_ref() {
+        let chat = Chat::key_with("%s", vec![Chat::text("a")])
+            .color(TextColor::Red)
+            .child(Chat::text("b").bold(true));
+        let view = ChatRef::new(&chat.kind, &chat.style, &chat.children);
+        let opts = SerializeOpts {
+            compact_strings: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            chat.serialize_str_with(VERSION_1_8, &opts).unwrap(),
+            view.serialize_str_with(VERSION_1_8, &opts).unwrap()
+        );
+        assert_eq!(chat, view.to_chat());
+    }
+
     #[test]
     pub fn serialize_chat_text() {
         let chat = Chat::text("Sample text");
@@ -298,4 +1657,148 @@ mod tests {
         let chat: Chat = serde_json::from_value(value).unwrap();
         assert_eq!(chat_orig, chat);
     }
+
+    #[test]
+    pub fn uuid_formats() {
+        #[cfg(feature = "uuid")]
+        let id = uuid::Uuid::parse_str("f81d4fae-7dec-11d0-a765-00a0c91e6bf6").unwrap();
+        #[cfg(not(feature = "uuid"))]
+        let id = EntityId::from("F81D4FAE7DEC11D0A76500A0C91E6BF6");
+        let chat = Chat::text("").hover(Some(crate::HoverEvent::ShowEntity(
+            crate::EntityTooltip::new(None, Option::<&str>::None, Some(id)),
+        )));
+        let serialize = |uuid_format| {
+            let opts = SerializeOpts {
+                uuid_format,
+                ..Default::default()
+            };
+            chat.serialize_str_with(crate::VERSION_1_16, &opts).unwrap()
+        };
+        assert!(serialize(UuidFormat::Hyphenated)
+            .contains(r#""id":"f81d4fae-7dec-11d0-a765-00a0c91e6bf6""#));
+        assert!(
+            serialize(UuidFormat::Simple).contains(r#""id":"f81d4fae7dec11d0a76500a0c91e6bf6""#)
+        );
+        assert!(serialize(UuidFormat::IntArray)
+            .contains(r#""id":[-132296786,2112623056,-1486552928,-920753162]"#));
+        // the options only last for one serialization
+        assert_eq!(
+            serialize(UuidFormat::Hyphenated),
+            chat.serialize_str(crate::VERSION_1_16).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn children_keys() {
+        let chat = Chat::text("a").child(Chat::text("b").child(Chat::text("c")));
+        let serialize = |children_key| {
+            let opts = SerializeOpts {
+                children_key,
+                ..Default::default()
+            };
+            chat.serialize_str_with(VERSION_1_8, &opts).unwrap()
+        };
+        assert_eq!(
+            r#"{"text":"a","children":[{"text":"b","children":[{"text":"c"}]}]}"#,
+            serialize(ChildrenKey::Custom("children".into()))
+        );
+        let array = serialize(ChildrenKey::Array);
+        assert_eq!(r#"[{"text":"a"},[{"text":"b"},{"text":"c"}]]"#, array);
+        assert_eq!(chat, serde_json::from_str(&array).unwrap());
+    }
+
+    #[test]
+    pub fn compact_arrays() {
+        let chat = Chat::text("a")
+            .child(Chat::text("b").bold(true).child(Chat::text("c")))
+            .child(Chat::keybind("d").child(Chat::text("e")));
+        let opts = SerializeOpts {
+            compact_arrays: true,
+            ..Default::default()
+        };
+        let json = chat.serialize_str_with(VERSION_1_8, &opts).unwrap();
+        assert_eq!(
+            r#"["a",{"text":"b","bold":true,"extra":[{"text":"c"}]},[{"keybind":"d"},{"text":"e"}]]"#,
+            json
+        );
+        assert_eq!(chat, serde_json::from_str(&json).unwrap());
+        // a hex color doesn't exist in 1.8, so the style is empty there
+        let chat = Chat::text("f")
+            .color(TextColor::from_rgb(0xff, 0x80, 0x00))
+            .child(Chat::text("g"));
+        assert_eq!(
+            r#"["f",{"text":"g"}]"#,
+            chat.serialize_str_with(VERSION_1_8, &opts).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn compact_strings() {
+        let opts = SerializeOpts {
+            compact_strings: true,
+            ..Default::default()
+        };
+        let serialize = |chat: &Chat| chat.serialize_str_with(VERSION_1_8, &opts).unwrap();
+        assert_eq!(r#""hello""#, serialize(&Chat::text("hello")));
+        let chat = Chat::key_with("%s%s", vec![Chat::text("a"), Chat::text("b").bold(true)])
+            .child(Chat::text("c"))
+            .child(Chat::text("d").child(Chat::text("e")));
+        let json = serialize(&chat);
+        assert_eq!(
+            r#"{"translate":"%s%s","with":["a",{"text":"b","bold":true}],"extra":["c",{"text":"d","extra":["e"]}]}"#,
+            json
+        );
+        assert_eq!(chat, serde_json::from_str(&json).unwrap());
+
+        let opts = SerializeOpts {
+            compact_arrays: true,
+            ..opts
+        };
+        assert_eq!(
+            r#"["d","e"]"#,
+            Chat::text("d")
+                .child(Chat::text("e"))
+                .serialize_str_with(VERSION_1_8, &opts)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn strict() {
+        let chat = Chat::text("a").color(TextColor::from_rgb(0xff, 0x80, 0x00));
+        assert_eq!(r#"{"text":"a"}"#, chat.serialize_str(VERSION_1_8).unwrap());
+        let opts = SerializeOpts {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(chat.serialize_str_with(VERSION_1_8, &opts).is_err());
+        assert_eq!(
+            r##"{"text":"a","color":"#ff8000"}"##,
+            chat.serialize_str_with(VERSION_1_16, &opts).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn error_paths() {
+        let path = |json: &str| Chat::from_json(json).unwrap_err().path;
+        assert_eq!("", path("{"));
+        assert_eq!("", path(r#"{"bold":true}"#));
+        assert_eq!("/extra/0", path(r#"{"text":"","extra":[{"bold":true}]}"#));
+        assert_eq!(
+            "/with/1/extra/0/color",
+            path(
+                r#"{"translate":"key","with":["a",{"text":"","extra":[{"text":"","color":"nope"}]}]}"#
+            )
+        );
+        assert_eq!(
+            "/2/clickEvent",
+            path(r#"["a","b",{"text":"","clickEvent":{"action":"open","value":"x"}}]"#)
+        );
+        assert_eq!(
+            "/hoverEvent",
+            path(r#"{"text":"","hoverEvent":{"action":"show_item","contents":1}}"#)
+        );
+        let err = Chat::from_json(r#"{"text":"","extra":[{"bold":true}]}"#).unwrap_err();
+        assert!(err.to_string().ends_with(" at /extra/0"));
+    }
 }
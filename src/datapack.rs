@@ -0,0 +1,426 @@
+//! Reading and writing the chat components in data pack files, like the
+//! description in `pack.mcmeta` or the title of an advancement.
+//!
+//! Data packs are read by the server of the version they're made for, the
+//! components in them are serialized for that protocol version like they
+//! would be sent to a client of that version.
+
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+use serde_json::{Map, Value};
+
+use crate::component::serde_support::{from_value, SerializeChat, SerializeCtx};
+use crate::freeze::FrozenStr;
+use crate::{Chat, ChatParseErr, ProtocolVersion, SerializeOpts};
+
+/// The `pack` section of a `pack.mcmeta` file.
+///
+/// # Example
+/// ```
+/// use mc_chat::datapack::PackMeta;
+/// use mc_chat::{Chat, TextColor};
+///
+/// let meta = PackMeta::new(15, Chat::text("Custom loot").color(TextColor::Gold));
+/// let json = meta.to_json(763).unwrap();
+/// assert_eq!(meta, PackMeta::from_json(&json).unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PackMeta {
+    /// The format of the pack, which changes with almost every version.
+    pub pack_format: i32,
+    pub description: Chat,
+}
+
+impl PackMeta {
+    pub fn new<C: Into<Chat>>(pack_format: i32, description: C) -> Self {
+        Self {
+            pack_format,
+            description: description.into(),
+        }
+    }
+
+    /// Reads the `pack` section of a `pack.mcmeta` file, other sections
+    /// like `filter` or `overlays` are ignored.
+    pub fn from_json(json: &str) -> Result<Self, DataPackErr> {
+        let value = parse(json)?;
+        let pack_format = value
+            .pointer("/pack/pack_format")
+            .ok_or_else(|| DataPackErr::Missing("/pack/pack_format".to_string()))?;
+        let pack_format = pack_format
+            .as_i64()
+            .and_then(|format| i32::try_from(format).ok())
+            .ok_or_else(|| DataPackErr::Json(format!("invalid pack format {}", pack_format)))?;
+        Ok(Self {
+            pack_format,
+            description: component_at(&value, "/pack/description")?,
+        })
+    }
+
+    /// Writes a `pack.mcmeta` file with only this `pack` section, pretty
+    /// printed. The description is serialized for the given version.
+    ///
+    /// Use [`write_component()`] to change the description of an existing
+    /// file, keeping its other sections.
+    pub fn to_json<V: Into<ProtocolVersion>>(&self, version: V) -> Result<String, DataPackErr> {
+        let version = version.into();
+        let mut pack = Map::new();
+        pack.insert("pack_format".to_string(), self.pack_format.into());
+        pack.insert(
+            "description".to_string(),
+            to_value(&self.description, version)?,
+        );
+        let mut meta = Map::new();
+        meta.insert("pack".to_string(), Value::Object(pack));
+        serde_json::to_string_pretty(&meta).map_err(|err| DataPackErr::Json(err.to_string()))
+    }
+}
+
+/// The `display` of an advancement: how it's shown in the advancement screen
+/// and the toast when it's completed.
+///
+/// # Example
+/// ```
+/// use mc_chat::datapack::{AdvancementDisplay, AdvancementFrame};
+/// use mc_chat::{Chat, TextColor};
+///
+/// let display = AdvancementDisplay::new(
+///     "minecraft:dragon_egg",
+///     Chat::text("The Next Generation").color(TextColor::Gold),
+///     "Hold the Dragon Egg",
+/// )
+/// .frame(AdvancementFrame::Goal);
+/// let json = display.to_json(763).unwrap();
+/// assert!(json.contains(r#""item": "minecraft:dragon_egg""#));
+/// assert_eq!(display, AdvancementDisplay::from_json(&json).unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AdvancementDisplay {
+    /// The id of the item shown as icon.
+    pub icon: FrozenStr,
+    pub title: Chat,
+    pub description: Chat,
+    pub frame: AdvancementFrame,
+    /// The texture behind a root advancement, like
+    /// `minecraft:textures/gui/advancements/backgrounds/stone.png`.
+    pub background: Option<FrozenStr>,
+    pub show_toast: bool,
+    pub announce_to_chat: bool,
+    pub hidden: bool,
+}
+
+/// The shape of the frame around the icon of an advancement, which also
+/// decides the color of its toast.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AdvancementFrame {
+    #[default]
+    Task,
+    Goal,
+    Challenge,
+}
+
+impl AdvancementFrame {
+    pub fn name(self) -> &'static str {
+        match self {
+            AdvancementFrame::Task => "task",
+            AdvancementFrame::Goal => "goal",
+            AdvancementFrame::Challenge => "challenge",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "task" => Some(AdvancementFrame::Task),
+            "goal" => Some(AdvancementFrame::Goal),
+            "challenge" => Some(AdvancementFrame::Challenge),
+            _ => None,
+        }
+    }
+}
+
+impl AdvancementDisplay {
+    /// A task with a toast that's announced in chat, like most vanilla
+    /// advancements.
+    pub fn new<I, T, D>(icon: I, title: T, description: D) -> Self
+    where
+        I: Into<FrozenStr>,
+        T: Into<Chat>,
+        D: Into<Chat>,
+    {
+        Self {
+            icon: icon.into(),
+            title: title.into(),
+            description: description.into(),
+            frame: AdvancementFrame::Task,
+            background: None,
+            show_toast: true,
+            announce_to_chat: true,
+            hidden: false,
+        }
+    }
+
+    pub fn frame(mut self, frame: AdvancementFrame) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    pub fn background<T: Into<FrozenStr>>(mut self, background: Option<T>) -> Self {
+        self.background = background.map(Into::into);
+        self
+    }
+
+    pub fn show_toast(mut self, show_toast: bool) -> Self {
+        self.show_toast = show_toast;
+        self
+    }
+
+    pub fn announce_to_chat(mut self, announce_to_chat: bool) -> Self {
+        self.announce_to_chat = announce_to_chat;
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Reads the `display` object of an advancement. Missing settings get
+    /// the defaults of the game.
+    pub fn from_json(json: &str) -> Result<Self, DataPackErr> {
+        let value = parse(json)?;
+        let string = |pointer: &str| value.pointer(pointer).and_then(Value::as_str);
+        let flag =
+            |key: &str, default: bool| value.get(key).and_then(Value::as_bool).unwrap_or(default);
+        // the icon is an `id` since 1.20.5, an `item` before
+        let icon = string("/icon/id")
+            .or_else(|| string("/icon/item"))
+            .ok_or_else(|| DataPackErr::Missing("/icon/id".to_string()))?;
+        let frame = match string("/frame") {
+            Some(frame) => AdvancementFrame::from_name(frame)
+                .ok_or_else(|| DataPackErr::Json(format!("invalid frame {:?}", frame)))?,
+            None => AdvancementFrame::Task,
+        };
+        Ok(Self {
+            icon: icon.into(),
+            title: component_at(&value, "/title")?,
+            description: component_at(&value, "/description")?,
+            frame,
+            background: string("/background").map(Into::into),
+            show_toast: flag("show_toast", true),
+            announce_to_chat: flag("announce_to_chat", true),
+            hidden: flag("hidden", false),
+        })
+    }
+
+    /// Writes the `display` object of an advancement, pretty printed. The
+    /// title and description are serialized for the given version.
+    ///
+    /// Since 1.20.5 (protocol 766), the icon is written as `id` instead of
+    /// `item`.
+    pub fn to_json<V: Into<ProtocolVersion>>(&self, version: V) -> Result<String, DataPackErr> {
+        let version = version.into();
+        let mut icon = Map::new();
+        icon.insert(
+            if version < ProtocolVersion::V1_20_5 {
+                "item"
+            } else {
+                "id"
+            }
+            .to_string(),
+            self.icon.to_string().into(),
+        );
+        let mut display = Map::new();
+        display.insert("icon".to_string(), Value::Object(icon));
+        display.insert("title".to_string(), to_value(&self.title, version)?);
+        display.insert(
+            "description".to_string(),
+            to_value(&self.description, version)?,
+        );
+        display.insert("frame".to_string(), self.frame.name().into());
+        if let Some(ref background) = self.background {
+            display.insert("background".to_string(), background.to_string().into());
+        }
+        display.insert("show_toast".to_string(), self.show_toast.into());
+        display.insert("announce_to_chat".to_string(), self.announce_to_chat.into());
+        display.insert("hidden".to_string(), self.hidden.into());
+        serde_json::to_string_pretty(&display).map_err(|err| DataPackErr::Json(err.to_string()))
+    }
+}
+
+/// Reads the component at a JSON pointer in a data pack file, like
+/// `/display/title` in an advancement.
+///
+/// The path of an invalid component starts with the pointer.
+///
+/// # Example
+/// ```
+/// use mc_chat::datapack::read_component;
+/// use mc_chat::Chat;
+///
+/// let advancement = r#"{"display":{"title":{"text":"Getting Wood","bold":true},"icon":{"item":"minecraft:oak_log"}}}"#;
+/// assert_eq!(
+///     Chat::text("Getting Wood").bold(true),
+///     read_component(advancement, "/display/title").unwrap()
+/// );
+/// ```
+pub fn read_component(json: &str, pointer: &str) -> Result<Chat, DataPackErr> {
+    component_at(&parse(json)?, pointer)
+}
+
+/// Replaces or adds the component at a JSON pointer in a data pack file,
+/// serialized for the given version. The object it's in has to exist
+/// already, the rest of the file is kept.
+///
+/// The file is pretty printed, its keys keep their order only when the
+/// `preserve_order` feature of `serde_json` is enabled.
+///
+/// # Example
+/// ```
+/// use mc_chat::datapack::{read_component, write_component};
+/// use mc_chat::{Chat, TextColor};
+///
+/// let advancement = r#"{"display":{"icon":{"item":"minecraft:diamond"}},"criteria":{}}"#;
+/// let title = Chat::text("Shiny!").color(TextColor::Aqua);
+/// let advancement = write_component(advancement, "/display/title", &title, 763).unwrap();
+/// assert_eq!(title, read_component(&advancement, "/display/title").unwrap());
+/// ```
+pub fn write_component<V: Into<ProtocolVersion>>(
+    json: &str,
+    pointer: &str,
+    chat: &Chat,
+    version: V,
+) -> Result<String, DataPackErr> {
+    let mut value = parse(json)?;
+    let (parent, key) = pointer
+        .rfind('/')
+        .map(|index| (&pointer[..index], &pointer[index + 1..]))
+        .ok_or_else(|| DataPackErr::Missing(pointer.to_string()))?;
+    let object = value
+        .pointer_mut(parent)
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| DataPackErr::Missing(parent.to_string()))?;
+    // the escapes of a JSON pointer, in this order
+    let key = key.replace("~1", "/").replace("~0", "~");
+    object.insert(key, to_value(chat, version.into())?);
+    serde_json::to_string_pretty(&value).map_err(|err| DataPackErr::Json(err.to_string()))
+}
+
+fn parse(json: &str) -> Result<Value, DataPackErr> {
+    serde_json::from_str(json).map_err(|err| DataPackErr::Json(err.to_string()))
+}
+
+fn component_at(value: &Value, pointer: &str) -> Result<Chat, DataPackErr> {
+    let component = value
+        .pointer(pointer)
+        .ok_or_else(|| DataPackErr::Missing(pointer.to_string()))?;
+    from_value(component).map_err(|err| {
+        DataPackErr::Component(ChatParseErr {
+            path: format!("{}{}", pointer, err.path),
+            message: err.message,
+        })
+    })
+}
+
+fn to_value(chat: &Chat, version: ProtocolVersion) -> Result<Value, DataPackErr> {
+    let opts = SerializeOpts::default();
+    let ctx = SerializeCtx {
+        version,
+        opts: &opts,
+    };
+    serde_json::to_value(SerializeChat::new(&ctx, chat))
+        .map_err(|err| DataPackErr::Json(err.to_string()))
+}
+
+/// An error while reading or writing a data pack file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataPackErr {
+    /// The file isn't valid JSON or couldn't be written.
+    Json(String),
+    /// Nothing is at the JSON pointer.
+    Missing(String),
+    /// The component is invalid, its path is relative to the file.
+    Component(ChatParseErr),
+}
+
+impl Display for DataPackErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataPackErr::Json(err) => write!(f, "invalid data pack file: {}", err),
+            DataPackErr::Missing(pointer) => write!(f, "nothing found at {}", pointer),
+            DataPackErr::Component(err) => write!(f, "invalid component: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DataPackErr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextColor;
+
+    #[test]
+    fn pack_meta() {
+        let meta = PackMeta::new(
+            4,
+            Chat::text("Old").color(TextColor::from_rgb(0x33, 0x66, 0x99)),
+        );
+        // hex colors are since 1.16
+        assert!(!meta.to_json(404).unwrap().contains("color"));
+        assert!(meta
+            .to_json(735)
+            .unwrap()
+            .contains(r##""color": "#336699""##));
+        assert_eq!(
+            PackMeta::new(26, "Plain"),
+            PackMeta::from_json(
+                r#"{"pack":{"pack_format":26,"description":"Plain"},"overlays":{}}"#
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Err(DataPackErr::Missing("/pack/description".to_string())),
+            PackMeta::from_json(r#"{"pack":{"pack_format":26}}"#)
+        );
+    }
+
+    #[test]
+    fn advancement_display() {
+        let display = AdvancementDisplay::new("minecraft:stone", "Stone Age", "Mine stone")
+            .frame(AdvancementFrame::Challenge)
+            .background(Some("minecraft:textures/block/stone.png"))
+            .announce_to_chat(false);
+        let json = display.to_json(766).unwrap();
+        assert!(json.contains(r#""id": "minecraft:stone""#));
+        assert!(json.contains(r#""frame": "challenge""#));
+        assert_eq!(display, AdvancementDisplay::from_json(&json).unwrap());
+
+        let display = AdvancementDisplay::from_json(
+            r#"{"icon":{"item":"minecraft:book"},"title":"a","description":{"text":"b"}}"#,
+        )
+        .unwrap();
+        assert_eq!(AdvancementDisplay::new("minecraft:book", "a", "b"), display);
+        assert_eq!(
+            Err(DataPackErr::Json("invalid frame \"huge\"".to_string())),
+            AdvancementDisplay::from_json(
+                r#"{"icon":{"id":"minecraft:book"},"title":"a","description":"b","frame":"huge"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn components() {
+        let file = r#"{"display":{"title":{"text":"a","color":"nope"}}}"#;
+        match read_component(file, "/display/title") {
+            Err(DataPackErr::Component(err)) => assert_eq!("/display/title/color", err.path),
+            result => panic!("expected an invalid component, got {:?}", result),
+        }
+        assert_eq!(
+            Err(DataPackErr::Missing("/display".to_string())),
+            write_component("{}", "/display/title", &Chat::text("a"), 763)
+        );
+        let file = write_component(r#"{"a":{}}"#, "/a/b~1c", &Chat::text("x"), 763).unwrap();
+        assert_eq!(Chat::text("x"), read_component(&file, "/a/b~1c").unwrap());
+        assert!(file.contains(r#""b/c""#));
+    }
+}
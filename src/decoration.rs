@@ -0,0 +1,191 @@
+use crate::{freeze::FrozenStr, Chat, Style, TextColor, TranslationComponent};
+
+/// A value a [`ChatDecoration`] passes to its translation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChatTypeParameter {
+    /// The name of the player sending the message.
+    Sender,
+    /// The name of the player or team receiving the message.
+    Target,
+    /// The message itself.
+    Content,
+}
+
+/// The decoration of a chat type, like the ones in the `minecraft:chat_type`
+/// registry since 1.19: a translation with its parameters and a style.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, ChatDecoration, TranslationComponent};
+///
+/// let message = Chat::text("Hello!").decorate(&ChatDecoration::chat(), Chat::text("Steve"), None);
+/// assert_eq!(
+///     Chat::component(
+///         TranslationComponent::new("chat.type.text")
+///             .argument(Chat::text("Steve"))
+///             .argument(Chat::text("Hello!"))
+///     ),
+///     message
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChatDecoration {
+    pub translation_key: FrozenStr,
+    pub parameters: Vec<ChatTypeParameter>,
+    pub style: Style,
+}
+
+impl ChatDecoration {
+    /// Creates an unstyled decoration.
+    pub fn new<T: Into<FrozenStr>>(translation_key: T, parameters: Vec<ChatTypeParameter>) -> Self {
+        Self {
+            translation_key: translation_key.into(),
+            parameters,
+            style: Style::default(),
+        }
+    }
+
+    /// Change the style of the decoration.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// `minecraft:chat`, a message sent in chat: `<Sender> Content`.
+    pub fn chat() -> Self {
+        Self::new(
+            "chat.type.text",
+            vec![ChatTypeParameter::Sender, ChatTypeParameter::Content],
+        )
+    }
+
+    /// `minecraft:say_command`: `[Sender] Content`.
+    pub fn say_command() -> Self {
+        Self::new(
+            "chat.type.announcement",
+            vec![ChatTypeParameter::Sender, ChatTypeParameter::Content],
+        )
+    }
+
+    /// `minecraft:msg_command_incoming`: `Sender whispers to you: Content`.
+    pub fn msg_command_incoming() -> Self {
+        Self::new(
+            "commands.message.display.incoming",
+            vec![ChatTypeParameter::Sender, ChatTypeParameter::Content],
+        )
+        .style(whisper_style())
+    }
+
+    /// `minecraft:msg_command_outgoing`: `You whisper to Target: Content`.
+    pub fn msg_command_outgoing() -> Self {
+        Self::new(
+            "commands.message.display.outgoing",
+            vec![ChatTypeParameter::Target, ChatTypeParameter::Content],
+        )
+        .style(whisper_style())
+    }
+
+    /// `minecraft:team_msg_command_incoming`: `Target <Sender> Content`.
+    pub fn team_msg_command_incoming() -> Self {
+        Self::new(
+            "chat.type.team.text",
+            vec![
+                ChatTypeParameter::Target,
+                ChatTypeParameter::Sender,
+                ChatTypeParameter::Content,
+            ],
+        )
+    }
+
+    /// `minecraft:team_msg_command_outgoing`: `Target -> <Sender> Content`.
+    pub fn team_msg_command_outgoing() -> Self {
+        Self::new(
+            "chat.type.team.sent",
+            vec![
+                ChatTypeParameter::Target,
+                ChatTypeParameter::Sender,
+                ChatTypeParameter::Content,
+            ],
+        )
+    }
+
+    /// `minecraft:emote_command`: `* Sender Content`.
+    pub fn emote_command() -> Self {
+        Self::new(
+            "chat.type.emote",
+            vec![ChatTypeParameter::Sender, ChatTypeParameter::Content],
+        )
+    }
+}
+
+fn whisper_style() -> Style {
+    let mut style = Style::new();
+    style.color(TextColor::Gray).italic(true);
+    style
+}
+
+impl Chat {
+    /// Wraps this message in the translation of a chat type, like the client
+    /// does for messages sent with a chat type since 1.19.
+    ///
+    /// A missing target is passed as empty text.
+    pub fn decorate(self, decoration: &ChatDecoration, sender: Chat, target: Option<Chat>) -> Chat {
+        let mut content = Some(self);
+        let mut sender = Some(sender);
+        let mut target = target;
+        let mut translation = TranslationComponent::new(decoration.translation_key.clone());
+        for parameter in &decoration.parameters {
+            let argument = match parameter {
+                ChatTypeParameter::Sender => sender.take(),
+                ChatTypeParameter::Target => target.take(),
+                ChatTypeParameter::Content => content.take(),
+            };
+            translation = translation.argument(argument.unwrap_or_else(|| Chat::text("")));
+        }
+        let mut chat = Chat::component(translation);
+        chat.style = decoration.style.clone();
+        chat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whisper() {
+        let message = Chat::text("Hi").decorate(
+            &ChatDecoration::msg_command_outgoing(),
+            Chat::text("Steve"),
+            Some(Chat::text("Alex")),
+        );
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("commands.message.display.outgoing")
+                    .argument(Chat::text("Alex"))
+                    .argument(Chat::text("Hi"))
+            )
+            .color(TextColor::Gray)
+            .italic(true),
+            message
+        );
+    }
+
+    #[test]
+    fn team() {
+        let message = Chat::text("Hi").decorate(
+            &ChatDecoration::team_msg_command_incoming(),
+            Chat::text("Steve"),
+            None,
+        );
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("chat.type.team.text")
+                    .argument(Chat::text(""))
+                    .argument(Chat::text("Steve"))
+                    .argument(Chat::text("Hi"))
+            ),
+            message
+        );
+    }
+}
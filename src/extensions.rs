@@ -0,0 +1,175 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// Values of any type attached to a [`Chat`](crate::Chat) component,
+/// at most one for every type.
+///
+/// Extensions carry data for the server building a component, like the
+/// permission needed to see it. They are not part of the component itself:
+/// they are never serialized and are ignored when comparing or hashing.
+///
+/// # Example
+/// ```
+/// use mc_chat::Chat;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Permission(&'static str);
+///
+/// let chat = Chat::text("Ban").extension(Permission("server.ban"));
+/// assert_eq!(Some(&Permission("server.ban")), chat.extensions.get::<Permission>());
+/// assert_eq!(Chat::text("Ban"), chat);
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions {
+    // boxed so components without extensions stay small
+    #[allow(clippy::box_collection)]
+    map: Option<Box<HashMap<TypeId, Box<dyn Extension>>>>,
+}
+
+impl Extensions {
+    pub const fn new() -> Self {
+        Extensions { map: None }
+    }
+
+    /// Attaches a value, returning the value of the same type it replaces.
+    pub fn insert<T: Clone + Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.map
+            .get_or_insert_with(Default::default)
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.into_any().downcast().ok().map(|old| *old))
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.map
+            .as_ref()?
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.as_ref().as_any().downcast_ref())
+    }
+
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.map
+            .as_mut()?
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.as_mut().as_any_mut().downcast_mut())
+    }
+
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.map
+            .as_mut()?
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.into_any().downcast().ok().map(|value| *value))
+    }
+
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.map
+            .as_ref()
+            .is_some_and(|map| map.contains_key(&TypeId::of::<T>()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.as_ref().map_or(0, |map| map.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.map = None;
+    }
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Extensions are not part of the component, every two are equal.
+impl PartialEq for Extensions {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Extensions {}
+
+impl Hash for Extensions {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+trait Extension: Any + Send + Sync {
+    fn clone_box(&self) -> Box<dyn Extension>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Clone + Send + Sync + 'static> Extension for T {
+    fn clone_box(&self) -> Box<dyn Extension> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl Clone for Box<dyn Extension> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chat;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct PlaceholderId(u32);
+
+    #[test]
+    fn typed() {
+        let mut extensions = Extensions::new();
+        assert!(extensions.is_empty());
+        assert_eq!(None, extensions.insert(PlaceholderId(1)));
+        assert_eq!(None, extensions.insert("permission"));
+        assert_eq!(Some(PlaceholderId(1)), extensions.insert(PlaceholderId(2)));
+        assert_eq!(2, extensions.len());
+
+        extensions.get_mut::<PlaceholderId>().unwrap().0 += 1;
+        let copy = extensions.clone();
+        assert_eq!(Some(PlaceholderId(3)), extensions.remove::<PlaceholderId>());
+        assert!(!extensions.contains::<PlaceholderId>());
+        assert_eq!(Some(&PlaceholderId(3)), copy.get::<PlaceholderId>());
+        assert_eq!(Some(&"permission"), copy.get::<&str>());
+    }
+
+    #[test]
+    fn transformations() {
+        let chat = Chat::text("Hi")
+            .extension(PlaceholderId(1))
+            .child(Chat::text("!").extension(PlaceholderId(2)));
+        let frozen = chat.clone().freeze().thaw();
+        assert_eq!(
+            Some(&PlaceholderId(2)),
+            frozen.children[0].extensions.get::<PlaceholderId>()
+        );
+    }
+}
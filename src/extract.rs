@@ -0,0 +1,123 @@
+//! Finding chat components in text that isn't JSON itself, like the lines
+//! of a server log with packet dumps or echoed `/tellraw` commands.
+
+use crate::Chat;
+
+/// A part of a line: either plain text or a chat component found in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineSegment<'a> {
+    Text(&'a str),
+    Chat(Box<Chat>),
+}
+
+/// Splits a line into the chat components embedded in it and the text
+/// around them, in their original order.
+///
+/// Every JSON object or array that deserializes into a [`Chat`] counts as a
+/// component, other JSON and unbalanced brackets are left in the text.
+///
+/// # Example
+/// ```
+/// use mc_chat::extract::{extract_chat, LineSegment};
+/// use mc_chat::{Chat, TextColor};
+///
+/// let line = r#"[12:00:00 INFO]: Sent {"text":"Welcome!","color":"gold"} to Steve"#;
+/// assert_eq!(
+///     vec![
+///         LineSegment::Text("[12:00:00 INFO]: Sent "),
+///         LineSegment::Chat(Box::new(Chat::text("Welcome!").color(TextColor::Gold))),
+///         LineSegment::Text(" to Steve"),
+///     ],
+///     extract_chat(line)
+/// );
+/// ```
+pub fn extract_chat(line: &str) -> Vec<LineSegment<'_>> {
+    let mut segments = Vec::new();
+    // the start of the text that hasn't been added yet
+    let mut text_start = 0;
+    let mut position = 0;
+    while let Some(offset) = line[position..].find(['{', '[']) {
+        let start = position + offset;
+        match parse_at(&line[start..]) {
+            Some((chat, length)) => {
+                if text_start < start {
+                    segments.push(LineSegment::Text(&line[text_start..start]));
+                }
+                segments.push(LineSegment::Chat(Box::new(chat)));
+                position = start + length;
+                text_start = position;
+            }
+            // both brackets are a single byte
+            None => position = start + 1,
+        }
+    }
+    if text_start < line.len() {
+        segments.push(LineSegment::Text(&line[text_start..]));
+    }
+    segments
+}
+
+/// Returns only the chat components embedded in a line, see [`extract_chat()`].
+pub fn extract_chats(line: &str) -> Vec<Chat> {
+    extract_chat(line)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            LineSegment::Chat(chat) => Some(*chat),
+            LineSegment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Parses the component at the start of the text and returns it with its length in bytes.
+fn parse_at(text: &str) -> Option<(Chat, usize)> {
+    let mut stream = serde_json::Deserializer::from_str(text).into_iter::<Chat>();
+    match stream.next() {
+        Some(Ok(chat)) => Some((chat, stream.byte_offset())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_only() {
+        assert_eq!(
+            vec![LineSegment::Text("[Server] no {json} here [")],
+            extract_chat("[Server] no {json} here [")
+        );
+        assert!(extract_chat("").is_empty());
+    }
+
+    #[test]
+    fn multiple() {
+        let line = r#"tellraw @a ["",{"text":"a"}]{"keybind":"key.jump"}{"b":1}"#;
+        let segments = extract_chat(line);
+        assert_eq!(4, segments.len());
+        assert_eq!(LineSegment::Text("tellraw @a "), segments[0]);
+        assert_eq!(
+            LineSegment::Chat(Box::new(Chat::text("").child(Chat::text("a")))),
+            segments[1]
+        );
+        assert_eq!(
+            LineSegment::Chat(Box::new(Chat::keybind("key.jump"))),
+            segments[2]
+        );
+        assert_eq!(LineSegment::Text(r#"{"b":1}"#), segments[3]);
+        assert_eq!(2, extract_chats(line).len());
+    }
+
+    #[test]
+    fn nested_in_brackets() {
+        let line = r#"[Packet {"text":"Hi"}]"#;
+        assert_eq!(
+            vec![
+                LineSegment::Text("[Packet "),
+                LineSegment::Chat(Box::new(Chat::text("Hi"))),
+                LineSegment::Text("]"),
+            ],
+            extract_chat(line)
+        );
+    }
+}
@@ -14,22 +14,86 @@
 //! whole chat component in a single [`Arc`](std::sync::Arc). This means that [`FrozenStr`] is
 //! implemented as a simple wrapper around [`Box<str>`].
 //!
+//! That single [`Arc`](std::sync::Arc) is what [`Chat::freeze()`] gives you: a [`FrozenChat`]
+//! shares the **whole tree** (including all of its strings) behind one reference count,
+//! so broadcasting it to many players only ever bumps that counter. Nothing inside the tree
+//! becomes shared on its own, every [`FrozenStr`] stays an owned [`Box<str>`].
+//! [`FrozenChat::thaw()`] hands back an owned [`Chat`] again, which only clones the tree
+//! when other handles to it are still alive.
+//!
+//! A [`FrozenStr`] can also borrow a `&'static str` with [`FrozenStr::from_static()`],
+//! which is a `const fn`. Fixed messages built with [`Chat::static_text()`] can live
+//! in a `static` and cloning them doesn't allocate.
+//!
 
-use std::{fmt::Display, ops::Deref};
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
 
+use crate::Chat;
+
+#[cfg(feature = "serde")]
 use serde::{de::Visitor, Deserialize, Serialize};
 
 /// Efficient immutable string.
 ///
 /// See the [module](self)'s documentation.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Clone)]
 pub struct FrozenStr {
-    str: Box<str>,
+    str: Repr,
+}
+
+#[derive(Clone)]
+enum Repr {
+    Static(&'static str),
+    Boxed(Box<str>),
+}
+
+impl FrozenStr {
+    /// Borrows a string that lives forever instead of copying it.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::freeze::FrozenStr;
+    ///
+    /// const GREETING: FrozenStr = FrozenStr::from_static("Welcome!");
+    /// assert_eq!(FrozenStr::from("Welcome!"), GREETING);
+    /// ```
+    pub const fn from_static(str: &'static str) -> Self {
+        Self {
+            str: Repr::Static(str),
+        }
+    }
 }
 
 impl Display for FrozenStr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.str.fmt(f)
+        self.deref().fmt(f)
+    }
+}
+
+impl std::fmt::Debug for FrozenStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrozenStr")
+            .field("str", &self.deref())
+            .finish()
+    }
+}
+
+impl PartialEq for FrozenStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl Eq for FrozenStr {}
+
+impl Hash for FrozenStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
     }
 }
 
@@ -38,7 +102,9 @@ where
     T: Into<Box<str>>,
 {
     fn from(str: T) -> Self {
-        Self { str: str.into() }
+        Self {
+            str: Repr::Boxed(str.into()),
+        }
     }
 }
 
@@ -46,7 +112,64 @@ impl Deref for FrozenStr {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        self.str.deref()
+        match self.str {
+            Repr::Static(str) => str,
+            Repr::Boxed(ref str) => str,
+        }
+    }
+}
+
+/// A shared, immutable [`Chat`] component.
+///
+/// Created by [`Chat::freeze()`], see the [module](self)'s documentation.
+/// Cloning a frozen component is cheap, the component itself can be accessed
+/// through [`Deref`].
+///
+/// # Example
+/// ```
+/// use mc_chat::Chat;
+///
+/// let frozen = Chat::text("Server restarting soon!").freeze();
+/// let for_other_player = frozen.clone();
+/// assert_eq!(frozen, for_other_player);
+///
+/// let chat = for_other_player.thaw().bold(true);
+/// assert_ne!(*frozen, chat);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FrozenChat {
+    chat: Arc<Chat>,
+}
+
+impl FrozenChat {
+    /// Turn this frozen component back into an owned [`Chat`].
+    ///
+    /// The tree is only cloned if other [`FrozenChat`] handles
+    /// to the same component still exist.
+    pub fn thaw(self) -> Chat {
+        Arc::try_unwrap(self.chat).unwrap_or_else(|chat| chat.deref().clone())
+    }
+}
+
+impl From<Chat> for FrozenChat {
+    fn from(chat: Chat) -> Self {
+        Self {
+            chat: Arc::new(chat),
+        }
+    }
+}
+
+impl Deref for FrozenChat {
+    type Target = Chat;
+
+    fn deref(&self) -> &Self::Target {
+        &self.chat
+    }
+}
+
+impl AsRef<Chat> for FrozenChat {
+    fn as_ref(&self) -> &Chat {
+        &self.chat
     }
 }
 
@@ -103,12 +226,46 @@ impl<'de> Deserialize<'de> for FrozenStr {
 
 #[cfg(test)]
 mod tests {
-    use serde_test::{assert_tokens, Token};
-
     use super::*;
+    use crate::HoverEvent;
+
+    /// The address of the component's boxed hover text.
+    fn hover_text(chat: &Chat) -> *const Chat {
+        match chat.style.hover_event {
+            Some(HoverEvent::ShowText(ref text)) => &**text,
+            _ => panic!("no hover text"),
+        }
+    }
+
+    fn chat() -> Chat {
+        Chat::text("Hello world").hover(Some(HoverEvent::ShowText(Box::new(Chat::text("Hi")))))
+    }
 
+    #[test]
+    fn thaw_unique() {
+        let frozen = chat().freeze();
+        let text = hover_text(&frozen);
+        let chat = frozen.thaw();
+        assert_eq!(self::chat(), chat);
+        // moved out of the Arc instead of cloned
+        assert_eq!(text, hover_text(&chat));
+    }
+
+    #[test]
+    fn thaw_shared() {
+        let frozen = chat().freeze();
+        let shared = frozen.clone();
+        let chat = frozen.thaw().italic(true);
+        assert_eq!(self::chat(), *shared);
+        assert_eq!(self::chat().italic(true), chat);
+        assert_ne!(hover_text(&shared), hover_text(&chat));
+    }
+
+    #[cfg(feature = "serde")]
     #[test]
     fn test_serde() {
+        use serde_test::{assert_tokens, Token};
+
         let str: FrozenStr = "Hello world".into();
         assert_tokens(&str, &[Token::BorrowedStr("Hello world")]);
     }
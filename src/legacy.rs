@@ -0,0 +1,1102 @@
+//! Runtime parsing of legacy formatted text.
+//!
+//! [`parse_format()`] builds the same [`Chat`] tree as the `chat!` macro,
+//! which makes it useful for format strings that are only known at runtime,
+//! e.g. messages loaded from a config file:
+//! - Formatting codes (`§a`, `§l`, `§r`...) style the text after them.
+//!   Color codes reset the formatting codes in front of them, like in the
+//!   vanilla client.
+//! - `§#RRGGBB` and `§x§R§R§G§G§B§B` set a custom hex color.
+//! - `§<#RRGGBB:#RRGGBB>text§>` colors the text with a gradient.
+//! - `{}` inserts the next [`FormatPart::Value`], `{{` and `}}` escape the braces.
+//! - The delimiter written twice (`§§`) is the delimiter itself.
+//! - Parts following a value become its children and inherit its style,
+//!   unless the value is styled itself.
+//!
+//! [`check_format()`] finds every problem in a format along with where it
+//! is, e.g. to point users at the exact bad character in their config.
+//!
+//! [`to_legacy()`] goes the other way, for places where older clients
+//! only accept legacy formatted text.
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use crate::metrics::raw_text;
+use crate::{Chat, ProtocolVersion, Style, TextColor};
+
+const HEX_DIGITS: &str = "0123456789abcdefABCDEF";
+
+/// A piece of a format, see [`parse_format()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FormatPart<'a> {
+    /// Legacy formatted text.
+    Literal(&'a str),
+    /// A chat component inserted as is.
+    Value(Box<Chat>),
+}
+
+impl<'a> From<&'a str> for FormatPart<'a> {
+    fn from(literal: &'a str) -> Self {
+        FormatPart::Literal(literal)
+    }
+}
+
+impl From<Chat> for FormatPart<'_> {
+    fn from(value: Chat) -> Self {
+        FormatPart::Value(Box::new(value))
+    }
+}
+
+/// An error while parsing legacy formatted text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LegacyParseErr {
+    /// An unknown formatting code, e.g. `§z`.
+    InvalidCode(String),
+    /// The delimiter at the very end of a literal.
+    DanglingDelimiter,
+    /// A `§#` or `§x` code not followed by 6 hex digits.
+    InvalidHexColor(String),
+    /// A gradient without a closing `>` after its colors.
+    UnclosedGradient,
+    /// A gradient end `§>` without a gradient.
+    UnexpectedGradientEnd,
+    /// A gradient color that isn't written as `#RRGGBB`.
+    InvalidGradientColor(String),
+    /// A gradient with less than two colors.
+    MissingGradientColor,
+    /// A formatting code inside a gradient's text.
+    CodeInGradient,
+    /// A `{}` placeholder without a value following the literal.
+    MissingArgument,
+    /// A `{name}` placeholder, only `{}` is supported at runtime.
+    NamedPlaceholder(String),
+    /// An unescaped `{` or `}`.
+    UnmatchedBrace,
+}
+
+impl Display for LegacyParseErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LegacyParseErr::InvalidCode(code) => write!(f, "Invalid formatting code `{}`", code),
+            LegacyParseErr::DanglingDelimiter => {
+                write!(f, "Dangling delimiter at the end of the literal")
+            }
+            LegacyParseErr::InvalidHexColor(code) => write!(f, "Invalid hex color `{}`", code),
+            LegacyParseErr::UnclosedGradient => write!(f, "Unclosed gradient, expected `>`"),
+            LegacyParseErr::UnexpectedGradientEnd => {
+                write!(f, "Gradient end found without a gradient")
+            }
+            LegacyParseErr::InvalidGradientColor(color) => {
+                write!(f, "Invalid gradient color `{}`, expected `#RRGGBB`", color)
+            }
+            LegacyParseErr::MissingGradientColor => {
+                write!(f, "A gradient needs at least two colors")
+            }
+            LegacyParseErr::CodeInGradient => {
+                write!(f, "Formatting codes are not allowed inside a gradient")
+            }
+            LegacyParseErr::MissingArgument => {
+                write!(f, "Missing argument for a `{{}}` placeholder")
+            }
+            LegacyParseErr::NamedPlaceholder(name) => {
+                write!(f, "Named placeholder `{{{}}}` isn't supported", name)
+            }
+            LegacyParseErr::UnmatchedBrace => {
+                write!(f, "Unmatched brace, use `{{{{` or `}}}}` to escape it")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LegacyParseErr {}
+
+/// A problem [`check_format()`] found in a literal of a format.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LegacyDiagnostic {
+    /// The index of the literal in the parts of the format.
+    pub part: usize,
+    /// The byte range of the offending characters in the literal.
+    pub span: Range<usize>,
+    /// The offending characters, e.g. `§z`.
+    pub code: String,
+    pub err: LegacyParseErr,
+}
+
+impl Display for LegacyDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{} of part {}",
+            self.err, self.span.start, self.span.end, self.part
+        )
+    }
+}
+
+/// Checks the literals of a format for everything [`parse_format()`]
+/// rejects, without stopping at the first problem. The diagnostics are in
+/// the order they appear in.
+///
+/// # Example
+/// ```
+/// use mc_chat::legacy::{check_format, LegacyParseErr};
+///
+/// let diagnostics = check_format("&", &["&aHello &zworld {name}".into()]);
+/// assert_eq!(2, diagnostics.len());
+/// assert_eq!(8..10, diagnostics[0].span);
+/// assert_eq!("&z", diagnostics[0].code);
+/// assert_eq!(LegacyParseErr::NamedPlaceholder("name".into()), diagnostics[1].err);
+/// ```
+pub fn check_format(pattern: &str, parts: &[FormatPart]) -> Vec<LegacyDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut index = 0;
+    while index < parts.len() {
+        let literal = match parts[index] {
+            FormatPart::Literal(literal) => literal,
+            FormatPart::Value(_) => {
+                index += 1;
+                continue;
+            }
+        };
+        let mut problems = Vec::new();
+        check_codes(literal, pattern, &mut problems);
+        let placeholders = check_braces(literal, &mut problems);
+        // the text between placeholders is parsed separately
+        let mut start = 0;
+        for placeholder in placeholders
+            .iter()
+            .chain(Some(&(literal.len()..literal.len())))
+        {
+            check_gradients(literal, start..placeholder.start, pattern, &mut problems);
+            start = placeholder.end;
+        }
+        let part = index;
+        for placeholder in placeholders {
+            // every placeholder takes the part after the previous one
+            index += 1;
+            if !matches!(parts.get(index), Some(FormatPart::Value(_))) {
+                problems.push((placeholder, LegacyParseErr::MissingArgument));
+            }
+        }
+        problems.sort_by_key(|(span, _)| span.start);
+        diagnostics.extend(problems.into_iter().map(|(span, err)| LegacyDiagnostic {
+            part,
+            code: literal[span.clone()].to_string(),
+            span,
+            err,
+        }));
+        index += 1;
+    }
+    diagnostics
+}
+
+/// Parses legacy formatted literals and values into a chat component,
+/// exactly like the `chat!` macro does. See the [module](self)'s documentation.
+///
+/// # Example
+/// ```
+/// use mc_chat::{legacy::parse_format, Chat, TextColor};
+///
+/// let format = "§eWelcome §l{}§r!";
+/// let chat = parse_format("§", vec![format.into(), Chat::text("Steve").into()]).unwrap();
+/// assert_eq!(
+///     Chat::text("")
+///         .child(
+///             Chat::text("Welcome ")
+///                 .color(TextColor::Yellow)
+///                 .child(Chat::text("Steve").bold(true))
+///         )
+///         .child(Chat::text("!")),
+///     chat
+/// );
+/// ```
+pub fn parse_format<'a, I>(pattern: &str, parts: I) -> Result<Chat, LegacyParseErr>
+where
+    I: IntoIterator<Item = FormatPart<'a>>,
+{
+    let parts = parts.into_iter().collect::<Vec<_>>();
+    if let Some(diagnostic) = check_format(pattern, &parts).into_iter().next() {
+        return Err(diagnostic.err);
+    }
+
+    // the same algorithm as `map_to_tree()` of the macros, the
+    // `legacy_parity` test in tests/macros.rs keeps them in sync
+    let mut root = Node::new(Chat::text(""));
+    let mut current_parent = Node::default();
+    for part in expand_placeholders(parts)?.into_iter().rev() {
+        let part = match part {
+            Part::Literal(literal) => literal,
+            Part::Value(value) if value.style.is_plain() => {
+                let mut node = Node::new(value);
+                if current_parent.is_placeholder() {
+                    node.children.extend(current_parent.children);
+                } else {
+                    current_parent.children.reverse();
+                    node.children.push(current_parent);
+                }
+                current_parent = node;
+                continue;
+            }
+            Part::Value(value) => {
+                // The value's own style only applies to itself, the parts
+                // following it are siblings inside an unstyled container instead.
+                let mut node = Node::new(Chat::text(""));
+                if current_parent.is_placeholder() {
+                    node.children.extend(current_parent.children);
+                } else {
+                    current_parent.children.reverse();
+                    node.children.push(current_parent);
+                }
+                node.children.push(Node::new(value));
+                current_parent = node;
+                continue;
+            }
+        };
+
+        let value = collapse_hex_sequences(&part, pattern);
+        let value = expand_gradients(&value, pattern)?;
+        let pieces = split_codes(&value, pattern);
+        let mut piece_iter = pieces.iter().rev().map(String::as_str);
+        let mut piece = piece_iter.next().unwrap_or_default();
+        let mut next_piece = piece_iter.next();
+        loop {
+            if piece.is_empty() {
+                if next_piece.is_some() {
+                    return Err(LegacyParseErr::DanglingDelimiter);
+                }
+            } else if next_piece.is_none() {
+                if !current_parent.is_placeholder() {
+                    // text in front of a styled node is its sibling, not its child
+                    current_parent.children.reverse();
+                    let node = current_parent;
+                    current_parent = Node::default();
+                    current_parent.children.push(node);
+                }
+                current_parent.children.push(Node::new(Chat::text(piece)));
+            } else {
+                let mut chars = piece.chars();
+                let code = chars.next().unwrap_or_default();
+                let mut rest = chars.as_str();
+
+                let color = if code == '#' {
+                    let (color, remaining) = split_hex_color(rest)?;
+                    rest = remaining;
+                    Some(color)
+                } else {
+                    color_from_code(code)
+                };
+
+                if let Some(color) = color {
+                    if current_parent.is_placeholder() {
+                        if !rest.is_empty() {
+                            let mut node = Node::new(Chat::text(rest));
+                            node.color = Some(color);
+                            current_parent.children.push(node);
+                        }
+                    } else if !rest.is_empty() {
+                        let mut node = Node::new(Chat::text(rest));
+                        node.color = Some(color);
+                        // reverse for correct left to right order
+                        current_parent.children.reverse();
+                        node.children.push(current_parent);
+                        current_parent = Node::default();
+                        current_parent.children.push(node);
+                    } else if current_parent.color.is_none() {
+                        current_parent.color = Some(color);
+                    }
+                } else if code == 'r' {
+                    if current_parent.is_placeholder() {
+                        root.children.extend(current_parent.children);
+                    } else {
+                        current_parent.children.reverse();
+                        root.children.push(current_parent);
+                    }
+                    current_parent = Node::default();
+                    if !rest.is_empty() {
+                        root.children.push(Node::new(Chat::text(rest)));
+                    }
+                } else if "klmno".contains(code) {
+                    if !rest.is_empty() {
+                        let mut node = Node::new(Chat::text(rest));
+                        node.styles.insert(code);
+                        if current_parent.is_placeholder() {
+                            node.children.extend(current_parent.children);
+                        } else {
+                            current_parent.children.reverse();
+                            node.children.push(current_parent);
+                        }
+                        current_parent = node;
+                    } else if !current_parent.is_placeholder()
+                        || !current_parent.children.is_empty()
+                    {
+                        current_parent.styles.insert(code);
+                        if current_parent.content.is_none() {
+                            current_parent.content = Some(Chat::text(""));
+                        }
+                    }
+                } else {
+                    return Err(LegacyParseErr::InvalidCode(format!("{}{}", pattern, code)));
+                }
+            }
+
+            match next_piece {
+                Some(next) => {
+                    piece = next;
+                    next_piece = piece_iter.next();
+                }
+                None => break,
+            }
+        }
+    }
+    if current_parent.is_placeholder() {
+        root.children.extend(current_parent.children);
+    } else {
+        current_parent.children.reverse();
+        root.children.push(current_parent);
+    }
+    if root.children.len() == 1 {
+        Ok(root.children.remove(0).into_chat())
+    } else {
+        root.children.reverse();
+        Ok(root.into_chat())
+    }
+}
+
+/// Writes a chat component as legacy formatted text, the reverse of [`parse_format()`].
+///
+/// Components other than text are written as their raw contents (e.g. the
+/// translation key) and events are left out. Since 1.16, custom colors are
+/// written as `§x§R§R§G§G§B§B`, older versions leave them out. The delimiter
+/// isn't escaped inside the text, the result is meant to be sent to clients.
+///
+/// # Example
+/// ```
+/// use mc_chat::{legacy::to_legacy, Chat, TextColor, VERSION_1_8};
+///
+/// let chat = Chat::text("Welcome ")
+///     .color(TextColor::Yellow)
+///     .child(Chat::text("Steve").bold(true))
+///     .child(Chat::text("!").color(TextColor::Yellow));
+/// assert_eq!("§eWelcome §lSteve§e!", to_legacy(&chat, "§", VERSION_1_8));
+/// ```
+pub fn to_legacy<V: Into<ProtocolVersion>>(chat: &Chat, pattern: &str, version: V) -> String {
+    let mut writer = LegacyWriter {
+        pattern,
+        version: version.into(),
+        color: None,
+        codes: Vec::new(),
+        result: String::new(),
+    };
+    writer.write(chat, &Style::default());
+    writer.result
+}
+
+/// Keeps track of the formatting codes that are in effect while writing.
+struct LegacyWriter<'a> {
+    pattern: &'a str,
+    version: ProtocolVersion,
+    color: Option<String>,
+    codes: Vec<char>,
+    result: String,
+}
+
+impl LegacyWriter<'_> {
+    fn write(&mut self, chat: &Chat, parent: &Style) {
+        let style = chat.style.inherit(parent);
+        let text = raw_text(&chat.kind);
+        if !text.is_empty() {
+            let color = style
+                .color
+                .as_ref()
+                .and_then(|color| self.color_code(color));
+            let codes = [
+                ('k', style.obfuscated),
+                ('l', style.bold),
+                ('m', style.strikethrough),
+                ('n', style.underlined),
+                ('o', style.italic),
+            ]
+            .iter()
+            .filter(|(_, enabled)| *enabled == Some(true))
+            .map(|(code, _)| *code)
+            .collect::<Vec<_>>();
+
+            if color != self.color || self.codes.iter().any(|code| !codes.contains(code)) {
+                // a color code resets the formatting codes as well
+                match color {
+                    Some(ref color) => self.result.push_str(color),
+                    None => {
+                        self.result.push_str(self.pattern);
+                        self.result.push('r');
+                    }
+                }
+                self.codes.clear();
+            }
+            for code in codes {
+                if !self.codes.contains(&code) {
+                    self.result.push_str(self.pattern);
+                    self.result.push(code);
+                    self.codes.push(code);
+                }
+            }
+            self.color = color;
+            self.result.push_str(text);
+        }
+        for child in &chat.children {
+            self.write(child, &style);
+        }
+    }
+
+    fn color_code(&self, color: &TextColor) -> Option<String> {
+        let code = match color {
+            TextColor::Black => '0',
+            TextColor::DarkBlue => '1',
+            TextColor::DarkGreen => '2',
+            TextColor::DarkCyan => '3',
+            TextColor::DarkRed => '4',
+            TextColor::Purple => '5',
+            TextColor::Gold => '6',
+            TextColor::Gray => '7',
+            TextColor::DarkGray => '8',
+            TextColor::Blue => '9',
+            TextColor::Green => 'a',
+            TextColor::Cyan => 'b',
+            TextColor::Red => 'c',
+            TextColor::Pink => 'd',
+            TextColor::Yellow => 'e',
+            TextColor::White => 'f',
+            TextColor::Custom(rgb) => {
+                if !self.version.supports_hex_colors() {
+                    return None;
+                }
+                let mut code = format!("{}x", self.pattern);
+                for digit in format!("{:06x}", rgb.to_u32()).chars() {
+                    code.push_str(self.pattern);
+                    code.push(digit);
+                }
+                return Some(code);
+            }
+            TextColor::Reset => return None,
+        };
+        Some(format!("{}{}", self.pattern, code))
+    }
+}
+
+/// A chat component while the tree is being built.
+#[derive(Default)]
+struct Node {
+    content: Option<Chat>,
+    color: Option<TextColor>,
+    styles: HashSet<char>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(content: Chat) -> Self {
+        Self {
+            content: Some(content),
+            ..Default::default()
+        }
+    }
+
+    fn is_placeholder(&self) -> bool {
+        self.color.is_none() && self.styles.is_empty() && self.content.is_none()
+    }
+
+    fn into_chat(self) -> Chat {
+        let mut chat = self.content.unwrap_or_else(|| Chat::text(""));
+        if let Some(color) = self.color {
+            chat = chat.color(color);
+        }
+        for code in self.styles {
+            chat = match code {
+                'k' => chat.obfuscated(true),
+                'l' => chat.bold(true),
+                'm' => chat.strikethrough(true),
+                'n' => chat.underlined(true),
+                _ => chat.italic(true),
+            };
+        }
+        for child in self.children {
+            chat = chat.child(child.into_chat());
+        }
+        chat
+    }
+}
+
+/// A part after expanding the placeholders.
+#[allow(clippy::large_enum_variant)]
+enum Part {
+    Literal(String),
+    Value(Chat),
+}
+
+/// Checks the formatting codes of a literal before anything else is expanded.
+fn check_codes(value: &str, pattern: &str, problems: &mut Vec<(Range<usize>, LegacyParseErr)>) {
+    let mut offset = 0;
+    while let Some(index) = value[offset..].find(pattern) {
+        let index = offset + index;
+        let after = &value[index + pattern.len()..];
+        let code = match after.chars().next() {
+            Some(code) => code,
+            None => {
+                problems.push((index..value.len(), LegacyParseErr::DanglingDelimiter));
+                return;
+            }
+        };
+        let mut end = index + pattern.len() + code.len_utf8();
+        if after.starts_with(pattern) {
+            // escaped delimiter
+            end = index + 2 * pattern.len();
+        } else if code == '#' {
+            let digits: String = after[1..].chars().take(6).collect();
+            end += digits.len();
+            if digits.len() != 6 || !digits.chars().all(|c| HEX_DIGITS.contains(c)) {
+                let code = value[index..end].to_string();
+                problems.push((index..end, LegacyParseErr::InvalidHexColor(code)));
+            }
+        } else if code == 'x' {
+            let mut sequence = &after[1..];
+            for _ in 0..6 {
+                let digit = sequence
+                    .strip_prefix(pattern)
+                    .and_then(|rest| rest.chars().next())
+                    .filter(|c| HEX_DIGITS.contains(*c));
+                if digit.is_none() {
+                    let code = value[index..end].to_string();
+                    problems.push((index..end, LegacyParseErr::InvalidHexColor(code)));
+                    break;
+                }
+                sequence = &sequence[pattern.len() + 1..];
+                end += pattern.len() + 1;
+            }
+        } else if !"0123456789abcdefklmnor<>".contains(code) {
+            let code = value[index..end].to_string();
+            problems.push((index..end, LegacyParseErr::InvalidCode(code)));
+        }
+        offset = end;
+    }
+}
+
+/// Checks the braces of a literal, returning where its `{}` placeholders are.
+fn check_braces(
+    value: &str,
+    problems: &mut Vec<(Range<usize>, LegacyParseErr)>,
+) -> Vec<Range<usize>> {
+    let mut placeholders = Vec::new();
+    let bytes = value.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' | b'}' if bytes.get(index + 1) == Some(&bytes[index]) => index += 1,
+            b'{' => match value[index..].find('}') {
+                Some(len) => {
+                    let name = value[index + 1..index + len].trim();
+                    if name.is_empty() {
+                        placeholders.push(index..index + len + 1);
+                    } else {
+                        let err = LegacyParseErr::NamedPlaceholder(name.to_string());
+                        problems.push((index..index + len + 1, err));
+                    }
+                    index += len;
+                }
+                None => {
+                    problems.push((index..value.len(), LegacyParseErr::UnmatchedBrace));
+                    break;
+                }
+            },
+            b'}' => problems.push((index..index + 1, LegacyParseErr::UnmatchedBrace)),
+            _ => {}
+        }
+        index += 1;
+    }
+    placeholders
+}
+
+/// Checks the gradients in a range of a literal that has no placeholders.
+fn check_gradients(
+    value: &str,
+    range: Range<usize>,
+    pattern: &str,
+    problems: &mut Vec<(Range<usize>, LegacyParseErr)>,
+) {
+    let segment = &value[range.clone()];
+    let mut gradient = false;
+    let mut offset = 0;
+    while let Some(index) = find_code(&segment[offset..], pattern, "") {
+        let index = offset + index;
+        let after = &segment[index + pattern.len()..];
+        let code = match after.chars().next() {
+            Some(code) => code,
+            None => return,
+        };
+        let mut end = index + pattern.len() + code.len_utf8();
+        let span = range.start + index..range.start + end;
+        match code {
+            '<' if !gradient => {
+                let header_len = match after[1..].find('>') {
+                    Some(len) => len,
+                    None => {
+                        problems.push((span.start..range.end, LegacyParseErr::UnclosedGradient));
+                        return;
+                    }
+                };
+                let mut stop_start = range.start + end;
+                let mut stops = 0;
+                for stop in after[1..1 + header_len].split(':') {
+                    if TextColor::from_hex(stop).is_err() {
+                        let err = LegacyParseErr::InvalidGradientColor(stop.to_string());
+                        problems.push((stop_start..stop_start + stop.len(), err));
+                    }
+                    stop_start += stop.len() + 1;
+                    stops += 1;
+                }
+                end += header_len + 1;
+                if stops < 2 {
+                    let span = span.start..range.start + end;
+                    problems.push((span, LegacyParseErr::MissingGradientColor));
+                }
+                gradient = true;
+            }
+            '>' if gradient => gradient = false,
+            '>' => problems.push((span, LegacyParseErr::UnexpectedGradientEnd)),
+            _ if gradient => problems.push((span, LegacyParseErr::CodeInGradient)),
+            _ => {}
+        }
+        offset = end;
+    }
+}
+
+/// Splits `{}` placeholders out of the literals and inserts the values following them.
+fn expand_placeholders(parts: Vec<FormatPart>) -> Result<Vec<Part>, LegacyParseErr> {
+    let mut result = Vec::with_capacity(parts.len());
+    let mut parts = parts.into_iter();
+    while let Some(part) = parts.next() {
+        let literal = match part {
+            FormatPart::Literal(literal) => literal,
+            FormatPart::Value(value) => {
+                result.push(Part::Value(*value));
+                continue;
+            }
+        };
+        let mut piece = String::new();
+        let mut chars = literal.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    piece.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    piece.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(LegacyParseErr::UnmatchedBrace),
+                        }
+                    }
+                    if !name.trim().is_empty() {
+                        return Err(LegacyParseErr::NamedPlaceholder(name.trim().to_string()));
+                    }
+                    if !piece.is_empty() {
+                        result.push(Part::Literal(std::mem::take(&mut piece)));
+                    }
+                    match parts.next() {
+                        Some(FormatPart::Value(value)) => result.push(Part::Value(*value)),
+                        _ => return Err(LegacyParseErr::MissingArgument),
+                    }
+                }
+                '}' => return Err(LegacyParseErr::UnmatchedBrace),
+                c => piece.push(c),
+            }
+        }
+        if !piece.is_empty() {
+            result.push(Part::Literal(piece));
+        }
+    }
+    Ok(result)
+}
+
+/// Finds the first formatting code starting with `code`, skipping
+/// escaped delimiters. An empty `code` matches any formatting code.
+fn find_code(value: &str, pattern: &str, code: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(index) = value[offset..].find(pattern) {
+        let index = offset + index;
+        let after = &value[index + pattern.len()..];
+        if after.starts_with(pattern) {
+            offset = index + 2 * pattern.len();
+        } else if after.starts_with(code) {
+            return Some(index);
+        } else {
+            offset = index + pattern.len();
+        }
+    }
+    None
+}
+
+/// Splits a literal at its formatting codes like `str::split` does,
+/// turning escaped delimiters back into text.
+fn split_codes(value: &str, pattern: &str) -> Vec<String> {
+    let mut split = value.split(pattern);
+    let mut pieces = vec![split.next().unwrap_or_default().to_string()];
+    while let Some(piece) = split.next() {
+        if !piece.is_empty() {
+            pieces.push(piece.to_string());
+            continue;
+        }
+        match split.next() {
+            Some(next) => {
+                let last = pieces.last_mut().expect("at least one piece");
+                last.push_str(pattern);
+                last.push_str(next);
+            }
+            None => pieces.push(String::new()),
+        }
+    }
+    pieces
+}
+
+fn color_from_code(code: char) -> Option<TextColor> {
+    Some(match code {
+        '0' => TextColor::Black,
+        '1' => TextColor::DarkBlue,
+        '2' => TextColor::DarkGreen,
+        '3' => TextColor::DarkCyan,
+        '4' => TextColor::DarkRed,
+        '5' => TextColor::Purple,
+        '6' => TextColor::Gold,
+        '7' => TextColor::Gray,
+        '8' => TextColor::DarkGray,
+        '9' => TextColor::Blue,
+        'a' => TextColor::Green,
+        'b' => TextColor::Cyan,
+        'c' => TextColor::Red,
+        'd' => TextColor::Pink,
+        'e' => TextColor::Yellow,
+        'f' => TextColor::White,
+        _ => return None,
+    })
+}
+
+/// Splits the 6 hex digits of a `#RRGGBB` color code from the rest of the piece.
+fn split_hex_color(piece: &str) -> Result<(TextColor, &str), LegacyParseErr> {
+    let hex: String = std::iter::once('#').chain(piece.chars().take(6)).collect();
+    match TextColor::from_hex(&hex) {
+        Ok(color) => Ok((color, &piece[6..])),
+        Err(_) => Err(LegacyParseErr::InvalidHexColor(hex)),
+    }
+}
+
+/// Rewrites every `xRRGGBB` sequence (each character prefixed by the pattern)
+/// to the shorter `#RRGGBB` form.
+fn collapse_hex_sequences(value: &str, pattern: &str) -> String {
+    let start = format!("{}x", pattern);
+    let mut result = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some(index) = find_code(remaining, pattern, "x") {
+        result.push_str(&remaining[..index]);
+        let mut sequence = &remaining[index + start.len()..];
+        let mut digits = String::with_capacity(6);
+        while digits.len() < 6 {
+            let mut chars = match sequence.strip_prefix(pattern) {
+                Some(after) => after.chars(),
+                None => break,
+            };
+            match chars.next() {
+                Some(c) if HEX_DIGITS.contains(c) => digits.push(c),
+                _ => break,
+            }
+            sequence = chars.as_str();
+        }
+        if digits.len() == 6 {
+            result.push_str(pattern);
+            result.push('#');
+            result.push_str(&digits);
+            remaining = sequence;
+        } else {
+            result.push_str(&start);
+            remaining = &remaining[index + start.len()..];
+        }
+    }
+    result.push_str(remaining);
+    result
+}
+
+/// Expands every `<#RRGGBB:#RRGGBB...>text>` gradient (with `<` and the closing `>`
+/// prefixed by the pattern) into hex color codes for the characters of the text.
+fn expand_gradients(value: &str, pattern: &str) -> Result<String, LegacyParseErr> {
+    let start = format!("{}<", pattern);
+    let end = format!("{}>", pattern);
+
+    let mut result = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some(index) = find_code(remaining, pattern, "<") {
+        if find_code(&remaining[..index], pattern, ">").is_some() {
+            return Err(LegacyParseErr::UnexpectedGradientEnd);
+        }
+        result.push_str(&remaining[..index]);
+        let after = &remaining[index + start.len()..];
+        let header_end = after.find('>').ok_or(LegacyParseErr::UnclosedGradient)?;
+        let stops = after[..header_end]
+            .split(':')
+            .map(|stop| {
+                TextColor::from_hex(stop)
+                    .ok()
+                    .and_then(|color| color.rgb())
+                    .ok_or_else(|| LegacyParseErr::InvalidGradientColor(stop.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if stops.len() < 2 {
+            return Err(LegacyParseErr::MissingGradientColor);
+        }
+
+        let after = &after[header_end + 1..];
+        let (text, rest) = match find_code(after, pattern, ">") {
+            Some(index) => (&after[..index], &after[index + end.len()..]),
+            None => (after, ""),
+        };
+        if find_code(text, pattern, "").is_some() {
+            return Err(LegacyParseErr::CodeInGradient);
+        }
+        // an escaped delimiter is colored as a single character
+        let escape = pattern.repeat(2);
+        let mut characters = Vec::new();
+        let mut chars = text;
+        while let Some(c) = chars.chars().next() {
+            let len = if chars.starts_with(&escape) {
+                escape.len()
+            } else {
+                c.len_utf8()
+            };
+            characters.push(&chars[..len]);
+            chars = &chars[len..];
+        }
+        let mut previous = None;
+        for (index, c) in characters.iter().enumerate() {
+            let color = gradient_color(&stops, index, characters.len());
+            if previous != Some(color) {
+                result.push_str(pattern);
+                result.push_str(&format!(
+                    "#{:02x}{:02x}{:02x}",
+                    color[0], color[1], color[2]
+                ));
+                previous = Some(color);
+            }
+            result.push_str(c);
+        }
+        remaining = rest;
+    }
+    if find_code(remaining, pattern, ">").is_some() {
+        return Err(LegacyParseErr::UnexpectedGradientEnd);
+    }
+    result.push_str(remaining);
+    Ok(result)
+}
+
+/// Linearly interpolates the color of character `index` out of `count`
+/// between the gradient's color stops.
+fn gradient_color(stops: &[[u8; 3]], index: usize, count: usize) -> [u8; 3] {
+    if count <= 1 {
+        return stops[0];
+    }
+    let position = index as f64 / (count - 1) as f64 * (stops.len() - 1) as f64;
+    let segment = (position.floor() as usize).min(stops.len() - 2);
+    let t = position - segment as f64;
+    let (from, to) = (stops[segment], stops[segment + 1]);
+    let mut color = [0; 3];
+    for (channel, (from, to)) in color.iter_mut().zip(from.iter().zip(to.iter())) {
+        *channel = (*from as f64 + (*to as f64 - *from as f64) * t).round() as u8;
+    }
+    color
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(literal: &str) -> Result<Chat, LegacyParseErr> {
+        parse_format("§", vec![literal.into()])
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(Chat::text("Hello world!"), parse("Hello world!").unwrap());
+    }
+
+    #[test]
+    fn color_bold_color() {
+        assert_eq!(
+            Chat::text("Color ").color(TextColor::DarkGreen).child(
+                Chat::text("testing ")
+                    .bold(true)
+                    .child(Chat::text("sequence").color(TextColor::DarkRed))
+            ),
+            parse("§2Color §ltesting §4sequence").unwrap()
+        );
+    }
+
+    #[test]
+    fn reset() {
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("Test ").bold(true))
+                .child(Chat::text("reset").color(TextColor::DarkGreen)),
+            parse("§lTest §r§2reset").unwrap()
+        );
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(
+            Chat::text("Hi").color(TextColor::from_rgb(0xff, 0x88, 0x00)),
+            parse("§x§F§F§8§8§0§0Hi").unwrap()
+        );
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("a").color(TextColor::from_rgb(0x00, 0x00, 0x00)))
+                .child(Chat::text("b").color(TextColor::from_rgb(0xff, 0xff, 0xff))),
+            parse("§<#000000:#ffffff>ab§>").unwrap()
+        );
+    }
+
+    #[test]
+    fn placeholders() {
+        let chat = parse_format(
+            "&",
+            vec!["&eHi {}, {{ok}}".into(), Chat::text("Steve").into()],
+        )
+        .unwrap();
+        assert_eq!(
+            Chat::text("Hi ")
+                .color(TextColor::Yellow)
+                .child(Chat::text("Steve").child(Chat::text(", {ok}"))),
+            chat
+        );
+    }
+
+    #[test]
+    fn styled_value() {
+        let chat = parse_format(
+            "§",
+            vec![
+                "Hi ".into(),
+                Chat::text("Steve").color(TextColor::Red).into(),
+                "!".into(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            Chat::text("").child(Chat::text("Hi ")).child(
+                Chat::text("")
+                    .child(Chat::text("Steve").color(TextColor::Red))
+                    .child(Chat::text("!"))
+            ),
+            chat
+        );
+    }
+
+    #[test]
+    fn escaped_delimiter() {
+        assert_eq!(Chat::text("100§"), parse("100§§").unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(LegacyParseErr::InvalidCode("§z".into())),
+            parse("Hello §zworld")
+        );
+        assert_eq!(Err(LegacyParseErr::DanglingDelimiter), parse("Hello §"));
+        assert_eq!(
+            Err(LegacyParseErr::InvalidHexColor("§#ff88He".into())),
+            parse("§#ff88Hello")
+        );
+        assert_eq!(Err(LegacyParseErr::MissingArgument), parse("Hi {}"));
+        assert_eq!(
+            Err(LegacyParseErr::NamedPlaceholder("name".into())),
+            parse("Hi {name}")
+        );
+        assert_eq!(
+            Err(LegacyParseErr::MissingGradientColor),
+            parse("§<#000000>ab")
+        );
+    }
+
+    #[test]
+    fn diagnostics() {
+        let check = |literal: &str| {
+            check_format("§", &[literal.into()])
+                .into_iter()
+                .map(|diagnostic| (diagnostic.span, diagnostic.err))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![
+                (0..9, LegacyParseErr::InvalidHexColor("§#ff88g ".into())),
+                (9..10, LegacyParseErr::UnmatchedBrace),
+                (10..12, LegacyParseErr::MissingArgument),
+            ],
+            check("§#ff88g }{}")
+        );
+        assert_eq!(
+            vec![
+                (
+                    13..19,
+                    LegacyParseErr::InvalidGradientColor("#00000".into())
+                ),
+                (21..24, LegacyParseErr::CodeInGradient),
+                (25..26, LegacyParseErr::UnmatchedBrace),
+            ],
+            check("ab§<#fff000:#00000>x§ay{")
+        );
+        // a placeholder ends the gradient
+        assert_eq!(
+            vec![
+                (20..22, LegacyParseErr::MissingArgument),
+                (22..25, LegacyParseErr::UnexpectedGradientEnd),
+            ],
+            check("§<#000000:#ffffff>a{}§>")
+        );
+        let parts = vec!["{}a{}".into(), Chat::text("b").into(), "c".into()];
+        let diagnostics = check_format("§", &parts);
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(3..5, diagnostics[0].span);
+    }
+
+    #[test]
+    fn legacy_output() {
+        let chat = Chat::text("")
+            .child(
+                Chat::text("Color ")
+                    .color(TextColor::DarkGreen)
+                    .child(Chat::text("testing ").bold(true))
+                    .child(Chat::text("sequence").color(TextColor::DarkRed)),
+            )
+            .child(Chat::text(" and "))
+            .child(Chat::text("reset").italic(true));
+        assert_eq!(
+            "§2Color §ltesting §4sequence§r and §oreset",
+            to_legacy(&chat, "§", 47)
+        );
+        let chat = parse("§lbold §mstrike§r§lbold").unwrap();
+        assert_eq!(
+            "§lbold §mstrike§r§lbold",
+            to_legacy(&chat, "&", 47).replace('&', "§")
+        );
+    }
+
+    #[test]
+    fn legacy_custom_color() {
+        let chat = Chat::text("hex").color(TextColor::from_rgb(0xff, 0x88, 0x00));
+        assert_eq!("§x§f§f§8§8§0§0hex", to_legacy(&chat, "§", 735));
+        assert_eq!("hex", to_legacy(&chat, "§", 47));
+        assert_eq!(chat, parse(&to_legacy(&chat, "§", 735)).unwrap());
+    }
+}
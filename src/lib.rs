@@ -3,37 +3,140 @@
 //!
 //! ### Serialization/Deserialization
 //!
-//! Serialization and Deserialization is implemented using serde. Excluding a
-//! few rare cases (mainly the `storage` component),
-//! all serialization and deserialization should happen correctly.
+//! Serialization and Deserialization is implemented using serde, this covers
+//! every component type including `nbt` and `storage`.
 //!
-//! I plan on implementing legacy text soon!
+//! ### Legacy text
+//!
+//! Text with `§` formatting codes is parsed with [`legacy::parse_format()`]
+//! and written with [`legacy::to_legacy()`], see the [`legacy`] module.
 //!
 //! ### Contributing
 //!
 //! Please check out our [github](https://github.com/GrizzlT/MinecraftChatRust) and
 //! feel free to contribute.
 
+mod book;
+mod boss_bar;
+mod builder;
+mod color;
 mod component;
+#[cfg(feature = "serde")]
+pub mod datapack;
+mod decoration;
+#[cfg(feature = "extensions")]
+mod extensions;
+#[cfg(feature = "serde")]
+pub mod extract;
 pub mod freeze;
+pub mod legacy;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod logging;
+pub mod messages;
+pub mod metrics;
+mod optimize;
+mod paginate;
+mod query;
+pub mod render;
+mod resolve;
+mod rope;
+mod sanitize;
+mod scoreboard;
+mod selector;
+mod sign;
 mod style;
+pub mod symbols;
+mod template;
+mod to_chat;
+pub mod translations;
+mod version;
+mod whitespace;
 
 mod tests;
 
+pub use book::*;
+pub use boss_bar::*;
+pub use builder::*;
 pub use component::*;
+pub use decoration::*;
+#[cfg(feature = "extensions")]
+pub use extensions::*;
+pub use paginate::*;
+pub use query::*;
+pub use resolve::*;
+pub use rope::*;
+pub use sanitize::*;
+pub use scoreboard::*;
+pub use selector::*;
+pub use sign::*;
 pub use style::*;
+pub use template::*;
+pub use to_chat::*;
+pub use version::*;
+
+#[cfg(feature = "macros")]
+pub use mc_chat_proc::ToChat;
+
+macro_rules! protocol_versions {
+    ($($name:ident $version:ident $release:literal $protocol:literal;)*) => {
+        $(
+            #[doc = concat!("The version number of the Minecraft protocol for ", $release)]
+            pub const $name: i32 = $protocol;
+        )*
+
+        impl ProtocolVersion {
+            $(pub const $version: ProtocolVersion = ProtocolVersion::new($name);)*
+        }
+    };
+}
 
-/// The version number of the Minecraft protocol for 1.7
-pub const VERSION_1_7: i32 = 4;
-/// The version number of the Minecraft protocol for 1.8
-pub const VERSION_1_8: i32 = 47;
-/// The version number of the Minecraft protocol for 1.15
-pub const VERSION_1_15: i32 = 573;
-/// The version number of the Minecraft protocol for 1.16
-pub const VERSION_1_16: i32 = 735;
+include!("version/named.rs");
 
+/// Builds a [`Chat`] component from legacy formatted literals and values.
+///
+/// Literals use the formatting codes of [`legacy::parse_format()`], with `§`
+/// as the default delimiter (`chat!("&" => ...)` to change it). Values between
+/// the literals are turned into text and nest the parts following them:
+/// - `{expr}: color = expr, bold` styles a value at runtime, without the
+///   style leaking into the following parts.
+/// - `@expr` inserts a [`Chat`] value (or a reference to any [`ToChat`]) as is.
+/// - `expr: sibling` keeps a value independent of the codes around it instead,
+///   as if it's surrounded by `§r`.
+/// - `tr "key", args...` builds a translation component.
+/// - `target = VERSION_1_8;` in front checks the literals against a version.
+///
+/// Only available with the `macros` feature.
+#[cfg_attr(
+    feature = "macros",
+    doc = r##"
+# Example
+```
+use mc_chat::{chat, Chat, TextColor, VERSION_1_16};
+
+let rank = Chat::text("[Admin]").color(TextColor::Red);
+let chat = chat!(@rank: sibling, " §eSteve");
+assert_eq!(
+    Chat::text("")
+        .child(Chat::text("[Admin]").color(TextColor::Red))
+        .child(Chat::text(" "))
+        .child(Chat::text("Steve").color(TextColor::Yellow)),
+    chat
+);
+
+// hex colors only exist since 1.16, `target = VERSION_1_8;` would fail to compile
+let chat = chat!(target = VERSION_1_16; "§#ff8000Hi");
+assert_eq!(Chat::text("Hi").color(TextColor::from_rgb(0xff, 0x80, 0x00)), chat);
+```
+"##
+)]
 #[macro_export]
 macro_rules! chat {
+    (target = $version:expr; $del:expr => $($tt:tt)*) => {
+        ::mc_chat_proc::chat!(@target $version, $del, $($tt)*)
+    };
+    (target = $version:expr; $($tt:tt)*) => {
+        ::mc_chat_proc::chat!(@target $version, "§", $($tt)*)
+    };
     ($del:expr => $($tt:tt)*) => {
         ::mc_chat_proc::chat!($del, $($tt)*)
     };
@@ -41,3 +144,55 @@ macro_rules! chat {
         ::mc_chat_proc::chat!("§", $($tt)*)
     };
 }
+
+/// Builds a chat component like [`chat!`] and serializes it at compile time
+/// for the given protocol version, expanding to a `&'static str`. The JSON is
+/// the same as `Chat::serialize_str()` writes.
+///
+/// The version is either an integer literal or one of the `VERSION_*` constants,
+/// imported or as `mc_chat::VERSION_*`.
+/// Only literal text is allowed, runtime values result in a compile error.
+#[macro_export]
+macro_rules! chat_static {
+    ($version:expr, $del:expr => $($tt:tt)*) => {
+        ::mc_chat_proc::chat_static!($version, $del, $($tt)*)
+    };
+    ($version:expr, $($tt:tt)*) => {
+        ::mc_chat_proc::chat_static!($version, "§", $($tt)*)
+    };
+}
+
+/// Builds a [`Book`] from chat literals, each page is written like the
+/// arguments of [`chat!`] between brackets.
+///
+/// The length of the title, the amount of pages and the literal text on every
+/// page are checked against [`Book`]'s limits at compile time.
+///
+/// Only available with the `macros` feature.
+#[cfg_attr(
+    feature = "macros",
+    doc = r##"
+# Example
+```
+use mc_chat::book;
+
+let player = "Steve";
+let book = book! {
+    title: "Adventures",
+    author: "Steve",
+    ["§lChapter 1§r\nOnce upon a time, ", player, " left home."],
+    ["§oThe end."],
+};
+assert_eq!(2, book.pages.len());
+```
+"##
+)]
+#[macro_export]
+macro_rules! book {
+    ($del:expr => $($tt:tt)*) => {
+        ::mc_chat_proc::book!($del, $($tt)*)
+    };
+    ($($tt:tt)*) => {
+        ::mc_chat_proc::book!("§", $($tt)*)
+    };
+}
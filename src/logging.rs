@@ -0,0 +1,122 @@
+//! Logging chat components with colors in a terminal and as plain text
+//! everywhere else.
+//!
+//! Log components with [`Chat::to_ansi()`], then let the output decide:
+//! - With the `log` feature, [`StripAnsi`] wraps the logger that writes to a file.
+//! - With the `tracing` feature, [`ChatFields`] formats the fields of a
+//!   `tracing_subscriber::fmt` layer, which keeps the colors only when ANSI
+//!   output is enabled for the layer.
+//!
+//! # Example
+//! ```ignore
+//! use mc_chat::logging::ChatFields;
+//!
+//! tracing_subscriber::fmt().fmt_fields(ChatFields::default()).init();
+//! tracing::info!(message = %chat.to_ansi(), player = "Steve");
+//! ```
+
+#[cfg(feature = "log")]
+pub use log_support::StripAnsi;
+#[cfg(feature = "tracing")]
+pub use tracing_support::ChatFields;
+
+#[cfg(feature = "log")]
+mod log_support {
+    use log::{Log, Metadata, Record};
+
+    use crate::render::strip_ansi;
+
+    /// A logger that removes ANSI escape codes from messages
+    /// before passing them on to the logger it wraps.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct StripAnsi<L>(pub L);
+
+    impl<L: Log> Log for StripAnsi<L> {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            self.0.enabled(metadata)
+        }
+
+        fn log(&self, record: &Record) {
+            let message = record.args().to_string();
+            self.0.log(
+                &Record::builder()
+                    .args(format_args!("{}", strip_ansi(&message)))
+                    .metadata(record.metadata().clone())
+                    .module_path(record.module_path())
+                    .file(record.file())
+                    .line(record.line())
+                    .build(),
+            );
+        }
+
+        fn flush(&self) {
+            self.0.flush()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::sync::Mutex;
+
+        use super::*;
+        use crate::{Chat, TextColor};
+
+        #[derive(Default)]
+        struct Collect(Mutex<Vec<String>>);
+
+        impl Log for Collect {
+            fn enabled(&self, _: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        #[test]
+        fn strip() {
+            let logger = StripAnsi(Collect::default());
+            let chat = Chat::text("Hello").color(TextColor::Red);
+            logger.log(
+                &Record::builder()
+                    .args(format_args!("<{}>", chat.to_ansi()))
+                    .build(),
+            );
+            assert_eq!(vec!["<Hello>".to_string()], *logger.0 .0.lock().unwrap());
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_support {
+    use tracing_subscriber::field::RecordFields;
+    use tracing_subscriber::fmt::format::{DefaultFields, Writer};
+    use tracing_subscriber::fmt::FormatFields;
+
+    use crate::render::strip_ansi;
+
+    /// Formats fields like [`DefaultFields`], but removes ANSI escape codes
+    /// when the output doesn't support them.
+    #[derive(Debug, Default)]
+    pub struct ChatFields {
+        inner: DefaultFields,
+    }
+
+    impl<'writer> FormatFields<'writer> for ChatFields {
+        fn format_fields<R: RecordFields>(
+            &self,
+            mut writer: Writer<'writer>,
+            fields: R,
+        ) -> std::fmt::Result {
+            if writer.has_ansi_escapes() {
+                return self.inner.format_fields(writer, fields);
+            }
+            let mut buffer = String::new();
+            self.inner.format_fields(Writer::new(&mut buffer), fields)?;
+            writer.write_str(&strip_ansi(&buffer))
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! The system messages a vanilla server sends, built from the same
+//! translations so they look exactly like the real ones.
+//!
+//! Names are passed as components, e.g. a player's display name with
+//! the hover and click events vanilla adds to it.
+//!
+//! [`SystemMessage`] and [`PlayerMessage`] hold a message with what's sent
+//! along with it, and turn it into the fields of the packet for a version.
+
+#[cfg(feature = "serde")]
+use crate::ProtocolVersion;
+use crate::{Chat, ChatDecoration, EntityId, TextColor, TranslationComponent};
+
+/// A message from the server, like the feedback of a command.
+///
+/// # Example
+/// ```
+/// use mc_chat::messages::{MessagePayload, SystemMessage};
+/// use mc_chat::{Chat, VERSION_1_8};
+///
+/// let message = SystemMessage::action_bar(Chat::text("Low health!"));
+/// assert_eq!(
+///     MessagePayload::ChatMessage {
+///         json: r#"{"text":"Low health!"}"#.to_string(),
+///         position: 2,
+///         sender: None,
+///     },
+///     message.payload(VERSION_1_8).unwrap()
+/// );
+/// assert_eq!(
+///     MessagePayload::SystemChat {
+///         json: r#"{"text":"Low health!"}"#.to_string(),
+///         overlay: true,
+///     },
+///     message.payload(763).unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemMessage {
+    pub message: Chat,
+    /// Whether the message is shown above the hotbar instead of in the chat.
+    pub overlay: bool,
+}
+
+impl SystemMessage {
+    pub fn new(message: Chat) -> Self {
+        Self {
+            message,
+            overlay: false,
+        }
+    }
+
+    /// A message shown above the hotbar.
+    pub fn action_bar(message: Chat) -> Self {
+        Self {
+            message,
+            overlay: true,
+        }
+    }
+
+    /// The fields of the packet this message is sent in.
+    #[cfg(feature = "serde")]
+    pub fn payload<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<MessagePayload> {
+        let version = version.into();
+        let json = self.message.serialize_str(version)?;
+        Ok(if version < ProtocolVersion::V1_19 {
+            MessagePayload::ChatMessage {
+                json,
+                position: if self.overlay { 2 } else { 1 },
+                sender: None,
+            }
+        } else if version < ProtocolVersion::V1_19_1 {
+            MessagePayload::SystemChatType {
+                json,
+                chat_type: if self.overlay { 2 } else { 1 },
+            }
+        } else {
+            MessagePayload::SystemChat {
+                json,
+                overlay: self.overlay,
+            }
+        })
+    }
+}
+
+/// A message sent by a player, with the chat type that decorates it.
+///
+/// Since 1.19 the client decorates the message with the chat type the
+/// server registered at `chat_type`. Before, the server sends the message
+/// with its `decoration` applied.
+///
+/// # Example
+/// ```
+/// use mc_chat::messages::{MessagePayload, PlayerMessage};
+/// use mc_chat::{Chat, ChatDecoration, VERSION_1_8};
+/// use uuid::Uuid;
+///
+/// let message = PlayerMessage::new(Chat::text("Hi"), Uuid::nil(), Chat::text("Steve"));
+/// assert_eq!(
+///     MessagePayload::ChatMessage {
+///         json: r#"{"translate":"chat.type.text","with":[{"text":"Steve"},{"text":"Hi"}]}"#.to_string(),
+///         position: 0,
+///         sender: None,
+///     },
+///     message.payload(VERSION_1_8).unwrap()
+/// );
+/// match message.payload(763).unwrap() {
+///     MessagePayload::PlayerChat { content, unsigned_content, chat_type, .. } => {
+///         assert_eq!("Hi", content);
+///         assert_eq!(None, unsigned_content);
+///         assert_eq!(0, chat_type);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerMessage {
+    pub content: Chat,
+    pub sender: EntityId,
+    pub sender_name: Chat,
+    /// The player or team the message is sent to, for whispers and team
+    /// messages.
+    pub target_name: Option<Chat>,
+    /// The index of the chat type in the `minecraft:chat_type` registry,
+    /// used since 1.19.
+    pub chat_type: i32,
+    /// The decoration of the chat type, applied by the server before 1.19.
+    pub decoration: ChatDecoration,
+}
+
+impl PlayerMessage {
+    /// A message sent in chat, with the `minecraft:chat` chat type at index 0.
+    pub fn new(content: Chat, sender: EntityId, sender_name: Chat) -> Self {
+        Self {
+            content,
+            sender,
+            sender_name,
+            target_name: None,
+            chat_type: 0,
+            decoration: ChatDecoration::chat(),
+        }
+    }
+
+    /// Change the chat type, by its index in the registry and its decoration.
+    pub fn chat_type(mut self, index: i32, decoration: ChatDecoration) -> Self {
+        self.chat_type = index;
+        self.decoration = decoration;
+        self
+    }
+
+    pub fn target_name(mut self, target_name: Option<Chat>) -> Self {
+        self.target_name = target_name;
+        self
+    }
+
+    /// The fields of the packet this message is sent in.
+    ///
+    /// Since 1.19.1 players sign the plain text of their message, the
+    /// formatted content is only sent when it has formatting.
+    #[cfg(feature = "serde")]
+    // the id is only `Copy` with the `uuid` feature
+    #[allow(clippy::clone_on_copy)]
+    pub fn payload<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<MessagePayload> {
+        let version = version.into();
+        if version < ProtocolVersion::V1_19 {
+            let decorated = self.content.clone().decorate(
+                &self.decoration,
+                self.sender_name.clone(),
+                self.target_name.clone(),
+            );
+            return Ok(MessagePayload::ChatMessage {
+                json: decorated.serialize_str(version)?,
+                position: 0,
+                sender: if version < ProtocolVersion::V1_16 {
+                    None
+                } else {
+                    Some(self.sender.clone())
+                },
+            });
+        }
+        let (content, unsigned_content) = if version < ProtocolVersion::V1_19_1 {
+            (self.content.serialize_str(version)?, None)
+        } else if self.content.has_formatting() {
+            (
+                self.content.to_plain(),
+                Some(self.content.serialize_str(version)?),
+            )
+        } else {
+            (self.content.to_plain(), None)
+        };
+        Ok(MessagePayload::PlayerChat {
+            content,
+            unsigned_content,
+            chat_type: self.chat_type,
+            sender: self.sender.clone(),
+            sender_name: self.sender_name.serialize_str(version)?,
+            target_name: self
+                .target_name
+                .as_ref()
+                .map(|target| target.serialize_str(version))
+                .transpose()?,
+        })
+    }
+}
+
+/// The fields of the packet a message is sent in, with components as JSON.
+///
+/// Since 1.20.3 (protocol 765) components are sent as NBT instead, the JSON
+/// has to be converted by the protocol implementation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MessagePayload {
+    /// The `Chat Message` packet before 1.19. The position is 0 for chat,
+    /// 1 for system messages and 2 for the action bar. The sender is
+    /// sent since 1.16.
+    ChatMessage {
+        json: String,
+        position: u8,
+        sender: Option<EntityId>,
+    },
+    /// The `System Chat Message` packet of 1.19, with the index of the
+    /// `minecraft:system` (1) or `minecraft:game_info` (2) chat type.
+    SystemChatType { json: String, chat_type: i32 },
+    /// The `System Chat Message` packet since 1.19.1.
+    SystemChat { json: String, overlay: bool },
+    /// The `Player Chat Message` packet since 1.19, without the signature.
+    /// The content is JSON in 1.19 and plain text since 1.19.1.
+    PlayerChat {
+        content: String,
+        unsigned_content: Option<String>,
+        chat_type: i32,
+        sender: EntityId,
+        sender_name: String,
+        target_name: Option<String>,
+    },
+}
+
+fn translate(key: &str, arguments: Vec<Chat>) -> Chat {
+    let mut translation = TranslationComponent::new(key);
+    for argument in arguments {
+        translation = translation.argument(argument);
+    }
+    Chat::component(translation)
+}
+
+/// `Steve joined the game`, in yellow.
+///
+/// # Example
+/// ```
+/// use mc_chat::{messages, Chat, TextColor, TranslationComponent};
+///
+/// assert_eq!(
+///     Chat::component(TranslationComponent::new("multiplayer.player.joined").argument(Chat::text("Steve")))
+///         .color(TextColor::Yellow),
+///     messages::player_joined(Chat::text("Steve"))
+/// );
+/// ```
+pub fn player_joined(name: Chat) -> Chat {
+    translate("multiplayer.player.joined", vec![name]).color(TextColor::Yellow)
+}
+
+/// `Steve (formerly known as Alex) joined the game`, in yellow.
+pub fn player_joined_renamed(name: Chat, old_name: Chat) -> Chat {
+    translate("multiplayer.player.joined.renamed", vec![name, old_name]).color(TextColor::Yellow)
+}
+
+/// `Steve left the game`, in yellow.
+pub fn player_left(name: Chat) -> Chat {
+    translate("multiplayer.player.left", vec![name]).color(TextColor::Yellow)
+}
+
+/// A death message with the given `death.*` translation key, like
+/// `death.attack.drown` or `death.attack.mob`.
+///
+/// Vanilla passes the killer as the second argument and the item used
+/// as the third, when there is one.
+pub fn death(key: &str, victim: Chat, killer: Option<Chat>, item: Option<Chat>) -> Chat {
+    let mut arguments = vec![victim];
+    arguments.extend(killer);
+    arguments.extend(item);
+    translate(key, arguments)
+}
+
+/// `Steve was slain by Alex`, or `Steve was slain by Alex using [Sword]`
+/// when the killer used a named item.
+pub fn slain_by_player(victim: Chat, killer: Chat, item: Option<Chat>) -> Chat {
+    let key = if item.is_some() {
+        "death.attack.player.item"
+    } else {
+        "death.attack.player"
+    };
+    death(key, victim, Some(killer), item)
+}
+
+/// `Steve whispers to you: message`, in gray italic.
+pub fn whisper_incoming(sender: Chat, message: Chat) -> Chat {
+    message.decorate(&ChatDecoration::msg_command_incoming(), sender, None)
+}
+
+/// `You whisper to Alex: message`, in gray italic.
+pub fn whisper_outgoing(target: Chat, message: Chat) -> Chat {
+    message.decorate(
+        &ChatDecoration::msg_command_outgoing(),
+        Chat::text(""),
+        Some(target),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deaths() {
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("death.attack.player.item")
+                    .argument(Chat::text("Steve"))
+                    .argument(Chat::text("Alex"))
+                    .argument(Chat::text("[Sword]"))
+            ),
+            slain_by_player(
+                Chat::text("Steve"),
+                Chat::text("Alex"),
+                Some(Chat::text("[Sword]"))
+            )
+        );
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("death.attack.drown").argument(Chat::text("Steve"))
+            ),
+            death("death.attack.drown", Chat::text("Steve"), None, None)
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "uuid"))]
+    #[test]
+    fn payloads() {
+        use uuid::Uuid;
+
+        let message = PlayerMessage::new(
+            Chat::text("Hi").bold(true),
+            Uuid::from_u128(1),
+            Chat::text("Steve"),
+        )
+        .chat_type(4, ChatDecoration::msg_command_incoming())
+        .target_name(Some(Chat::text("Alex")));
+        match message.payload(crate::VERSION_1_16).unwrap() {
+            MessagePayload::ChatMessage {
+                json,
+                position,
+                sender,
+            } => {
+                assert!(json.contains("commands.message.display.incoming"));
+                assert_eq!(0, position);
+                assert_eq!(Some(Uuid::from_u128(1)), sender);
+            }
+            payload => panic!("unexpected payload {:?}", payload),
+        }
+        assert_eq!(
+            MessagePayload::PlayerChat {
+                content: "Hi".to_string(),
+                unsigned_content: Some(r#"{"text":"Hi","bold":true}"#.to_string()),
+                chat_type: 4,
+                sender: Uuid::from_u128(1),
+                sender_name: r#"{"text":"Steve"}"#.to_string(),
+                target_name: Some(r#"{"text":"Alex"}"#.to_string()),
+            },
+            message.payload(760).unwrap()
+        );
+        assert_eq!(
+            MessagePayload::SystemChatType {
+                json: r#"{"text":"a"}"#.to_string(),
+                chat_type: 1,
+            },
+            SystemMessage::new(Chat::text("a")).payload(759).unwrap()
+        );
+    }
+
+    #[test]
+    fn whispers() {
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("commands.message.display.incoming")
+                    .argument(Chat::text("Steve"))
+                    .argument(Chat::text("Hi"))
+            )
+            .color(TextColor::Gray)
+            .italic(true),
+            whisper_incoming(Chat::text("Steve"), Chat::text("Hi"))
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Measuring text in the pixels of Minecraft's font, used to lay out
+//! text in books and on signs.
+//!
+//! [`DefaultFont`] knows the widths of the ASCII characters in the default
+//! font, a [`FontMetrics`] implementation of your own can support resource
+//! packs or other fonts.
+//!
+//! # Example
+//! ```
+//! use mc_chat::metrics::{str_width, DefaultFont, FontMetrics};
+//! use mc_chat::Chat;
+//!
+//! assert_eq!(22, str_width("Hello", false));
+//! assert_eq!(27, str_width("Hello", true));
+//! assert_eq!(27, DefaultFont.chat_width(&Chat::text("Hello").bold(true)));
+//! ```
+
+use crate::{symbols, Chat, ComponentKind, Style};
+
+/// The width of the text area of a book page in pixels.
+pub const BOOK_WIDTH: u32 = 114;
+/// The amount of lines on a book page.
+pub const BOOK_LINES: usize = 14;
+/// The width of a boss bar in pixels.
+pub const BOSS_BAR_WIDTH: u32 = 182;
+/// The default width of the chat window in pixels.
+pub const CHAT_WIDTH: u32 = 320;
+/// The maximum width of a line on a sign in pixels.
+pub const SIGN_WIDTH: u32 = 90;
+
+/// The widths of the glyphs of a font.
+pub trait FontMetrics {
+    /// The advance of a character in pixels: the width of its glyph
+    /// plus the spacing after it.
+    fn advance(&self, c: char, bold: bool) -> u32;
+
+    /// The width of a string in pixels.
+    fn str_width(&self, text: &str, bold: bool) -> u32 {
+        text.chars().map(|c| self.advance(c, bold)).sum()
+    }
+
+    /// The width of a component and its children in pixels, taking
+    /// inherited boldness into account.
+    ///
+    /// Components other than text are measured by their raw contents,
+    /// e.g. the translation key, which is only an estimate.
+    fn chat_width(&self, chat: &Chat) -> u32 {
+        fn width<M: FontMetrics + ?Sized>(metrics: &M, chat: &Chat, parent: &Style) -> u32 {
+            let style = chat.style.inherit(parent);
+            let own = metrics.str_width(raw_text(&chat.kind), style.bold == Some(true));
+            own + chat
+                .children
+                .iter()
+                .map(|child| width(metrics, child, &style))
+                .sum::<u32>()
+        }
+        width(self, chat, &Style::default())
+    }
+}
+
+/// The default font of the vanilla client, for the ASCII characters and
+/// the [`symbols`](crate::symbols). Other characters are assumed to be
+/// as wide as most letters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DefaultFont;
+
+impl DefaultFont {
+    /// The width of a glyph without the spacing after it.
+    fn glyph_width(c: char) -> u32 {
+        match c {
+            'i' | 'l' | '!' | ':' | ';' | '\'' | '|' | '.' | ',' => 1,
+            '`' => 2,
+            'I' | '[' | ']' | '"' | ' ' => 3,
+            'f' | 'k' | 't' | '(' | ')' | '{' | '}' | '<' | '>' => 4,
+            '@' => 6,
+            // the other letters, digits and symbols, and anything unknown
+            _ => 5,
+        }
+    }
+}
+
+impl FontMetrics for DefaultFont {
+    fn advance(&self, c: char, bold: bool) -> u32 {
+        let advance = symbols::advance(c).unwrap_or_else(|| Self::glyph_width(c) + 1);
+        advance + bold as u32
+    }
+}
+
+/// The width of a string in pixels in the [`DefaultFont`].
+pub fn str_width(text: &str, bold: bool) -> u32 {
+    DefaultFont.str_width(text, bold)
+}
+
+impl<M: FontMetrics + ?Sized> FontMetrics for &M {
+    fn advance(&self, c: char, bold: bool) -> u32 {
+        (**self).advance(c, bold)
+    }
+}
+
+/// The text to measure a component by, without its children.
+pub(crate) fn raw_text(kind: &ComponentKind) -> &str {
+    match kind {
+        ComponentKind::Text(text) => &text.text,
+        ComponentKind::Translation(translation) => &translation.key,
+        ComponentKind::Score(score) => score.value.as_ref().unwrap_or(&score.name),
+        ComponentKind::Selector(selector) => &selector.selector,
+        ComponentKind::Keybind(keybind) => &keybind.keybind,
+        ComponentKind::Nbt(nbt) => &nbt.nbt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmapped() {
+        assert!(symbols::advance('é').is_none());
+        assert_eq!(
+            DefaultFont.advance('a', false),
+            DefaultFont.advance('é', false)
+        );
+        assert_eq!(7, DefaultFont.advance('ж', true));
+    }
+
+    #[test]
+    fn widths() {
+        assert_eq!(6, DefaultFont.advance('a', false));
+        assert_eq!(7, DefaultFont.advance('a', true));
+        assert_eq!(2, DefaultFont.advance('i', false));
+        assert_eq!(4, DefaultFont.advance(' ', false));
+        assert_eq!(7, DefaultFont.advance('@', false));
+        assert_eq!(6 + 2 + 4 + 7, str_width("a! @", false));
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+
+use crate::freeze::FrozenChat;
+use crate::{Chat, ComponentKind, HoverEvent, TextComponent};
+
+impl Chat {
+    /// Shrinks this component without changing how it looks, for trees
+    /// that are built by code, like lists of items.
+    ///
+    /// - Neighbouring text children with the same style, click and hover
+    ///   events are merged into one.
+    /// - Neighbouring children with the same hover event that differ otherwise
+    ///   are moved into an empty component holding the hover event once, which
+    ///   they inherit. A tooltip repeated for every part of a line is only
+    ///   sent once this way.
+    ///
+    /// This happens for every level of the tree, including translation
+    /// arguments and hover text.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, HoverEvent, TextColor};
+    ///
+    /// let tooltip = Some(HoverEvent::ShowText(Box::new(Chat::text("Diamond sword"))));
+    /// let mut chat = Chat::text("Items: ")
+    ///     .child(Chat::text("[").hover(tooltip.clone()))
+    ///     .child(Chat::text("Sword").color(TextColor::Cyan).hover(tooltip.clone()))
+    ///     .child(Chat::text("]").hover(tooltip.clone()))
+    ///     .child(Chat::text(", "))
+    ///     .child(Chat::text("and more"));
+    /// chat.merge_adjacent();
+    /// assert_eq!(
+    ///     Chat::text("Items: ")
+    ///         .child(
+    ///             Chat::text("")
+    ///                 .hover(tooltip)
+    ///                 .child(Chat::text("["))
+    ///                 .child(Chat::text("Sword").color(TextColor::Cyan))
+    ///                 .child(Chat::text("]")),
+    ///         )
+    ///         .child(Chat::text(", and more")),
+    ///     chat
+    /// );
+    /// ```
+    pub fn merge_adjacent(&mut self) {
+        if let Some(text) = self
+            .style
+            .hover_event
+            .as_mut()
+            .and_then(HoverEvent::text_mut)
+        {
+            text.merge_adjacent();
+        }
+        match self.kind {
+            ComponentKind::Translation(ref mut translation) => {
+                translation.components_mut().for_each(Chat::merge_adjacent);
+            }
+            ref mut kind => {
+                if let Some(sep) = kind.separator_mut() {
+                    sep.merge_adjacent();
+                }
+            }
+        }
+        self.children.iter_mut().for_each(Chat::merge_adjacent);
+        self.children = share_hover(merge_text(std::mem::take(&mut self.children)));
+    }
+
+    /// Makes identical hover text in this tree share a single allocation,
+    /// with [`HoverEvent::ShowSharedText`], returning how many hover events
+    /// now reuse the text of another one.
+    ///
+    /// The other style settings are stored in every component itself, hover
+    /// text is the only part of a style that's worth sharing. Unlike
+    /// [`Chat::merge_adjacent()`], this doesn't change the structure of the
+    /// tree or what's serialized.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, HoverEvent};
+    ///
+    /// let tooltip = || Some(HoverEvent::ShowText(Box::new(Chat::text("Buy for 5 coins"))));
+    /// let mut chat = Chat::text("Shop: ")
+    ///     .child(Chat::text("Apple").hover(tooltip()))
+    ///     .child(Chat::text(", "))
+    ///     .child(Chat::text("Bread").hover(tooltip()));
+    /// let original = chat.clone();
+    /// assert_eq!(1, chat.dedup_styles());
+    /// assert_eq!(original, chat);
+    /// assert!(matches!(chat.children[2].style.hover_event, Some(HoverEvent::ShowSharedText(_))));
+    /// ```
+    pub fn dedup_styles(&mut self) -> usize {
+        let mut shared = HashSet::new();
+        let mut reused = 0;
+        dedup_hover(self, &mut shared, &mut reused);
+        reused
+    }
+}
+
+fn dedup_hover(chat: &mut Chat, shared: &mut HashSet<FrozenChat>, reused: &mut usize) {
+    let text = match chat.style.hover_event.take() {
+        Some(HoverEvent::ShowText(text)) => {
+            let mut text = *text;
+            dedup_hover(&mut text, shared, reused);
+            Some(text.freeze())
+        }
+        Some(HoverEvent::ShowSharedText(text)) => Some(text),
+        event => {
+            chat.style.hover_event = event;
+            None
+        }
+    };
+    if let Some(text) = text {
+        let text = match shared.get(&text) {
+            Some(existing) => {
+                *reused += 1;
+                existing.clone()
+            }
+            None => {
+                shared.insert(text.clone());
+                text
+            }
+        };
+        chat.style.hover_event = Some(HoverEvent::ShowSharedText(text));
+    }
+    match chat.kind {
+        ComponentKind::Translation(ref mut translation) => {
+            for argument in translation.components_mut() {
+                dedup_hover(argument, shared, reused);
+            }
+        }
+        ref mut kind => {
+            if let Some(sep) = kind.separator_mut() {
+                dedup_hover(sep, shared, reused);
+            }
+        }
+    }
+    for child in chat.children.iter_mut() {
+        dedup_hover(child, shared, reused);
+    }
+}
+
+/// Merges neighbouring text components with the same style. The first one
+/// can't have children, those would end up between the texts.
+fn merge_text(children: Vec<Chat>) -> Vec<Chat> {
+    let mut merged: Vec<Chat> = Vec::with_capacity(children.len());
+    for child in children {
+        if let Some(previous) = merged.last_mut() {
+            if let (ComponentKind::Text(ref mut first), ComponentKind::Text(ref second)) =
+                (&mut previous.kind, &child.kind)
+            {
+                if previous.children.is_empty() && previous.style == child.style {
+                    *first = TextComponent::new(format!("{}{}", &*first.text, &*second.text));
+                    previous.children = child.children;
+                    continue;
+                }
+            }
+        }
+        merged.push(child);
+    }
+    merged
+}
+
+/// Moves runs of children with the same hover event into
+/// a parent that holds the hover event instead.
+fn share_hover(children: Vec<Chat>) -> Vec<Chat> {
+    let mut result = Vec::with_capacity(children.len());
+    let mut run: Vec<Chat> = Vec::new();
+    for child in children {
+        let same = match (run.first(), &child.style.hover_event) {
+            (Some(first), Some(hover)) => first.style.hover_event.as_ref() == Some(hover),
+            _ => false,
+        };
+        if !same {
+            flush_run(&mut run, &mut result);
+        }
+        run.push(child);
+    }
+    flush_run(&mut run, &mut result);
+    result
+}
+
+fn flush_run(run: &mut Vec<Chat>, result: &mut Vec<Chat>) {
+    if run.len() < 2 {
+        result.append(run);
+        return;
+    }
+    let mut parent = Chat::text("");
+    parent.style.hover_event = run[0].style.hover_event.clone();
+    for child in run.iter_mut() {
+        child.style.hover_event = None;
+    }
+    parent.children = std::mem::take(run);
+    result.push(parent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClickEvent, TextColor, TranslationComponent};
+
+    #[test]
+    fn merge_text_children() {
+        let mut chat = Chat::text("")
+            .child(Chat::text("a").color(TextColor::Red))
+            .child(
+                Chat::text("b")
+                    .color(TextColor::Red)
+                    .child(Chat::text("c").bold(true)),
+            )
+            .child(Chat::text("d").color(TextColor::Red))
+            .child(
+                Chat::text("e")
+                    .color(TextColor::Red)
+                    .click(Some(ClickEvent::page(2u32))),
+            )
+            .child(Chat::translate("key").color(TextColor::Red));
+        chat.merge_adjacent();
+        assert_eq!(
+            Chat::text("")
+                .child(
+                    Chat::text("ab")
+                        .color(TextColor::Red)
+                        .child(Chat::text("c").bold(true))
+                )
+                .child(Chat::text("d").color(TextColor::Red))
+                .child(
+                    Chat::text("e")
+                        .color(TextColor::Red)
+                        .click(Some(ClickEvent::page(2u32)))
+                )
+                .child(Chat::translate("key").color(TextColor::Red)),
+            chat
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let hover = Some(HoverEvent::ShowText(Box::new(
+            Chat::text("").child(Chat::text("a")).child(Chat::text("b")),
+        )));
+        let mut chat = Chat::component(
+            TranslationComponent::new("key").argument(
+                Chat::text("")
+                    .hover(hover)
+                    .child(Chat::text("x"))
+                    .child(Chat::text("y")),
+            ),
+        );
+        chat.merge_adjacent();
+        let merged_hover = Some(HoverEvent::ShowText(Box::new(
+            Chat::text("").child(Chat::text("ab")),
+        )));
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("key")
+                    .argument(Chat::text("").hover(merged_hover).child(Chat::text("xy")))
+            ),
+            chat
+        );
+    }
+
+    #[test]
+    fn dedup_nested_hover() {
+        let inner = || Some(HoverEvent::ShowText(Box::new(Chat::text("inner"))));
+        let outer = || {
+            Some(HoverEvent::ShowText(Box::new(
+                Chat::text("outer").hover(inner()),
+            )))
+        };
+        let mut chat = Chat::component(
+            TranslationComponent::new("%s %s")
+                .argument(Chat::text("a").hover(outer()))
+                .argument(Chat::text("b").hover(inner())),
+        )
+        .child(Chat::text("c").hover(outer()));
+        let original = chat.clone();
+        assert_eq!(3, chat.dedup_styles());
+        assert_eq!(original, chat);
+        match (&chat.children[0].style.hover_event, chat.as_translation()) {
+            (Some(HoverEvent::ShowSharedText(c)), Some(translation)) => {
+                match translation.with[0].as_chat().unwrap().style.hover_event {
+                    Some(HoverEvent::ShowSharedText(ref a)) => assert!(std::ptr::eq(&**a, &**c)),
+                    _ => panic!("hover text isn't shared"),
+                }
+            }
+            _ => panic!("hover text isn't shared"),
+        }
+    }
+
+    #[test]
+    fn single_hover_untouched() {
+        let hover = Some(HoverEvent::ShowText(Box::new(Chat::text("tip"))));
+        let original = Chat::text("")
+            .child(Chat::text("a").hover(hover.clone()))
+            .child(Chat::text("b").bold(true))
+            .child(Chat::text("c").hover(hover));
+        let mut chat = original.clone();
+        chat.merge_adjacent();
+        assert_eq!(original, chat);
+    }
+}
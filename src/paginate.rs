@@ -0,0 +1,336 @@
+use crate::freeze::FrozenStr;
+use crate::metrics::{raw_text, FontMetrics, CHAT_WIDTH};
+use crate::symbols::{DOUBLE_ARROW_LEFT, DOUBLE_ARROW_RIGHT};
+use crate::{Chat, ClickEvent, ComponentKind, Style, TextColor, TextComponent, TranslationArg};
+
+/// Splits long messages into pages for the chat window, with buttons
+/// to go to the previous and the next page below every page.
+///
+/// The buttons run a command, where `{page}` is replaced by the number
+/// of the page to go to, counting from 1. A message that fits on a single
+/// page is returned without buttons.
+///
+/// # Example
+/// ```
+/// use mc_chat::metrics::DefaultFont;
+/// use mc_chat::{Chat, ChatPaginator, ClickEvent};
+///
+/// let help = Chat::text("/spawn - teleports you to spawn\n".repeat(20));
+/// let pages = ChatPaginator::new(&DefaultFont, 8, "/help {page}").paginate(&help);
+/// assert_eq!(3, pages.len());
+/// let next = &pages[0].children[2].children[2];
+/// assert_eq!(Some(ClickEvent::command("/help 2")), next.style.click_event);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChatPaginator<'a, M: ?Sized> {
+    metrics: &'a M,
+    lines: usize,
+    width: u32,
+    command: FrozenStr,
+}
+
+impl<'a, M: FontMetrics + ?Sized> ChatPaginator<'a, M> {
+    /// Creates a paginator for pages of `lines` lines, not counting
+    /// the line with the buttons.
+    pub fn new<T: Into<FrozenStr>>(metrics: &'a M, lines: usize, command: T) -> Self {
+        Self {
+            metrics,
+            lines: lines.max(1),
+            width: CHAT_WIDTH,
+            command: command.into(),
+        }
+    }
+
+    /// Change the width of the chat window, [`CHAT_WIDTH`] by default.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Splits a message into pages.
+    pub fn paginate(&self, chat: &Chat) -> Vec<Chat> {
+        let mut pages = paginate(chat, self.metrics, self.width, self.lines, usize::MAX);
+        // the line break at the end of the last line starts an empty page
+        if pages.len() > 1 && pages.last().is_some_and(|page| page.children.is_empty()) {
+            pages.pop();
+        }
+        if pages.len() == 1 {
+            return pages;
+        }
+        let count = pages.len();
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, page)| {
+                let number = index + 1;
+                let footer = Chat::text("")
+                    .color(TextColor::Gray)
+                    .child(self.button(DOUBLE_ARROW_LEFT, number - 1, count))
+                    .child(Chat::text(format!(" {}/{} ", number, count)))
+                    .child(self.button(DOUBLE_ARROW_RIGHT, number + 1, count));
+                Chat::text("")
+                    .child(page)
+                    .child(Chat::text("\n"))
+                    .child(footer)
+            })
+            .collect()
+    }
+
+    fn button(&self, arrow: char, page: usize, count: usize) -> Chat {
+        let button = Chat::text(arrow.to_string());
+        if page == 0 || page > count {
+            return button.color(TextColor::DarkGray);
+        }
+        let command = self.command.replace("{page}", &page.to_string());
+        button
+            .color(TextColor::Yellow)
+            .click(Some(ClickEvent::command(command)))
+    }
+}
+
+/// Splits a component into pages of `lines` lines that are `width` pixels wide,
+/// with at most `max_length` characters, see [`Book::paginate()`](crate::Book::paginate).
+pub(crate) fn paginate<M: FontMetrics + ?Sized>(
+    chat: &Chat,
+    metrics: &M,
+    width: u32,
+    lines: usize,
+    max_length: usize,
+) -> Vec<Chat> {
+    let mut paginator = Paginator {
+        metrics,
+        width,
+        lines,
+        max_length,
+        pages: Vec::new(),
+        page: Vec::new(),
+        length: 0,
+        line: 0,
+        x: 0,
+        wrapped: false,
+    };
+    paginator.walk(chat, &Style::default());
+    paginator.finish()
+}
+
+/// Counts the characters of all text in a component tree.
+pub(crate) fn text_length(chat: &Chat) -> usize {
+    let own = match chat.kind {
+        ComponentKind::Text(ref text) => text.text.chars().count(),
+        ComponentKind::Translation(ref translation) => translation
+            .with
+            .iter()
+            .map(|argument| match argument {
+                TranslationArg::Chat(chat) => text_length(chat),
+                TranslationArg::Number(number) => number.chars().count(),
+                TranslationArg::Bool(value) => value.to_string().len(),
+            })
+            .sum(),
+        _ => 0,
+    };
+    own + chat.children.iter().map(text_length).sum::<usize>()
+}
+
+/// Part of a page, text is merged as long as the style stays the same.
+enum Segment {
+    Text(String, Style),
+    Component(Chat),
+}
+
+/// Lays out text on pages like a book or the chat window does.
+struct Paginator<'a, M: ?Sized> {
+    metrics: &'a M,
+    width: u32,
+    lines: usize,
+    /// The maximum amount of characters on a page.
+    max_length: usize,
+    pages: Vec<Chat>,
+    page: Vec<Segment>,
+    /// The amount of characters on the current page.
+    length: usize,
+    /// The line on the current page, starting at 0.
+    line: usize,
+    /// The width of the current line.
+    x: u32,
+    /// Whether the current line started by wrapping a longer line,
+    /// the spaces at its start are consumed by the line break.
+    wrapped: bool,
+}
+
+impl<'a, M: FontMetrics + ?Sized> Paginator<'a, M> {
+    fn walk(&mut self, chat: &Chat, parent: &Style) {
+        let style = chat.style.inherit(parent);
+        match chat.kind {
+            ComponentKind::Text(ref text) => self.text(&text.text, &style),
+            ref kind => {
+                self.component(
+                    Chat {
+                        kind: kind.clone(),
+                        style: style.clone(),
+                        children: Vec::new(),
+                        #[cfg(feature = "extensions")]
+                        extensions: chat.extensions.clone(),
+                    },
+                    self.metrics
+                        .str_width(raw_text(kind), style.bold == Some(true)),
+                );
+            }
+        }
+        for child in &chat.children {
+            self.walk(child, &style);
+        }
+    }
+
+    fn text(&mut self, mut text: &str, style: &Style) {
+        let bold = style.bold == Some(true);
+        while let Some(first) = text.chars().next() {
+            if first == '\n' {
+                self.newline(true, style);
+                text = &text[1..];
+                continue;
+            }
+            let end = text
+                .find(|c: char| c == '\n' || c.is_whitespace() != first.is_whitespace())
+                .unwrap_or(text.len());
+            let (token, rest) = text.split_at(end);
+            text = rest;
+            if first.is_whitespace() {
+                token.chars().for_each(|c| self.space(c, bold, style));
+            } else {
+                self.word(token, bold, style);
+            }
+        }
+    }
+
+    fn space(&mut self, c: char, bold: bool, style: &Style) {
+        if self.wrapped && self.x == 0 {
+            return;
+        }
+        let width = self.metrics.advance(c, bold);
+        if self.x + width > self.width {
+            self.newline(false, style);
+        } else if self.length == self.max_length {
+            self.break_page();
+        } else {
+            self.append(c, width, style);
+        }
+    }
+
+    fn word(&mut self, word: &str, bold: bool, style: &Style) {
+        let length = word.chars().count();
+        if self.x > 0 && self.x + self.metrics.str_width(word, bold) > self.width {
+            self.newline(false, style);
+        }
+        if self.length > 0 && self.length + length > self.max_length && length <= self.max_length {
+            self.break_page();
+        }
+        for c in word.chars() {
+            let width = self.metrics.advance(c, bold);
+            if self.x > 0 && self.x + width > self.width {
+                self.newline(false, style);
+            }
+            if self.length == self.max_length {
+                self.break_page();
+            }
+            self.append(c, width, style);
+        }
+    }
+
+    fn component(&mut self, chat: Chat, width: u32) {
+        let length = text_length(&chat);
+        if self.x > 0 && self.x + width > self.width {
+            self.newline(false, &chat.style);
+        }
+        if self.length > 0 && self.length + length > self.max_length {
+            self.break_page();
+        }
+        self.x += width;
+        self.length += length;
+        self.wrapped = false;
+        self.page.push(Segment::Component(chat));
+    }
+
+    fn newline(&mut self, hard: bool, style: &Style) {
+        self.line += 1;
+        self.x = 0;
+        self.wrapped = !hard;
+        if self.line == self.lines {
+            self.break_page();
+        } else if hard {
+            if self.length == self.max_length {
+                self.break_page();
+            } else {
+                self.append('\n', 0, style);
+            }
+        }
+    }
+
+    fn append(&mut self, c: char, width: u32, style: &Style) {
+        self.x += width;
+        self.length += 1;
+        if c != '\n' {
+            self.wrapped = false;
+        }
+        if let Some(Segment::Text(text, last)) = self.page.last_mut() {
+            if last == style {
+                text.push(c);
+                return;
+            }
+        }
+        self.page.push(Segment::Text(c.to_string(), style.clone()));
+    }
+
+    fn break_page(&mut self) {
+        let mut page = Chat::text("");
+        page.children = std::mem::take(&mut self.page)
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Text(text, style) => Chat {
+                    kind: TextComponent::new(text).into(),
+                    style,
+                    children: Vec::new(),
+                    #[cfg(feature = "extensions")]
+                    extensions: Default::default(),
+                },
+                Segment::Component(chat) => chat,
+            })
+            .collect();
+        self.pages.push(page);
+        self.length = 0;
+        self.line = 0;
+        self.x = 0;
+    }
+
+    fn finish(mut self) -> Vec<Chat> {
+        if !self.page.is_empty() || self.pages.is_empty() {
+            self.break_page();
+        }
+        self.pages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::DefaultFont;
+
+    #[test]
+    fn chat_pages() {
+        let paginator = ChatPaginator::new(&DefaultFont, 2, "/list {page}");
+        assert_eq!(1, paginator.paginate(&Chat::text("a\nb")).len());
+
+        let pages = paginator.paginate(&Chat::text("a\nb\nc\n"));
+        assert_eq!(2, pages.len());
+        let footer = &pages[1].children[2];
+        assert_eq!(None, footer.children[2].style.click_event);
+        assert_eq!(Some(TextColor::DarkGray), footer.children[2].style.color);
+        assert_eq!(
+            Some(ClickEvent::command("/list 1")),
+            footer.children[0].style.click_event
+        );
+        assert_eq!(
+            Chat::text("").child(Chat::text("c\n")),
+            pages[1].children[0]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{Chat, ClickEvent, ComponentKind, HoverEvent, TextColor, TranslationArg};
+
+impl Chat {
+    /// Whether any component in this tree has a style setting.
+    pub fn has_formatting(&self) -> bool {
+        any(self, &|chat| !chat.style.is_plain())
+    }
+
+    /// Whether any component in this tree has a hex color, which needs 1.16.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TextColor};
+    ///
+    /// let chat = Chat::text("Hi ").child(Chat::text("there").color(TextColor::from_rgb(0xff, 0x80, 0x00)));
+    /// assert!(chat.uses_hex_colors());
+    /// assert!(!chat.has_click_events());
+    /// assert_eq!(1, chat.max_depth());
+    /// ```
+    pub fn uses_hex_colors(&self) -> bool {
+        any(self, &|chat| {
+            matches!(chat.style.color, Some(TextColor::Custom(_)))
+        })
+    }
+
+    /// Whether any component in this tree has a click event.
+    pub fn has_click_events(&self) -> bool {
+        any(self, &|chat| chat.style.click_event.is_some())
+    }
+
+    /// Whether any component in this tree has a hover event.
+    pub fn has_hover_events(&self) -> bool {
+        any(self, &|chat| chat.style.hover_event.is_some())
+    }
+
+    /// How deep components are nested in this tree, the root is at depth 0.
+    ///
+    /// Translation arguments, selector separators and hover text are one
+    /// level deeper than their component, like children, which matches
+    /// [`SanitizePolicy::max_depth`](crate::SanitizePolicy::max_depth).
+    pub fn max_depth(&self) -> usize {
+        nested(self)
+            .map(|nested| nested.max_depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// How deep hover events are nested in the text of other hover events:
+    /// 0 without hover events, 1 if no hover text has hover events itself.
+    ///
+    /// The client doesn't show hover events inside a tooltip, so anything
+    /// over 1 only makes the component bigger, see
+    /// [`Chat::strip_nested_hovers()`].
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, HoverEvent};
+    ///
+    /// let tooltip = |chat: Chat| Some(HoverEvent::ShowText(Box::new(chat)));
+    /// let chat = Chat::text("a").child(Chat::text("b").hover(tooltip(
+    ///     Chat::text("c").hover(tooltip(Chat::text("d"))),
+    /// )));
+    /// assert_eq!(2, chat.hover_depth());
+    /// ```
+    pub fn hover_depth(&self) -> usize {
+        let own = match self.style.hover_event {
+            Some(ref hover) => 1 + hover.text().map_or(0, Chat::hover_depth),
+            None => 0,
+        };
+        nested_steps(self)
+            .filter(|(step, _)| *step != PathStep::HoverText)
+            .map(|(_, nested)| nested.hover_depth())
+            .fold(own, usize::max)
+    }
+
+    /// The component at the end of a path, relative to this one.
+    pub fn find(&self, path: &[PathStep]) -> Option<&Chat> {
+        path.iter().try_fold(self, |chat, step| {
+            nested_steps(chat)
+                .find(|(nested, _)| nested == step)
+                .map(|(_, nested)| nested)
+        })
+    }
+
+    /// Every click event in this tree with the path to its component,
+    /// in pre-order: a component comes before its translation arguments,
+    /// separator, hover text and children.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, ClickEvent, PathStep};
+    ///
+    /// let chat = Chat::text("Visit ")
+    ///     .child(Chat::text("our site").click(Some(ClickEvent::url("https://example.com"))))
+    ///     .child(Chat::text(" or ").child(Chat::text("spawn").click(Some(ClickEvent::command("/spawn")))));
+    /// let events = chat.click_events().collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     vec![
+    ///         (vec![PathStep::Child(0)], &ClickEvent::url("https://example.com")),
+    ///         (vec![PathStep::Child(1), PathStep::Child(0)], &ClickEvent::command("/spawn")),
+    ///     ],
+    ///     events
+    /// );
+    /// let link = chat.find(&events[0].0).and_then(Chat::as_text);
+    /// assert_eq!(Some("our site"), link.map(|text| &*text.text));
+    /// ```
+    pub fn click_events(&self) -> impl Iterator<Item = (Vec<PathStep>, &ClickEvent)> {
+        let mut found = Vec::new();
+        collect(
+            self,
+            &mut Vec::new(),
+            &|chat| chat.style.click_event.as_ref(),
+            &mut found,
+        );
+        found.into_iter()
+    }
+
+    /// Every hover event in this tree with the path to its component, in the
+    /// order of [`Chat::click_events()`].
+    pub fn hover_events(&self) -> impl Iterator<Item = (Vec<PathStep>, &HoverEvent)> {
+        let mut found = Vec::new();
+        collect(
+            self,
+            &mut Vec::new(),
+            &|chat| chat.style.hover_event.as_ref(),
+            &mut found,
+        );
+        found.into_iter()
+    }
+}
+
+/// A step from a component to a component nested in it, see [`Chat::find()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathStep {
+    /// A translation argument.
+    Argument(usize),
+    /// The separator of a selector.
+    Separator,
+    /// The text of a `show_text` hover event.
+    HoverText,
+    Child(usize),
+}
+
+/// Whether `f` is true for a component of the tree, stopping at the first one.
+fn any(chat: &Chat, f: &dyn Fn(&Chat) -> bool) -> bool {
+    f(chat) || nested(chat).any(|nested| any(nested, f))
+}
+
+/// The components nested directly in `chat`, in the order of [`Chat::click_events()`].
+fn nested(chat: &Chat) -> impl Iterator<Item = &Chat> {
+    nested_steps(chat).map(|(_, nested)| nested)
+}
+
+pub(crate) fn nested_steps(chat: &Chat) -> impl Iterator<Item = (PathStep, &Chat)> {
+    let (arguments, sep): (&[TranslationArg], _) = match chat.kind {
+        ComponentKind::Translation(ref translation) => (&translation.with, None),
+        ref kind => (&[], kind.separator()),
+    };
+    let hover = chat.style.hover_event.as_ref().and_then(HoverEvent::text);
+    let arguments = arguments
+        .iter()
+        .enumerate()
+        .filter_map(|(index, argument)| Some((PathStep::Argument(index), argument.as_chat()?)));
+    let children = chat
+        .children
+        .iter()
+        .enumerate()
+        .map(|(index, child)| (PathStep::Child(index), child));
+    arguments
+        .chain(sep.map(|sep| (PathStep::Separator, sep)))
+        .chain(hover.map(|hover| (PathStep::HoverText, hover)))
+        .chain(children)
+}
+
+/// Collects the components of the tree for which `f` returns something,
+/// with their path.
+fn collect<'a, T>(
+    chat: &'a Chat,
+    path: &mut Vec<PathStep>,
+    f: &dyn Fn(&'a Chat) -> Option<T>,
+    found: &mut Vec<(Vec<PathStep>, T)>,
+) {
+    if let Some(value) = f(chat) {
+        found.push((path.clone(), value));
+    }
+    for (step, nested) in nested_steps(chat) {
+        path.push(step);
+        collect(nested, path, f, found);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Chat, ClickEvent, HoverEvent, PathStep, TranslationComponent};
+
+    #[test]
+    fn nested() {
+        let chat =
+            Chat::text("a").child(Chat::component(TranslationComponent::new("%s").argument(
+                Chat::text("b").hover(Some(HoverEvent::ShowText(Box::new(
+                    Chat::text("c").click(Some(ClickEvent::url("https://example.com"))),
+                )))),
+            )));
+        assert!(chat.has_formatting());
+        assert!(chat.has_hover_events());
+        assert!(chat.has_click_events());
+        assert!(!chat.uses_hex_colors());
+        assert_eq!(3, chat.max_depth());
+
+        let plain = Chat::text("a").child(Chat::text("b"));
+        assert!(!plain.has_formatting());
+        assert_eq!(0, Chat::text("a").max_depth());
+    }
+
+    #[test]
+    fn paths() {
+        let hover = HoverEvent::ShowText(Box::new(
+            Chat::text("c").click(Some(ClickEvent::command("/c"))),
+        ));
+        let chat = Chat::selector(
+            "@a",
+            Some(Chat::text(", ").click(Some(ClickEvent::command("/sep")))),
+        )
+        .child(Chat::text("b").hover(Some(hover.clone())));
+        let clicks = chat.click_events().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (vec![PathStep::Separator], &ClickEvent::command("/sep")),
+                (
+                    vec![PathStep::Child(0), PathStep::HoverText],
+                    &ClickEvent::command("/c")
+                ),
+            ],
+            clicks
+        );
+        assert_eq!(
+            vec![(vec![PathStep::Child(0)], &hover)],
+            chat.hover_events().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&chat), chat.find(&[]));
+        assert_eq!(None, chat.find(&[PathStep::Argument(0)]));
+    }
+}
@@ -0,0 +1,823 @@
+//! Rendering chat components as plain text, as text with ANSI escape codes
+//! for terminals or as HTML.
+//!
+//! Plain text is always available, the other formats need the `render`
+//! feature, which is enabled by default.
+//!
+//! The client resolves some components itself, a [`Renderer`] approximates
+//! them: translations show their key, keybinds the name of their default key
+//! (see [`KeybindResolver`]) and selectors their raw selector.
+
+use std::borrow::Cow;
+#[cfg(feature = "render")]
+use std::fmt::Write;
+
+use crate::{Chat, ClickEvent, ComponentKind, HoverEvent, Style, TextColor};
+
+/// Turns the identifier of a keybind, like `key.inventory`,
+/// into the name of the key that's bound to it.
+///
+/// Closures taking the identifier are resolvers as well.
+pub trait KeybindResolver {
+    fn resolve(&self, keybind: &str) -> Option<Cow<'_, str>>;
+}
+
+impl<F: Fn(&str) -> Option<String>> KeybindResolver for F {
+    fn resolve(&self, keybind: &str) -> Option<Cow<'_, str>> {
+        self(keybind).map(Cow::Owned)
+    }
+}
+
+/// The keys vanilla binds by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DefaultKeybinds;
+
+impl DefaultKeybinds {
+    const KEYS: &'static [(&'static str, &'static str)] = &[
+        ("key.advancements", "L"),
+        ("key.attack", "Left Button"),
+        ("key.back", "S"),
+        ("key.chat", "T"),
+        ("key.command", "/"),
+        ("key.drop", "Q"),
+        ("key.forward", "W"),
+        ("key.fullscreen", "F11"),
+        ("key.hotbar.1", "1"),
+        ("key.hotbar.2", "2"),
+        ("key.hotbar.3", "3"),
+        ("key.hotbar.4", "4"),
+        ("key.hotbar.5", "5"),
+        ("key.hotbar.6", "6"),
+        ("key.hotbar.7", "7"),
+        ("key.hotbar.8", "8"),
+        ("key.hotbar.9", "9"),
+        ("key.inventory", "E"),
+        ("key.jump", "Space"),
+        ("key.left", "A"),
+        ("key.loadToolbarActivator", "X"),
+        ("key.pickItem", "Middle Button"),
+        ("key.playerlist", "Tab"),
+        ("key.right", "D"),
+        ("key.saveToolbarActivator", "C"),
+        ("key.screenshot", "F2"),
+        ("key.smoothCamera", "Not Bound"),
+        ("key.sneak", "Left Shift"),
+        ("key.socialInteractions", "P"),
+        ("key.spectatorOutlines", "Not Bound"),
+        ("key.sprint", "Left Control"),
+        ("key.swapOffhand", "F"),
+        ("key.togglePerspective", "F5"),
+        ("key.use", "Right Button"),
+    ];
+}
+
+impl KeybindResolver for DefaultKeybinds {
+    fn resolve(&self, keybind: &str) -> Option<Cow<'_, str>> {
+        Self::KEYS
+            .binary_search_by_key(&keybind, |(id, _)| id)
+            .ok()
+            .map(|index| Cow::Borrowed(Self::KEYS[index].1))
+    }
+}
+
+/// Renders chat components, see the [module](self)'s documentation.
+///
+/// # Example
+/// ```
+/// use mc_chat::render::Renderer;
+/// use mc_chat::{Chat, TextColor};
+///
+/// let chat = Chat::text("Press ")
+///     .color(TextColor::Red)
+///     .child(Chat::keybind("key.inventory").bold(true));
+/// let renderer = Renderer::new();
+/// assert_eq!("Press E", renderer.plain(&chat));
+///
+/// let renderer = Renderer::new().keybinds(&|keybind: &str| Some(format!("<{}>", keybind)));
+/// assert_eq!("Press <key.inventory>", renderer.plain(&chat));
+/// ```
+#[cfg_attr(
+    feature = "render",
+    doc = r##"
+With the `render` feature, the same chat as ANSI escapes and HTML:
+```
+use mc_chat::render::Renderer;
+use mc_chat::{Chat, TextColor};
+
+let chat = Chat::text("Press ")
+    .color(TextColor::Red)
+    .child(Chat::keybind("key.inventory").bold(true));
+let renderer = Renderer::new();
+assert_eq!("\u{1b}[0;91mPress \u{1b}[0;91;1mE\u{1b}[0m", renderer.ansi(&chat));
+assert_eq!(
+    r#"<span style="color:#ff5555">Press </span><span style="color:#ff5555;font-weight:bold">E</span>"#,
+    renderer.html(&chat)
+);
+```
+"##
+)]
+#[derive(Clone, Copy)]
+pub struct Renderer<'a> {
+    keybinds: &'a dyn KeybindResolver,
+    obfuscation: Obfuscation,
+}
+
+impl Default for Renderer<'_> {
+    fn default() -> Self {
+        Self {
+            keybinds: &DefaultKeybinds,
+            obfuscation: Obfuscation::default(),
+        }
+    }
+}
+
+/// How a [`Renderer`] outputs obfuscated text, which the client shows as
+/// constantly changing characters.
+///
+/// # Example
+/// ```
+/// use mc_chat::render::{Obfuscation, Renderer};
+/// use mc_chat::Chat;
+///
+/// let chat = Chat::text("Secret: ").child(Chat::text("hunter 2").obfuscated(true));
+/// assert_eq!("Secret: hunter 2", Renderer::new().plain(&chat));
+///
+/// let renderer = Renderer::new().obfuscation(Obfuscation::PLACEHOLDER);
+/// assert_eq!("Secret: ▒▒▒▒▒▒ ▒", renderer.plain(&chat));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Obfuscation {
+    /// Output the raw characters.
+    #[default]
+    Reveal,
+    /// Replace every character except whitespace with this one, keeping the
+    /// width of the text.
+    Replace(char),
+}
+
+impl Obfuscation {
+    /// Replaces obfuscated text with `▒`.
+    pub const PLACEHOLDER: Obfuscation = Obfuscation::Replace('▒');
+}
+
+impl<'a> Renderer<'a> {
+    /// A renderer using the [`DefaultKeybinds`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Change how keybinds are resolved.
+    pub fn keybinds(mut self, keybinds: &'a dyn KeybindResolver) -> Self {
+        self.keybinds = keybinds;
+        self
+    }
+
+    /// Change how obfuscated text is output, it's revealed by default.
+    pub fn obfuscation(mut self, obfuscation: Obfuscation) -> Self {
+        self.obfuscation = obfuscation;
+        self
+    }
+
+    /// The text of the component without any style.
+    pub fn plain(&self, chat: &Chat) -> String {
+        self.runs(chat).map(|(text, _)| text).collect()
+    }
+
+    #[cfg(feature = "render")]
+    /// The text of the component with ANSI escape codes for its colors and
+    /// formatting, using 24-bit colors for custom colors.
+    pub fn ansi(&self, chat: &Chat) -> String {
+        let mut result = String::new();
+        let mut current = None;
+        for (text, style) in self.runs(chat) {
+            let codes = ansi_codes(&style);
+            if current.as_ref() != Some(&codes) {
+                let _ = write!(result, "\u{1b}[{}m", codes);
+                current = Some(codes);
+            }
+            result.push_str(&text);
+        }
+        if current.is_some() {
+            result.push_str("\u{1b}[0m");
+        }
+        result
+    }
+
+    #[cfg(feature = "render")]
+    /// The component as HTML `<span>`s with inline styles, unstyled text
+    /// isn't wrapped.
+    pub fn html(&self, chat: &Chat) -> String {
+        let mut result = String::new();
+        for (text, style) in self.runs(chat) {
+            let css = css(&style);
+            if !css.is_empty() {
+                let _ = write!(result, "<span style=\"{}\">", css);
+            }
+            for c in text.chars() {
+                match c {
+                    '&' => result.push_str("&amp;"),
+                    '<' => result.push_str("&lt;"),
+                    '>' => result.push_str("&gt;"),
+                    '"' => result.push_str("&quot;"),
+                    '\'' => result.push_str("&#39;"),
+                    '\n' => result.push_str("<br>"),
+                    c => result.push(c),
+                }
+            }
+            if !css.is_empty() {
+                result.push_str("</span>");
+            }
+        }
+        result
+    }
+
+    /// The text of every component in the order it's shown, with its
+    /// complete style. Components without text are skipped.
+    ///
+    /// The components are visited as the iterator advances.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::render::Renderer;
+    /// use mc_chat::{Chat, TextColor};
+    ///
+    /// let chat = Chat::text("Press ")
+    ///     .color(TextColor::Red)
+    ///     .child(Chat::keybind("key.jump").bold(true));
+    /// let runs = Renderer::new().runs(&chat).collect::<Vec<_>>();
+    /// assert_eq!("Space", runs[1].0);
+    /// assert!(runs[1].1.bold);
+    /// assert_eq!(Some(&TextColor::Red), runs[1].1.color);
+    /// ```
+    pub fn runs<'c>(&self, chat: &'c Chat) -> Runs<'a, 'c> {
+        Runs {
+            renderer: *self,
+            stack: vec![(chat, EffectiveStyle::default())],
+        }
+    }
+
+    /// The text of a single component with its complete style, if it has text.
+    fn run<'c>(
+        &self,
+        chat: &'c Chat,
+        style: EffectiveStyle<'c>,
+    ) -> Option<(Cow<'c, str>, EffectiveStyle<'c>)> {
+        let text = match chat.kind {
+            ComponentKind::Text(ref text) => Cow::Borrowed(&*text.text),
+            ComponentKind::Translation(ref translation) => {
+                Cow::Borrowed(translation.fallback.as_deref().unwrap_or(&translation.key))
+            }
+            ComponentKind::Score(ref score) => {
+                Cow::Borrowed(score.value.as_deref().unwrap_or_default())
+            }
+            ComponentKind::Selector(ref selector) => Cow::Borrowed(&*selector.selector),
+            ComponentKind::Keybind(ref keybind) => self
+                .keybinds
+                .resolve(&keybind.keybind)
+                .map(|key| Cow::Owned(key.into_owned()))
+                .unwrap_or(Cow::Borrowed(&*keybind.keybind)),
+            ComponentKind::Nbt(ref nbt) => Cow::Borrowed(&*nbt.nbt),
+        };
+        if text.is_empty() {
+            return None;
+        }
+        match self.obfuscation {
+            Obfuscation::Replace(placeholder) if style.obfuscated => {
+                let text = text
+                    .chars()
+                    .map(|c| if c.is_whitespace() { c } else { placeholder })
+                    .collect::<String>();
+                Some((Cow::Owned(text), style))
+            }
+            _ => Some((text, style)),
+        }
+    }
+}
+
+/// The text of a component and its children with their complete style,
+/// see [`Renderer::runs()`].
+#[derive(Clone)]
+pub struct Runs<'a, 'c> {
+    renderer: Renderer<'a>,
+    /// The components left to visit with the style of their parent, the
+    /// next one on top.
+    stack: Vec<(&'c Chat, EffectiveStyle<'c>)>,
+}
+
+impl<'c> Iterator for Runs<'_, 'c> {
+    type Item = (Cow<'c, str>, EffectiveStyle<'c>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((chat, parent)) = self.stack.pop() {
+            let style = parent.inherit(&chat.style);
+            self.stack
+                .extend(chat.children.iter().rev().map(|child| (child, style)));
+            if let Some(run) = self.renderer.run(chat, style) {
+                return Some(run);
+            }
+        }
+        None
+    }
+}
+
+/// The complete style of text, after inheriting from its parents.
+///
+/// Formatting that isn't set anywhere is off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EffectiveStyle<'a> {
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+    pub color: Option<&'a TextColor>,
+    pub insertion: Option<&'a str>,
+    pub font: Option<&'a str>,
+    pub click_event: Option<&'a ClickEvent>,
+    pub hover_event: Option<&'a HoverEvent>,
+}
+
+impl<'a> EffectiveStyle<'a> {
+    /// The style of a child component with the given style.
+    pub fn inherit(&self, style: &'a Style) -> EffectiveStyle<'a> {
+        EffectiveStyle {
+            bold: style.bold.unwrap_or(self.bold),
+            italic: style.italic.unwrap_or(self.italic),
+            underlined: style.underlined.unwrap_or(self.underlined),
+            strikethrough: style.strikethrough.unwrap_or(self.strikethrough),
+            obfuscated: style.obfuscated.unwrap_or(self.obfuscated),
+            color: style.color.as_ref().or(self.color),
+            insertion: style.insertion.as_deref().or(self.insertion),
+            font: style.font.as_deref().or(self.font),
+            click_event: style.click_event.as_ref().or(self.click_event),
+            hover_event: style.hover_event.as_ref().or(self.hover_event),
+        }
+    }
+}
+
+impl<'a> From<&'a Style> for EffectiveStyle<'a> {
+    fn from(style: &'a Style) -> Self {
+        EffectiveStyle::default().inherit(style)
+    }
+}
+
+#[cfg(feature = "render")]
+fn ansi_codes(style: &EffectiveStyle<'_>) -> String {
+    let mut codes = String::from("0");
+    match style.color {
+        Some(color @ TextColor::Custom(_)) => {
+            if let Some([r, g, b]) = color.rgb() {
+                let _ = write!(codes, ";38;2;{};{};{}", r, g, b);
+            }
+        }
+        Some(color) => {
+            let code = match color {
+                TextColor::Black => "30",
+                TextColor::DarkBlue => "34",
+                TextColor::DarkGreen => "32",
+                TextColor::DarkCyan => "36",
+                TextColor::DarkRed => "31",
+                TextColor::Purple => "35",
+                TextColor::Gold => "33",
+                TextColor::Gray => "37",
+                TextColor::DarkGray => "90",
+                TextColor::Blue => "94",
+                TextColor::Green => "92",
+                TextColor::Cyan => "96",
+                TextColor::Red => "91",
+                TextColor::Pink => "95",
+                TextColor::Yellow => "93",
+                TextColor::White => "97",
+                TextColor::Custom(_) | TextColor::Reset => "",
+            };
+            if !code.is_empty() {
+                codes.push(';');
+                codes.push_str(code);
+            }
+        }
+        None => {}
+    }
+    for (enabled, code) in [
+        (style.bold, ";1"),
+        (style.italic, ";3"),
+        (style.underlined, ";4"),
+        (style.strikethrough, ";9"),
+    ]
+    .iter()
+    {
+        if *enabled {
+            codes.push_str(code);
+        }
+    }
+    codes
+}
+
+#[cfg(feature = "render")]
+fn css(style: &EffectiveStyle<'_>) -> String {
+    let mut css = Vec::new();
+    if let Some([r, g, b]) = style.color.and_then(TextColor::rgb) {
+        css.push(format!("color:#{:02x}{:02x}{:02x}", r, g, b));
+    }
+    if style.bold {
+        css.push("font-weight:bold".to_string());
+    }
+    if style.italic {
+        css.push("font-style:italic".to_string());
+    }
+    let mut decorations = Vec::new();
+    if style.underlined {
+        decorations.push("underline");
+    }
+    if style.strikethrough {
+        decorations.push("line-through");
+    }
+    if !decorations.is_empty() {
+        css.push(format!("text-decoration:{}", decorations.join(" ")));
+    }
+    css.join(";")
+}
+
+#[cfg(feature = "render")]
+/// Removes the ANSI escape codes of [`Renderer::ansi()`] from text, for
+/// output that doesn't support them.
+///
+/// # Example
+/// ```
+/// use mc_chat::render::strip_ansi;
+/// use mc_chat::{Chat, TextColor};
+///
+/// let chat = Chat::text("Hello").color(TextColor::Red);
+/// assert_eq!(chat.to_plain(), strip_ansi(&chat.to_ansi()));
+/// ```
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\u{1b}') {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find("\u{1b}[") {
+        result.push_str(&rest[..index]);
+        let sequence = &rest[index + 2..];
+        // the sequence ends with a byte in the range `@` to `~`
+        rest = match sequence.find(|c| ('@'..='~').contains(&c)) {
+            Some(end) => &sequence[end + 1..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+impl Chat {
+    /// The text of this component without any style, see [`Renderer::plain()`].
+    pub fn to_plain(&self) -> String {
+        Renderer::new().plain(self)
+    }
+
+    #[cfg(feature = "render")]
+    /// This component with ANSI escape codes, see [`Renderer::ansi()`].
+    pub fn to_ansi(&self) -> String {
+        Renderer::new().ansi(self)
+    }
+
+    #[cfg(feature = "render")]
+    /// This component as HTML, see [`Renderer::html()`].
+    pub fn to_html(&self) -> String {
+        Renderer::new().html(self)
+    }
+
+    /// The text of every component with its complete style, see
+    /// [`Renderer::runs()`].
+    pub fn runs(&self) -> Runs<'static, '_> {
+        Renderer::new().runs(self)
+    }
+
+    #[cfg(feature = "render")]
+    /// An indented tree of this component for debugging, one line per
+    /// component with its kind, contents and complete style.
+    ///
+    /// Settings inherited from a parent are put between parentheses, or
+    /// dimmed with `ansi`, which also shows text in its own style.
+    /// Translation arguments, selector separators and hover text are
+    /// listed before the children.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TextColor, TranslationComponent};
+    ///
+    /// let chat = Chat::text("Hello ")
+    ///     .color(TextColor::Gold)
+    ///     .child(Chat::component(
+    ///         TranslationComponent::new("chat.type.text").argument(Chat::text("Steve")),
+    ///     ))
+    ///     .child(Chat::keybind("key.jump").bold(true));
+    /// assert_eq!(
+    ///     r#"text "Hello " color=Gold
+    /// ├─ translate "chat.type.text" (color=Gold)
+    /// │  └─ with[0]: text "Steve" (color=Gold)
+    /// └─ keybind "key.jump" bold (color=Gold)
+    /// "#,
+    ///     chat.debug_tree(false)
+    /// );
+    /// ```
+    pub fn debug_tree(&self, ansi: bool) -> String {
+        let mut tree = String::new();
+        tree_node(self, &Style::default(), ansi, "", ("", ""), &mut tree);
+        tree
+    }
+}
+
+#[cfg(feature = "render")]
+/// Writes a line for a component and the lines of everything nested in it.
+/// The first prefix is put in front of the component's line, the second in
+/// front of the lines nested in it.
+fn tree_node(
+    chat: &Chat,
+    parent: &Style,
+    ansi: bool,
+    label: &str,
+    (first_prefix, prefix): (&str, &str),
+    tree: &mut String,
+) {
+    let style = chat.style.inherit(parent);
+    let (kind, contents) = match chat.kind {
+        ComponentKind::Text(ref text) => ("text", format!("{:?}", &*text.text)),
+        ComponentKind::Translation(ref translation) => {
+            ("translate", format!("{:?}", &*translation.key))
+        }
+        ComponentKind::Score(ref score) => (
+            "score",
+            match score.value {
+                Some(ref value) => format!(
+                    "{:?} {:?} = {:?}",
+                    &*score.name, &*score.objective, &**value
+                ),
+                None => format!("{:?} {:?}", &*score.name, &*score.objective),
+            },
+        ),
+        ComponentKind::Selector(ref selector) => ("selector", format!("{:?}", &*selector.selector)),
+        ComponentKind::Keybind(ref keybind) => ("keybind", format!("{:?}", &*keybind.keybind)),
+        ComponentKind::Nbt(ref nbt) => {
+            let (source, value) = match nbt.source {
+                crate::NbtSource::Block(ref pos) => ("block", pos),
+                crate::NbtSource::Entity(ref selector) => ("entity", selector),
+                crate::NbtSource::Storage(ref id) => ("storage", id),
+            };
+            let interpret = if nbt.interpret { " interpret" } else { "" };
+            (
+                "nbt",
+                format!("{} {:?} {:?}{}", source, &**value, &*nbt.nbt, interpret),
+            )
+        }
+    };
+    tree.push_str(first_prefix);
+    tree.push_str(label);
+    if ansi {
+        let _ = write!(
+            tree,
+            "\u{1b}[1m{}\u{1b}[0m \u{1b}[{}m{}\u{1b}[0m",
+            kind,
+            ansi_codes(&EffectiveStyle::from(&style)),
+            contents
+        );
+    } else {
+        let _ = write!(tree, "{} {}", kind, contents);
+    }
+    let own = settings(&chat.style);
+    let inherited = settings(&style)
+        .into_iter()
+        .filter(|setting| !own.contains(setting))
+        .collect::<Vec<_>>();
+    for setting in &own {
+        tree.push(' ');
+        tree.push_str(setting);
+    }
+    for setting in &inherited {
+        if ansi {
+            let _ = write!(tree, " \u{1b}[2m{}\u{1b}[0m", setting);
+        } else {
+            let _ = write!(tree, " ({})", setting);
+        }
+    }
+    tree.push('\n');
+
+    // nested components with their label and the style they inherit
+    let mut nested = Vec::new();
+    let default_style = Style::default();
+    match chat.kind {
+        ComponentKind::Translation(ref translation) => {
+            for (index, argument) in translation.with.iter().enumerate() {
+                if let Some(argument) = argument.as_chat() {
+                    nested.push((format!("with[{}]: ", index), argument, &style));
+                }
+            }
+        }
+        ref kind => {
+            if let Some(sep) = kind.separator() {
+                nested.push(("separator: ".to_string(), sep, &default_style));
+            }
+        }
+    }
+    if let Some(text) = chat.style.hover_event.as_ref().and_then(HoverEvent::text) {
+        nested.push(("hover: ".to_string(), text, &default_style));
+    }
+    for child in &chat.children {
+        nested.push((String::new(), child, &style));
+    }
+    let count = nested.len();
+    for (index, (label, child, parent)) in nested.into_iter().enumerate() {
+        let (first, rest) = if index + 1 == count {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        tree_node(
+            child,
+            parent,
+            ansi,
+            &label,
+            (
+                &format!("{}{}", prefix, first),
+                &format!("{}{}", prefix, rest),
+            ),
+            tree,
+        );
+    }
+}
+
+/// The settings of a style as they're shown by [`Chat::debug_tree()`].
+#[cfg(feature = "render")]
+fn settings(style: &Style) -> Vec<String> {
+    let mut settings = Vec::new();
+    for (name, value) in [
+        ("bold", style.bold),
+        ("italic", style.italic),
+        ("underlined", style.underlined),
+        ("strikethrough", style.strikethrough),
+        ("obfuscated", style.obfuscated),
+    ]
+    .iter()
+    {
+        match value {
+            Some(true) => settings.push(name.to_string()),
+            Some(false) => settings.push(format!("{}=false", name)),
+            None => {}
+        }
+    }
+    match style.color {
+        Some(TextColor::Custom(rgb)) => settings.push(format!("color={}", rgb)),
+        Some(ref color) => settings.push(format!("color={:?}", color)),
+        None => {}
+    }
+    if let Some(ref font) = style.font {
+        settings.push(format!("font={:?}", &**font));
+    }
+    if let Some(ref insertion) = style.insertion {
+        settings.push(format!("insertion={:?}", &**insertion));
+    }
+    if let Some(ref click) = style.click_event {
+        settings.push(match click {
+            ClickEvent::OpenUrl(url) => format!("click=open_url {:?}", &**url),
+            ClickEvent::RunCommand(command) => format!("click=run_command {:?}", &**command),
+            ClickEvent::SuggestCommand(command) => {
+                format!("click=suggest_command {:?}", &**command)
+            }
+            ClickEvent::ChangePage(page) => format!("click=change_page {}", page),
+            ClickEvent::CopyToClipBoard(text) => format!("click=copy_to_clipboard {:?}", &**text),
+            ClickEvent::Custom { id, .. } => format!("click=custom {:?}", &**id),
+            ClickEvent::ShowDialog(dialog) => format!("click=show_dialog {:?}", &**dialog),
+        });
+    }
+    if let Some(ref hover) = style.hover_event {
+        settings.push(match hover {
+            HoverEvent::ShowText(_) | HoverEvent::ShowSharedText(_) => {
+                "hover=show_text".to_string()
+            }
+            HoverEvent::ShowItem(item) => format!("hover=show_item {:?}", &*item.id),
+            HoverEvent::ShowEntity(entity) => match entity.kind {
+                Some(ref kind) => format!("hover=show_entity {:?}", &**kind),
+                None => "hover=show_entity".to_string(),
+            },
+            HoverEvent::ShowAchievement(key) => format!("hover=show_achievement {:?}", &**key),
+        });
+    }
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_inherit_style() {
+        let chat = Chat::text("a")
+            .bold(true)
+            .click(Some(ClickEvent::page(2u32)))
+            .child(Chat::text("").child(Chat::text("b").bold(false).color(TextColor::Gold)))
+            .child(Chat::score("Steve", "kills"));
+        let runs = chat.runs().collect::<Vec<_>>();
+        assert_eq!(2, runs.len());
+        assert_eq!(("a", true), (&*runs[0].0, runs[0].1.bold));
+        let b = &runs[1].1;
+        assert_eq!("b", runs[1].0);
+        assert!(!b.bold);
+        assert_eq!(Some(&TextColor::Gold), b.color);
+        assert_eq!(Some(&ClickEvent::page(2u32)), b.click_event);
+        assert!(matches!(runs[0].0, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn runs_in_order() {
+        let chat = Chat::text("a")
+            .child(Chat::text("b").child(Chat::text("c")))
+            .child(Chat::text("d"));
+        let mut runs = chat.runs();
+        assert_eq!("a", runs.next().unwrap().0);
+        assert_eq!(
+            vec!["b", "c", "d"],
+            runs.map(|(text, _)| text).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn keybind_table_is_sorted() {
+        assert!(DefaultKeybinds::KEYS
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(
+            Some(Cow::Borrowed("Left Shift")),
+            DefaultKeybinds.resolve("key.sneak")
+        );
+        assert_eq!("key.unknown", Chat::keybind("key.unknown").to_plain());
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn formats() {
+        let chat = Chat::text("a")
+            .child(
+                Chat::text("<b>")
+                    .color(TextColor::from_rgb(0x10, 0x20, 0x30))
+                    .underlined(true)
+                    .strikethrough(true),
+            )
+            .child(Chat::text("c").italic(true));
+        assert_eq!("a<b>c", chat.to_plain());
+        assert_eq!(
+            "\u{1b}[0ma\u{1b}[0;38;2;16;32;48;4;9m<b>\u{1b}[0;3mc\u{1b}[0m",
+            chat.to_ansi()
+        );
+        assert_eq!(
+            "a<span style=\"color:#102030;text-decoration:underline line-through\">&lt;b&gt;</span><span style=\"font-style:italic\">c</span>",
+            chat.to_html()
+        );
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn obfuscation() {
+        let chat = Chat::text("a b")
+            .obfuscated(true)
+            .child(Chat::text("<c>\n").color(TextColor::Red))
+            .child(Chat::text("d").obfuscated(false));
+        let renderer = Renderer::new().obfuscation(Obfuscation::Replace('*'));
+        assert_eq!("a b<c>\nd", chat.to_plain());
+        assert_eq!("* ****\nd", renderer.plain(&chat));
+        assert_eq!(
+            "* *<span style=\"color:#ff5555\">***<br></span>d",
+            renderer.html(&chat)
+        );
+        assert_eq!(
+            "\u{1b}[0m* *\u{1b}[0;91m***\n\u{1b}[0md\u{1b}[0m",
+            renderer.ansi(&chat)
+        );
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn tree() {
+        let chat = Chat::selector("@a", Some(Chat::text(", ")))
+            .click(Some(ClickEvent::command("/list")))
+            .hover(Some(HoverEvent::ShowText(Box::new(
+                Chat::text("Players").child(Chat::score("@s", "kills").italic(false)),
+            ))))
+            .child(Chat::text("!").color(TextColor::from_rgb(0x10, 0x20, 0x30)));
+        assert_eq!(
+            r#"selector "@a" click=run_command "/list" hover=show_text
+├─ separator: text ", "
+├─ hover: text "Players"
+│  └─ score "@s" "kills" italic=false
+└─ text "!" color=#102030 (click=run_command "/list") (hover=show_text)
+"#,
+            chat.debug_tree(false)
+        );
+        assert_eq!(
+            "\u{1b}[1mtext\u{1b}[0m \u{1b}[0;1m\"a\"\u{1b}[0m bold\n\
+             └─ \u{1b}[1mtext\u{1b}[0m \u{1b}[0;1m\"b\"\u{1b}[0m \u{1b}[2mbold\u{1b}[0m\n",
+            Chat::text("a")
+                .bold(true)
+                .child(Chat::text("b"))
+                .debug_tree(true)
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use std::borrow::Cow;
+
+use crate::render::{DefaultKeybinds, KeybindResolver};
+use crate::translations::fill_pattern;
+use crate::{
+    Chat, ComponentKind, HoverEvent, NbtSource, ProtocolVersion, TextColor, TextComponent, ToChat,
+    TranslationArg,
+};
+
+/// Supplies what a server knows and a client would otherwise fill in,
+/// for [`Chat::resolve()`].
+///
+/// Every method returns [`None`] by default, which leaves that kind of
+/// component as it is. A pair of resolvers tries the first one, then
+/// the second.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+///
+/// use mc_chat::render::DefaultKeybinds;
+/// use mc_chat::translations::Translations;
+/// use mc_chat::{Chat, Resolver, TranslationComponent};
+///
+/// struct Scores;
+///
+/// impl Resolver for Scores {
+///     fn score(&self, name: &str, objective: &str) -> Option<String> {
+///         (objective == "coins").then(|| format!("{} coins", name.len()))
+///     }
+/// }
+///
+/// let translations = Translations::from_lang("hint=Press %s to jump, you have %s");
+/// let chat = Chat::component(
+///     TranslationComponent::new("hint")
+///         .argument(Chat::keybind("key.jump"))
+///         .argument(Chat::score("Steve", "coins")),
+/// );
+/// assert_eq!(
+///     "Press Space to jump, you have 5 coins",
+///     chat.resolve(&(&translations, (DefaultKeybinds, Scores))).to_plain()
+/// );
+/// ```
+pub trait Resolver {
+    /// The pattern of a translation key, see [`Chat::resolve_translations()`].
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        let _ = key;
+        None
+    }
+
+    /// The value of a score, see [`Chat::resolve_scores()`].
+    fn score(&self, name: &str, objective: &str) -> Option<String> {
+        let _ = (name, objective);
+        None
+    }
+
+    /// The names of the entities a selector selects, see [`Chat::resolve_selectors()`].
+    fn selector(&self, selector: &str) -> Option<Vec<Chat>> {
+        let _ = selector;
+        None
+    }
+
+    /// The name of the key bound to a keybind.
+    fn keybind(&self, keybind: &str) -> Option<Cow<'_, str>> {
+        let _ = keybind;
+        None
+    }
+
+    /// The values at the path of an [`NbtComponent`](crate::NbtComponent),
+    /// as SNBT text or, when `interpret` is set, read as components.
+    fn nbt(&self, source: &NbtSource, path: &str, interpret: bool) -> Option<Vec<Chat>> {
+        let _ = (source, path, interpret);
+        None
+    }
+}
+
+impl<R: Resolver + ?Sized> Resolver for &R {
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        (**self).translate(key)
+    }
+
+    fn score(&self, name: &str, objective: &str) -> Option<String> {
+        (**self).score(name, objective)
+    }
+
+    fn selector(&self, selector: &str) -> Option<Vec<Chat>> {
+        (**self).selector(selector)
+    }
+
+    fn keybind(&self, keybind: &str) -> Option<Cow<'_, str>> {
+        (**self).keybind(keybind)
+    }
+
+    fn nbt(&self, source: &NbtSource, path: &str, interpret: bool) -> Option<Vec<Chat>> {
+        (**self).nbt(source, path, interpret)
+    }
+}
+
+impl<A: Resolver, B: Resolver> Resolver for (A, B) {
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.0.translate(key).or_else(|| self.1.translate(key))
+    }
+
+    fn score(&self, name: &str, objective: &str) -> Option<String> {
+        self.0
+            .score(name, objective)
+            .or_else(|| self.1.score(name, objective))
+    }
+
+    fn selector(&self, selector: &str) -> Option<Vec<Chat>> {
+        self.0
+            .selector(selector)
+            .or_else(|| self.1.selector(selector))
+    }
+
+    fn keybind(&self, keybind: &str) -> Option<Cow<'_, str>> {
+        self.0.keybind(keybind).or_else(|| self.1.keybind(keybind))
+    }
+
+    fn nbt(&self, source: &NbtSource, path: &str, interpret: bool) -> Option<Vec<Chat>> {
+        self.0
+            .nbt(source, path, interpret)
+            .or_else(|| self.1.nbt(source, path, interpret))
+    }
+}
+
+impl Resolver for DefaultKeybinds {
+    fn keybind(&self, keybind: &str) -> Option<Cow<'_, str>> {
+        KeybindResolver::resolve(self, keybind)
+    }
+}
+
+impl Chat {
+    /// Resolves every translation, score, selector, keybind and nbt component
+    /// the resolver knows in one pass over the tree, including hover text and
+    /// arguments.
+    ///
+    /// Translations are resolved like [`Chat::resolve_translations()`] and
+    /// selectors like [`Chat::resolve_selectors()`]. Scores and keybinds are
+    /// replaced by text components, keeping their style and children. The
+    /// values of nbt components are joined by `, ` like the client does.
+    /// Whatever the resolver doesn't know stays for the client to resolve.
+    pub fn resolve<R: Resolver + ?Sized>(&self, resolver: &R) -> Chat {
+        let mut style = self.style.clone();
+        if let Some(text) = style.hover_event.as_mut().and_then(HoverEvent::text_mut) {
+            *text = text.resolve(resolver);
+        }
+        let mut chat = Chat {
+            kind: self.kind.clone(),
+            style,
+            children: Vec::with_capacity(self.children.len()),
+            #[cfg(feature = "extensions")]
+            extensions: self.extensions.clone(),
+        };
+        match self.kind {
+            ComponentKind::Translation(ref translation) => {
+                let arguments = translation
+                    .with
+                    .iter()
+                    .map(|argument| match argument {
+                        TranslationArg::Chat(argument) => argument.resolve(resolver).into(),
+                        primitive => primitive.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                match resolver.translate(&translation.key) {
+                    Some(pattern) => {
+                        let arguments = arguments.iter().map(ToChat::to_chat).collect::<Vec<_>>();
+                        chat.kind = TextComponent::new("").into();
+                        chat.children = fill_pattern(&pattern, &arguments);
+                    }
+                    None => {
+                        if let ComponentKind::Translation(ref mut unresolved) = chat.kind {
+                            unresolved.with = arguments;
+                        }
+                    }
+                }
+            }
+            ComponentKind::Score(ref score) => {
+                if let Some(value) = resolver.score(&score.name, &score.objective) {
+                    chat.kind = TextComponent::new(value).into();
+                }
+            }
+            ComponentKind::Selector(ref selector) => match resolver.selector(&selector.selector) {
+                Some(names) => {
+                    let sep = match selector.sep {
+                        Some(ref sep) => sep.resolve(resolver),
+                        None => default_separator(),
+                    };
+                    let names = names.iter().map(|name| name.resolve(resolver)).collect();
+                    chat.kind = TextComponent::new("").into();
+                    chat.children = join(names, &sep);
+                }
+                None => {
+                    if let ComponentKind::Selector(ref mut unresolved) = chat.kind {
+                        if let Some(ref mut sep) = unresolved.sep {
+                            **sep = sep.resolve(resolver);
+                        }
+                    }
+                }
+            },
+            ComponentKind::Keybind(ref keybind) => {
+                if let Some(name) = resolver.keybind(&keybind.keybind) {
+                    chat.kind = TextComponent::new(name.into_owned()).into();
+                }
+            }
+            ComponentKind::Nbt(ref nbt) => match resolver.nbt(&nbt.source, &nbt.nbt, nbt.interpret)
+            {
+                Some(values) => {
+                    let sep = match nbt.sep {
+                        Some(ref sep) => sep.resolve(resolver),
+                        None => Chat::text(", "),
+                    };
+                    let values = values.iter().map(|value| value.resolve(resolver)).collect();
+                    chat.kind = TextComponent::new("").into();
+                    chat.children = join(values, &sep);
+                }
+                None => {
+                    if let ComponentKind::Nbt(ref mut unresolved) = chat.kind {
+                        if let Some(ref mut sep) = unresolved.sep {
+                            **sep = sep.resolve(resolver);
+                        }
+                    }
+                }
+            },
+            ComponentKind::Text(_) => {}
+        }
+        chat.children
+            .extend(self.children.iter().map(|child| child.resolve(resolver)));
+        chat
+    }
+
+    /// Fills in the value of every score component with a callback taking
+    /// the score holder's name and the objective, like a server does before
+    /// sending a component.
+    ///
+    /// Since 1.20.3 (protocol 765) score components have no value anymore,
+    /// they are replaced by a text component with the value instead, keeping
+    /// their style and children. Scores the callback returns [`None`] for are
+    /// left as they are.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, ScoreComponent};
+    ///
+    /// let mut chat = Chat::text("Kills: ").child(Chat::score("Steve", "kills"));
+    /// chat.resolve_scores(765, |name, objective| match (name, objective) {
+    ///     ("Steve", "kills") => Some(12),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(Chat::text("Kills: ").child(Chat::text("12")), chat);
+    ///
+    /// let mut chat = Chat::score("Steve", "kills");
+    /// chat.resolve_scores(764, |_, _| Some(12));
+    /// assert_eq!(
+    ///     Chat::component(ScoreComponent::new("Steve", "kills").value(Some("12"))),
+    ///     chat
+    /// );
+    /// ```
+    pub fn resolve_scores<P, F, V>(&mut self, version: P, mut resolver: F)
+    where
+        P: Into<ProtocolVersion>,
+        F: FnMut(&str, &str) -> Option<V>,
+        V: ToString,
+    {
+        let version = version.into();
+        for_each_mut(self, &mut |chat| {
+            if let ComponentKind::Score(ref mut score) = chat.kind {
+                if let Some(value) = resolver(&score.name, &score.objective) {
+                    let value = value.to_string();
+                    if version.supports_score_values() {
+                        score.value = Some(value.into());
+                    } else {
+                        chat.kind = TextComponent::new(value).into();
+                    }
+                }
+            }
+        });
+    }
+
+    /// Replaces every selector component with the names of the entities it
+    /// selects, looked up by a callback taking the selector. The names are
+    /// joined by the component's separator, or a gray `", "` like vanilla.
+    ///
+    /// A replaced selector becomes an empty text component with its style,
+    /// the names as its first children and its own children after them.
+    /// Selectors the callback returns [`None`] for are left as they are.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TextColor};
+    ///
+    /// let mut chat = Chat::text("Online: ").child(Chat::selector("@a", None));
+    /// chat.resolve_selectors(|selector| match selector {
+    ///     "@a" => Some(vec![Chat::text("Steve"), Chat::text("Alex")]),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(
+    ///     Chat::text("Online: ").child(
+    ///         Chat::text("")
+    ///             .child(Chat::text("Steve"))
+    ///             .child(Chat::text(", ").color(TextColor::Gray))
+    ///             .child(Chat::text("Alex"))
+    ///     ),
+    ///     chat
+    /// );
+    /// ```
+    pub fn resolve_selectors<F>(&mut self, mut resolver: F)
+    where
+        F: FnMut(&str) -> Option<Vec<Chat>>,
+    {
+        for_each_mut(self, &mut |chat| {
+            if let ComponentKind::Selector(ref selector) = chat.kind {
+                if let Some(names) = resolver(&selector.selector) {
+                    let sep = match selector.sep {
+                        Some(ref sep) => (**sep).clone(),
+                        None => default_separator(),
+                    };
+                    chat.kind = TextComponent::new("").into();
+                    let children = std::mem::take(&mut chat.children);
+                    chat.children = join(names, &sep);
+                    chat.children.extend(children);
+                }
+            }
+        });
+    }
+}
+
+/// The names of a selector with the separator between them.
+fn join(names: Vec<Chat>, sep: &Chat) -> Vec<Chat> {
+    let mut joined = Vec::with_capacity((names.len() * 2).saturating_sub(1));
+    for (index, name) in names.into_iter().enumerate() {
+        if index > 0 {
+            joined.push(sep.clone());
+        }
+        joined.push(name);
+    }
+    joined
+}
+
+/// The separator the client uses between the names of a selector.
+fn default_separator() -> Chat {
+    Chat::text(", ").color(TextColor::Gray)
+}
+
+/// Calls `f` for a component and every component nested in it: its children,
+/// translation arguments, selector separators and hover text.
+pub(crate) fn for_each_mut<F: FnMut(&mut Chat)>(chat: &mut Chat, f: &mut F) {
+    f(chat);
+    match chat.kind {
+        ComponentKind::Translation(ref mut translation) => {
+            for argument in translation.components_mut() {
+                for_each_mut(argument, f);
+            }
+        }
+        ref mut kind => {
+            if let Some(sep) = kind.separator_mut() {
+                for_each_mut(sep, f);
+            }
+        }
+    }
+    if let Some(text) = chat
+        .style
+        .hover_event
+        .as_mut()
+        .and_then(HoverEvent::text_mut)
+    {
+        for_each_mut(text, f);
+    }
+    for child in chat.children.iter_mut() {
+        for_each_mut(child, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NbtComponent, ScoreComponent, TextColor, TranslationComponent};
+
+    #[test]
+    fn nbt() {
+        struct Storage;
+
+        impl Resolver for Storage {
+            fn nbt(&self, source: &NbtSource, path: &str, _: bool) -> Option<Vec<Chat>> {
+                match (source, path) {
+                    (NbtSource::Storage(id), "names") if &**id == "mc:data" => {
+                        Some(vec![Chat::text("a"), Chat::keybind("key.jump")])
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        let chat = Chat::component(NbtComponent::storage("mc:data", "names"))
+            .child(Chat::component(NbtComponent::entity("@s", "Pos")));
+        assert_eq!(
+            "a, SpacePos",
+            chat.resolve(&(Storage, DefaultKeybinds)).to_plain()
+        );
+    }
+
+    #[test]
+    fn nested_scores() {
+        let mut chat = Chat::component(
+            TranslationComponent::new("key").argument(
+                Chat::score("Alex", "deaths")
+                    .color(TextColor::Red)
+                    .child(Chat::text("!")),
+            ),
+        )
+        .hover(Some(HoverEvent::ShowText(Box::new(Chat::score(
+            "Steve", "deaths",
+        )))))
+        .child(Chat::score("Steve", "unknown"));
+        chat.resolve_scores(765, |name, objective| match objective {
+            "deaths" => Some(name.len()),
+            _ => None,
+        });
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("key")
+                    .argument(Chat::text("4").color(TextColor::Red).child(Chat::text("!"))),
+            )
+            .hover(Some(HoverEvent::ShowText(Box::new(Chat::text("5")))))
+            .child(Chat::component(ScoreComponent::new("Steve", "unknown"))),
+            chat
+        );
+    }
+
+    #[test]
+    fn selectors() {
+        let mut chat = Chat::selector("@a", Some(Chat::text(" | ")))
+            .bold(true)
+            .child(Chat::text("!"))
+            .child(Chat::selector("@e", None))
+            .child(Chat::selector("@s", None));
+        chat.resolve_selectors(|selector| match selector {
+            "@a" => Some(vec![Chat::text("a"), Chat::text("b"), Chat::text("c")]),
+            "@e" => Some(vec![]),
+            _ => None,
+        });
+        assert_eq!(
+            Chat::text("")
+                .bold(true)
+                .child(Chat::text("a"))
+                .child(Chat::text(" | "))
+                .child(Chat::text("b"))
+                .child(Chat::text(" | "))
+                .child(Chat::text("c"))
+                .child(Chat::text("!"))
+                .child(Chat::text(""))
+                .child(Chat::selector("@s", None)),
+            chat
+        );
+    }
+
+    #[test]
+    fn resolve_all() {
+        struct Server;
+
+        impl Resolver for Server {
+            fn score(&self, name: &str, _: &str) -> Option<String> {
+                Some(name.len().to_string())
+            }
+
+            fn selector(&self, selector: &str) -> Option<Vec<Chat>> {
+                (selector == "@a").then(|| vec![Chat::score("Steve", "x"), Chat::text("Alex")])
+            }
+        }
+
+        let chat = Chat::selector("@a", Some(Chat::keybind("key.jump")))
+            .italic(true)
+            .child(
+                Chat::keybind("key.unknown")
+                    .child(Chat::selector("@p", Some(Chat::keybind("key.jump")))),
+            );
+        assert_eq!(
+            Chat::text("")
+                .italic(true)
+                .child(Chat::text("5"))
+                .child(Chat::text("Space"))
+                .child(Chat::text("Alex"))
+                .child(
+                    Chat::keybind("key.unknown")
+                        .child(Chat::selector("@p", Some(Chat::text("Space"))))
+                ),
+            chat.resolve(&(Server, DefaultKeybinds))
+        );
+    }
+}
@@ -0,0 +1,477 @@
+use std::ops::Range;
+
+use crate::{Chat, ComponentKind, Style};
+
+/// The most runs a chunk holds before it's split in two.
+const MAX_RUNS: usize = 32;
+/// The most characters a single text run holds.
+const MAX_RUN_LEN: usize = 512;
+
+/// A flat, editable form of a [`Chat`] component for chat input fields and
+/// editors.
+///
+/// The tree is turned into a list of runs of text, each with the style it
+/// resolves to. Components other than text, like translations, are kept
+/// whole and count as a single character. The runs are stored in small
+/// chunks, an edit only touches the chunk it lands in instead of rebuilding
+/// the whole message.
+///
+/// Offsets are counted in characters, like a cursor moves.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, ChatRope, Style, TextColor};
+///
+/// let mut rope = ChatRope::from(&Chat::text("Hello world").color(TextColor::Gold));
+/// rope.insert(5, ",");
+/// rope.delete(7..12);
+/// let mut green = Style::new();
+/// green.color(TextColor::Green);
+/// rope.insert_styled(7, "there", green);
+/// assert_eq!(
+///     Chat::text("")
+///         .child(Chat::text("Hello, ").color(TextColor::Gold))
+///         .child(Chat::text("there").color(TextColor::Green)),
+///     rope.to_chat()
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ChatRope {
+    len: usize,
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Chunk {
+    len: usize,
+    runs: Vec<Run>,
+}
+
+#[derive(Clone, Debug)]
+struct Run {
+    len: usize,
+    style: Style,
+    content: Content,
+}
+
+#[derive(Clone, Debug)]
+enum Content {
+    Text(String),
+    Component(ComponentKind),
+}
+
+impl ChatRope {
+    /// Creates an empty rope.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The amount of characters in this rope, every component other than
+    /// text counts as one.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this rope has no characters.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts text at `offset` with the style of the character before it,
+    /// or the one after it at the start.
+    ///
+    /// # Panics
+    /// When `offset` is past the end of the rope.
+    pub fn insert(&mut self, offset: usize, text: &str) {
+        let style = self
+            .style_at(offset.saturating_sub(1))
+            .cloned()
+            .unwrap_or_default();
+        self.insert_styled(offset, text, style);
+    }
+
+    /// Inserts text at `offset` with the given (resolved) style.
+    ///
+    /// # Panics
+    /// When `offset` is past the end of the rope.
+    pub fn insert_styled(&mut self, offset: usize, text: &str, style: Style) {
+        assert!(
+            offset <= self.len,
+            "offset {} is past the end of the rope ({})",
+            offset,
+            self.len
+        );
+        if text.is_empty() {
+            return;
+        }
+        let run = Run {
+            len: text.chars().count(),
+            style,
+            content: Content::Text(text.to_owned()),
+        };
+        self.len += run.len;
+        if self.chunks.is_empty() {
+            self.chunks = pack(Some(run));
+            return;
+        }
+
+        let mut index = 0;
+        let mut offset = offset;
+        while index + 1 < self.chunks.len() && offset > self.chunks[index].len {
+            offset -= self.chunks[index].len;
+            index += 1;
+        }
+        let chunk = &mut self.chunks[index];
+        chunk.insert(offset, run);
+        if chunk.runs.len() > MAX_RUNS || chunk.runs.iter().any(|run| run.len > MAX_RUN_LEN) {
+            let runs = std::mem::take(&mut chunk.runs);
+            self.chunks.splice(index..=index, pack(runs));
+        }
+    }
+
+    /// Removes the characters in `range`.
+    ///
+    /// # Panics
+    /// When the range is reversed or ends past the end of the rope.
+    pub fn delete(&mut self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range {:?} is out of bounds for the rope ({})",
+            range,
+            self.len
+        );
+        let mut start = 0;
+        for chunk in &mut self.chunks {
+            let end = start + chunk.len;
+            if end > range.start && start < range.end {
+                chunk.delete(range.start.max(start) - start, range.end.min(end) - start);
+            }
+            if end >= range.end {
+                break;
+            }
+            start = end;
+        }
+        self.chunks.retain(|chunk| !chunk.runs.is_empty());
+        self.len -= range.end - range.start;
+    }
+
+    /// The resolved style of the character at `offset`, if there is one.
+    pub fn style_at(&self, offset: usize) -> Option<&Style> {
+        let mut offset = offset;
+        for chunk in &self.chunks {
+            if offset < chunk.len {
+                for run in &chunk.runs {
+                    if offset < run.len {
+                        return Some(&run.style);
+                    }
+                    offset -= run.len;
+                }
+            }
+            offset -= chunk.len;
+        }
+        None
+    }
+
+    /// Turns this rope back into a component, the same shape as
+    /// [`Chat::from_segments()`] gives. Neighbouring text with the same
+    /// style is merged.
+    pub fn to_chat(&self) -> Chat {
+        let mut chat = Chat::text("");
+        let mut text: Option<(String, &Style)> = None;
+        for run in self.chunks.iter().flat_map(|chunk| &chunk.runs) {
+            match run.content {
+                Content::Text(ref value) => match text {
+                    Some((ref mut pending, style)) if *style == run.style => {
+                        pending.push_str(value)
+                    }
+                    _ => {
+                        chat.children.extend(text.take().map(text_child));
+                        text = Some((value.clone(), &run.style));
+                    }
+                },
+                Content::Component(ref kind) => {
+                    chat.children.extend(text.take().map(text_child));
+                    let mut child = Chat::component(kind.clone());
+                    child.style = run.style.clone();
+                    chat.children.push(child);
+                }
+            }
+        }
+        chat.children.extend(text.map(text_child));
+        chat
+    }
+}
+
+impl From<&Chat> for ChatRope {
+    fn from(chat: &Chat) -> Self {
+        let mut runs = Vec::new();
+        flatten(chat, &Style::new(), &mut runs);
+        ChatRope {
+            len: runs.iter().map(|run| run.len).sum(),
+            chunks: pack(runs),
+        }
+    }
+}
+
+impl Chunk {
+    fn insert(&mut self, offset: usize, run: Run) {
+        self.len += run.len;
+        let mut index = 0;
+        let mut offset = offset;
+        while index + 1 < self.runs.len() && offset > self.runs[index].len {
+            offset -= self.runs[index].len;
+            index += 1;
+        }
+        if self.runs[index].accepts(&run.style) {
+            self.runs[index].insert(offset, run);
+        } else if offset == self.runs[index].len
+            && self
+                .runs
+                .get(index + 1)
+                .is_some_and(|next| next.accepts(&run.style))
+        {
+            self.runs[index + 1].insert(0, run);
+        } else if offset == 0 {
+            self.runs.insert(index, run);
+        } else {
+            if offset < self.runs[index].len {
+                let tail = self.runs[index].split_off(offset);
+                self.runs.insert(index + 1, tail);
+            }
+            self.runs.insert(index + 1, run);
+        }
+    }
+
+    fn delete(&mut self, from: usize, to: usize) {
+        self.len -= to - from;
+        let mut start = 0;
+        let mut index = 0;
+        while index < self.runs.len() && start < to {
+            let end = start + self.runs[index].len;
+            if end > from {
+                let run = &mut self.runs[index];
+                run.delete(from.max(start) - start, to.min(end) - start);
+                if run.len == 0 {
+                    self.runs.remove(index);
+                    start = end;
+                    continue;
+                }
+            }
+            start = end;
+            index += 1;
+        }
+        // the text on both sides of the deleted part might have the same style now
+        let mut index = 1;
+        while index < self.runs.len() {
+            if let Content::Text(ref next) = self.runs[index].content {
+                if self.runs[index - 1].accepts(&self.runs[index].style) {
+                    let next = next.clone();
+                    let len = self.runs[index].len;
+                    let run = &mut self.runs[index - 1];
+                    if let Content::Text(ref mut text) = run.content {
+                        text.push_str(&next);
+                    }
+                    run.len += len;
+                    self.runs.remove(index);
+                    continue;
+                }
+            }
+            index += 1;
+        }
+    }
+}
+
+impl Run {
+    /// Whether text with the given style can be added to this run.
+    fn accepts(&self, style: &Style) -> bool {
+        matches!(self.content, Content::Text(_)) && self.style == *style
+    }
+
+    fn insert(&mut self, offset: usize, run: Run) {
+        if let (Content::Text(ref mut text), Content::Text(value)) =
+            (&mut self.content, run.content)
+        {
+            text.insert_str(byte_index(text, offset), &value);
+            self.len += run.len;
+        }
+    }
+
+    /// Splits this run at `offset`, returning the second half. Only text
+    /// is ever split, other components are a single character.
+    fn split_off(&mut self, offset: usize) -> Run {
+        let tail = match self.content {
+            Content::Text(ref mut text) => text.split_off(byte_index(text, offset)),
+            Content::Component(_) => unreachable!("components are a single character"),
+        };
+        let run = Run {
+            len: self.len - offset,
+            style: self.style.clone(),
+            content: Content::Text(tail),
+        };
+        self.len = offset;
+        run
+    }
+
+    fn delete(&mut self, from: usize, to: usize) {
+        if let Content::Text(ref mut text) = self.content {
+            let start = byte_index(text, from);
+            let end = byte_index(text, to);
+            text.replace_range(start..end, "");
+        }
+        self.len -= to - from;
+    }
+}
+
+fn byte_index(text: &str, offset: usize) -> usize {
+    text.char_indices()
+        .nth(offset)
+        .map_or(text.len(), |(index, _)| index)
+}
+
+fn text_child((text, style): (String, &Style)) -> Chat {
+    let mut child = Chat::text(text);
+    child.style = style.clone();
+    child
+}
+
+fn flatten(chat: &Chat, parent: &Style, runs: &mut Vec<Run>) {
+    let style = chat.style.inherit(parent);
+    match chat.kind {
+        ComponentKind::Text(ref text) => {
+            if !text.text.is_empty() {
+                runs.push(Run {
+                    len: text.text.chars().count(),
+                    style: style.clone(),
+                    content: Content::Text(text.text.to_string()),
+                });
+            }
+        }
+        ref kind => runs.push(Run {
+            len: 1,
+            style: style.clone(),
+            content: Content::Component(kind.clone()),
+        }),
+    }
+    for child in &chat.children {
+        flatten(child, &style, runs);
+    }
+}
+
+/// Puts runs into chunks that are half full, splitting long text.
+fn pack<I: IntoIterator<Item = Run>>(runs: I) -> Vec<Chunk> {
+    fn push(chunks: &mut Vec<Chunk>, chunk: &mut Chunk, run: Run) {
+        if chunk.runs.len() == MAX_RUNS / 2 {
+            chunks.push(std::mem::take(chunk));
+        }
+        chunk.len += run.len;
+        chunk.runs.push(run);
+    }
+
+    let mut chunks = Vec::new();
+    let mut chunk = Chunk::default();
+    for mut run in runs {
+        while run.len > MAX_RUN_LEN {
+            let tail = run.split_off(MAX_RUN_LEN);
+            push(&mut chunks, &mut chunk, run);
+            run = tail;
+        }
+        push(&mut chunks, &mut chunk, run);
+    }
+    if !chunk.runs.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Chat, ChatRope, Style, TextColor};
+
+    #[test]
+    fn round_trip() {
+        let chat = Chat::text("a")
+            .color(TextColor::Red)
+            .child(Chat::text("b").bold(true))
+            .child(Chat::translate("key"))
+            .child(Chat::text("c"));
+        let rope = ChatRope::from(&chat);
+        assert_eq!(4, rope.len());
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("a").color(TextColor::Red))
+                .child(Chat::text("b").color(TextColor::Red).bold(true))
+                .child(Chat::translate("key").color(TextColor::Red))
+                .child(Chat::text("c").color(TextColor::Red)),
+            rope.to_chat()
+        );
+        assert_eq!(Chat::text(""), ChatRope::new().to_chat());
+    }
+
+    #[test]
+    fn edits() {
+        let mut rope = ChatRope::from(&Chat::text("ab").child(Chat::text("cd").bold(true)));
+        // at a boundary the text before wins
+        rope.insert(2, "§");
+        rope.insert(0, "_");
+        rope.insert(6, "é");
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("_ab§"))
+                .child(Chat::text("cdé").bold(true)),
+            rope.to_chat()
+        );
+        rope.delete(2..5);
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("_a"))
+                .child(Chat::text("dé").bold(true)),
+            rope.to_chat()
+        );
+        rope.delete(1..3);
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("_"))
+                .child(Chat::text("é").bold(true)),
+            rope.to_chat()
+        );
+        rope.delete(0..2);
+        assert!(rope.is_empty());
+        rope.insert(0, "new");
+        assert_eq!(Chat::text("").child(Chat::text("new")), rope.to_chat());
+    }
+
+    #[test]
+    fn styled_inside() {
+        let mut rope = ChatRope::from(&Chat::text("abcd"));
+        let mut bold = Style::new();
+        bold.bold(true);
+        rope.insert_styled(2, "X", bold.clone());
+        assert_eq!(Some(&bold), rope.style_at(2));
+        assert_eq!(None, rope.style_at(5));
+        rope.delete(2..3);
+        assert_eq!(Chat::text("").child(Chat::text("abcd")), rope.to_chat());
+    }
+
+    #[test]
+    fn many_chunks() {
+        let mut rope = ChatRope::new();
+        let mut expected = String::new();
+        for i in 0..2000 {
+            let offset = (i * 7) % (expected.len() + 1);
+            let text = if i % 3 == 0 { "xy" } else { "z" };
+            let mut style = Style::new();
+            style.bold(i % 2 == 0);
+            rope.insert_styled(offset, text, style);
+            expected.insert_str(offset, text);
+        }
+        assert_eq!(expected.len(), rope.len());
+        rope.delete(100..1500);
+        expected.replace_range(100..1500, "");
+        assert_eq!(expected, rope.to_chat().to_plain());
+        assert!(rope.chunks.len() > 1);
+        assert!(rope
+            .chunks
+            .iter()
+            .all(|chunk| chunk.len == chunk.runs.iter().map(|run| run.len).sum::<usize>()));
+    }
+}
@@ -0,0 +1,341 @@
+use crate::resolve::for_each_mut;
+use crate::{Chat, ClickEvent, ComponentKind, HoverEvent, TextComponent, TranslationArg};
+
+/// What [`Chat::sanitize()`] allows in a component that comes from a client,
+/// e.g. a book, a renamed item or a sign.
+///
+/// The default policy only allows plain styled text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SanitizePolicy {
+    /// The deepest a component may be nested, the root is at depth 0.
+    pub max_depth: usize,
+    /// The maximum amount of components, including translation arguments.
+    pub max_components: usize,
+    /// Whether click events are allowed at all.
+    pub click_events: bool,
+    /// Whether `run_command` click events are allowed, which would make
+    /// other players run a command.
+    pub run_command: bool,
+    /// Whether `open_url` click events are allowed.
+    pub open_url: bool,
+    /// Whether hover events are allowed, their text is sanitized as well.
+    pub hover_events: bool,
+    /// How deep hover events may be nested in hover text, see
+    /// [`Chat::hover_depth()`]. Deeper hover events are removed.
+    pub max_hover_depth: usize,
+    pub fonts: bool,
+    pub obfuscated: bool,
+    /// Whether score and selector components are allowed, which reveal
+    /// information when the server resolves them. Otherwise they're replaced
+    /// by their raw text.
+    pub resolvable_components: bool,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self {
+            max_depth: 32,
+            max_components: 1024,
+            click_events: false,
+            run_command: false,
+            open_url: false,
+            hover_events: false,
+            max_hover_depth: 1,
+            fonts: false,
+            obfuscated: false,
+            resolvable_components: false,
+        }
+    }
+}
+
+/// What [`Chat::sanitize()`] removed from a component.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SanitizeReport {
+    pub click_events: usize,
+    pub hover_events: usize,
+    pub fonts: usize,
+    pub obfuscated: usize,
+    /// Score and selector components replaced by text.
+    pub resolvable_components: usize,
+    /// Components removed for being too deep or too many.
+    pub truncated_components: usize,
+}
+
+impl SanitizeReport {
+    /// Whether nothing was removed.
+    pub fn is_clean(&self) -> bool {
+        *self == SanitizeReport::default()
+    }
+}
+
+impl Chat {
+    /// Removes everything the policy doesn't allow from this component,
+    /// reporting what was removed.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, ClickEvent, SanitizePolicy};
+    ///
+    /// let mut chat = Chat::text("Click me!").click(Some(ClickEvent::command("/op Steve")));
+    /// let report = chat.sanitize(&SanitizePolicy::default());
+    /// assert_eq!(1, report.click_events);
+    /// assert_eq!(Chat::text("Click me!"), chat);
+    /// ```
+    pub fn sanitize(&mut self, policy: &SanitizePolicy) -> SanitizeReport {
+        let mut sanitizer = Sanitizer {
+            policy,
+            components: 1,
+            hover_depth: 0,
+            report: SanitizeReport::default(),
+        };
+        sanitizer.sanitize(self, 0);
+        sanitizer.report
+    }
+}
+
+impl Chat {
+    /// Removes the hover events inside hover text, which the client doesn't
+    /// show, returning how many were removed.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, HoverEvent};
+    ///
+    /// let tooltip = |chat: Chat| Some(HoverEvent::ShowText(Box::new(chat)));
+    /// let mut chat = Chat::text("a").hover(tooltip(Chat::text("b").hover(tooltip(Chat::text("c")))));
+    /// assert_eq!(1, chat.strip_nested_hovers());
+    /// assert_eq!(Chat::text("a").hover(tooltip(Chat::text("b"))), chat);
+    /// ```
+    pub fn strip_nested_hovers(&mut self) -> usize {
+        let mut removed = 0;
+        for_each_mut(self, &mut |chat| {
+            if let Some(text) = chat
+                .style
+                .hover_event
+                .as_mut()
+                .and_then(HoverEvent::text_mut)
+            {
+                for_each_mut(text, &mut |nested| {
+                    if nested.style.hover_event.take().is_some() {
+                        removed += 1;
+                    }
+                });
+            }
+        });
+        removed
+    }
+}
+
+struct Sanitizer<'a> {
+    policy: &'a SanitizePolicy,
+    /// The amount of components that are kept so far.
+    components: usize,
+    /// The amount of hover texts the current component is in.
+    hover_depth: usize,
+    report: SanitizeReport,
+}
+
+impl Sanitizer<'_> {
+    fn sanitize(&mut self, chat: &mut Chat, depth: usize) {
+        let policy = self.policy;
+        let style = &mut chat.style;
+        if let Some(ref click) = style.click_event {
+            let allowed = policy.click_events
+                && match click {
+                    ClickEvent::RunCommand(_) => policy.run_command,
+                    ClickEvent::OpenUrl(_) => policy.open_url,
+                    _ => true,
+                };
+            if !allowed {
+                style.click_event = None;
+                self.report.click_events += 1;
+            }
+        }
+        if style.hover_event.is_some()
+            && (!policy.hover_events || self.hover_depth >= policy.max_hover_depth)
+        {
+            style.hover_event = None;
+            self.report.hover_events += 1;
+        }
+        if style.font.is_some() && !policy.fonts {
+            style.font = None;
+            self.report.fonts += 1;
+        }
+        if style.obfuscated.is_some() && !policy.obfuscated {
+            style.obfuscated = None;
+            self.report.obfuscated += 1;
+        }
+        if let Some(text) = chat
+            .style
+            .hover_event
+            .as_mut()
+            .and_then(HoverEvent::text_mut)
+        {
+            self.hover_depth += 1;
+            self.children(std::slice::from_mut(text), depth);
+            self.hover_depth -= 1;
+        }
+
+        match chat.kind {
+            ComponentKind::Score(ref score) if !policy.resolvable_components => {
+                let text = score.value.clone().unwrap_or_else(|| score.name.clone());
+                chat.kind = TextComponent::new(text).into();
+                self.report.resolvable_components += 1;
+            }
+            ComponentKind::Selector(ref selector) if !policy.resolvable_components => {
+                chat.kind = TextComponent::new(selector.selector.clone()).into();
+                self.report.resolvable_components += 1;
+            }
+            ComponentKind::Translation(ref mut translation) => {
+                self.truncate_arguments(&mut translation.with, depth);
+            }
+            ref mut kind => {
+                if let Some(sep) = kind.separator_mut() {
+                    self.children(std::slice::from_mut(sep), depth);
+                }
+            }
+        }
+        self.truncate(&mut chat.children, depth);
+    }
+
+    /// Sanitizes the components nested in a component at `depth`, removing
+    /// the ones over the limits.
+    fn truncate(&mut self, children: &mut Vec<Chat>, depth: usize) {
+        let kept = self.kept(children.len(), depth);
+        for child in children.drain(kept..) {
+            self.report.truncated_components += count(&child);
+        }
+        self.children(children, depth);
+    }
+
+    /// Like [`Sanitizer::truncate`], a primitive argument counts as one component.
+    fn truncate_arguments(&mut self, arguments: &mut Vec<TranslationArg>, depth: usize) {
+        let kept = self.kept(arguments.len(), depth);
+        for argument in arguments.drain(kept..) {
+            self.report.truncated_components += argument.as_chat().map_or(1, count);
+        }
+        for argument in arguments {
+            self.components += 1;
+            if let Some(argument) = argument.as_chat_mut() {
+                self.sanitize(argument, depth + 1);
+            }
+        }
+    }
+
+    /// How many of `len` nested components at `depth` stay within the limits.
+    fn kept(&self, len: usize, depth: usize) -> usize {
+        if depth >= self.policy.max_depth {
+            0
+        } else {
+            len.min(self.policy.max_components.saturating_sub(self.components))
+        }
+    }
+
+    fn children(&mut self, children: &mut [Chat], depth: usize) {
+        for child in children {
+            self.components += 1;
+            self.sanitize(child, depth + 1);
+        }
+    }
+}
+
+/// The amount of components in a tree.
+fn count(chat: &Chat) -> usize {
+    let nested = match chat.kind {
+        ComponentKind::Translation(ref translation) => translation
+            .with
+            .iter()
+            .map(|argument| argument.as_chat().map_or(1, count))
+            .sum(),
+        _ => 0,
+    };
+    1 + nested + chat.children.iter().map(count).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TranslationComponent;
+
+    #[test]
+    fn events() {
+        let mut chat = Chat::text("Links")
+            .child(Chat::text("url").click(Some(ClickEvent::url("https://example.com"))))
+            .child(Chat::text("page").click(Some(ClickEvent::page(2u32))))
+            .child(Chat::text("op").click(Some(ClickEvent::command("/op Steve"))))
+            .child(
+                Chat::text("hover").hover(Some(HoverEvent::ShowText(Box::new(
+                    Chat::text("secret").font(Some("alt")),
+                )))),
+            );
+        let policy = SanitizePolicy {
+            click_events: true,
+            open_url: true,
+            hover_events: true,
+            ..Default::default()
+        };
+        let report = chat.sanitize(&policy);
+        assert_eq!(1, report.click_events);
+        assert_eq!(1, report.fonts);
+        assert_eq!(None, chat.children[2].style.click_event);
+        assert!(chat.children[0].style.click_event.is_some());
+        assert!(chat.sanitize(&policy).is_clean());
+    }
+
+    #[test]
+    fn nested_hovers() {
+        let tooltip = |chat: Chat| Some(HoverEvent::ShowText(Box::new(chat)));
+        let deep = || {
+            Chat::text("a").hover(tooltip(
+                Chat::text("b").hover(tooltip(Chat::text("c").hover(tooltip(Chat::text("d"))))),
+            ))
+        };
+        let policy = SanitizePolicy {
+            hover_events: true,
+            max_hover_depth: 2,
+            ..Default::default()
+        };
+        let mut chat = deep();
+        assert_eq!(3, chat.hover_depth());
+        assert_eq!(1, chat.sanitize(&policy).hover_events);
+        assert_eq!(2, chat.hover_depth());
+
+        let mut chat = deep();
+        assert_eq!(1, chat.strip_nested_hovers());
+        assert_eq!(1, chat.hover_depth());
+        assert_eq!(0, chat.strip_nested_hovers());
+    }
+
+    #[test]
+    fn limits() {
+        let mut chat = Chat::text("0").child(Chat::text("1").child(Chat::text("2")));
+        let policy = SanitizePolicy {
+            max_depth: 1,
+            ..Default::default()
+        };
+        assert_eq!(1, chat.sanitize(&policy).truncated_components);
+        assert_eq!(Chat::text("0").child(Chat::text("1")), chat);
+
+        let mut chat = Chat::component(
+            TranslationComponent::new("chat.type.text")
+                .argument(Chat::selector("@a", None))
+                .argument(Chat::text("1").child(Chat::text("2"))),
+        )
+        .child(Chat::text("3"));
+        let policy = SanitizePolicy {
+            max_components: 3,
+            ..Default::default()
+        };
+        let report = chat.sanitize(&policy);
+        assert_eq!(2, report.truncated_components);
+        assert_eq!(1, report.resolvable_components);
+        assert_eq!(
+            Chat::component(
+                TranslationComponent::new("chat.type.text")
+                    .argument(Chat::text("@a"))
+                    .argument(Chat::text("1"))
+            ),
+            chat
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::{Display, Formatter};
+
+use crate::legacy::to_legacy;
+use crate::{Chat, ProtocolVersion};
+
+/// The text of a team or an objective as it's sent to a client.
+///
+/// Before 1.13, teams and objectives use legacy formatted strings of limited
+/// length, since then they're chat components. The constructors convert a
+/// component to the form a version expects and check the old limits.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, ScoreboardText, TextColor, VERSION_1_8};
+///
+/// let prefix = Chat::text("[Admin] ").color(TextColor::Red);
+/// assert_eq!(
+///     ScoreboardText::Legacy("§c[Admin] ".into()),
+///     ScoreboardText::team_prefix(prefix.clone(), VERSION_1_8).unwrap()
+/// );
+/// assert_eq!(
+///     ScoreboardText::Chat(prefix.clone()),
+///     ScoreboardText::team_prefix(prefix, 393).unwrap()
+/// );
+/// ```
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ScoreboardText {
+    Legacy(String),
+    Chat(Chat),
+}
+
+impl ScoreboardText {
+    /// The maximum length of a team's prefix or suffix before 1.13.
+    pub const MAX_AFFIX_LENGTH: usize = 16;
+    /// The maximum length of a team's or an objective's display name before 1.13.
+    pub const MAX_DISPLAY_NAME_LENGTH: usize = 32;
+
+    /// The prefix in front of the names of a team's members.
+    pub fn team_prefix<C: Into<Chat>, V: Into<ProtocolVersion>>(
+        chat: C,
+        version: V,
+    ) -> Result<Self, ScoreboardErr> {
+        Self::limited(chat.into(), version.into(), Self::MAX_AFFIX_LENGTH)
+    }
+
+    /// The suffix after the names of a team's members.
+    pub fn team_suffix<C: Into<Chat>, V: Into<ProtocolVersion>>(
+        chat: C,
+        version: V,
+    ) -> Result<Self, ScoreboardErr> {
+        Self::limited(chat.into(), version.into(), Self::MAX_AFFIX_LENGTH)
+    }
+
+    /// The display name of a team.
+    pub fn team_display_name<C: Into<Chat>, V: Into<ProtocolVersion>>(
+        chat: C,
+        version: V,
+    ) -> Result<Self, ScoreboardErr> {
+        Self::limited(chat.into(), version.into(), Self::MAX_DISPLAY_NAME_LENGTH)
+    }
+
+    /// The display name of an objective, e.g. the title of the sidebar.
+    pub fn objective_display_name<C: Into<Chat>, V: Into<ProtocolVersion>>(
+        chat: C,
+        version: V,
+    ) -> Result<Self, ScoreboardErr> {
+        Self::limited(chat.into(), version.into(), Self::MAX_DISPLAY_NAME_LENGTH)
+    }
+
+    /// Scoreboard texts are components since 1.13.
+    fn limited(chat: Chat, version: ProtocolVersion, limit: usize) -> Result<Self, ScoreboardErr> {
+        if version >= ProtocolVersion::V1_13 {
+            return Ok(ScoreboardText::Chat(chat));
+        }
+        let legacy = to_legacy(&chat, "§", version);
+        let length = legacy.chars().count();
+        if length > limit {
+            return Err(ScoreboardErr::TooLong { length, limit });
+        }
+        Ok(ScoreboardText::Legacy(legacy))
+    }
+
+    /// Serialize this text to the string that's sent to the client, the legacy
+    /// text as is or the component as JSON.
+    #[cfg(feature = "serde")]
+    pub fn serialize_str<V: Into<ProtocolVersion>>(
+        &self,
+        version: V,
+    ) -> serde_json::Result<String> {
+        match self {
+            ScoreboardText::Legacy(text) => Ok(text.clone()),
+            ScoreboardText::Chat(chat) => chat.serialize_str(version),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScoreboardErr {
+    /// The length of the legacy text, including the formatting codes.
+    TooLong { length: usize, limit: usize },
+}
+
+impl Display for ScoreboardErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreboardErr::TooLong { length, limit } => write!(
+                f,
+                "the text is {} characters long, the limit is {}",
+                length, limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScoreboardErr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextColor;
+
+    #[test]
+    fn limits() {
+        // the color code counts towards the limit
+        let prefix = Chat::text("a".repeat(15)).color(TextColor::Red);
+        assert_eq!(
+            Err(ScoreboardErr::TooLong {
+                length: 17,
+                limit: 16
+            }),
+            ScoreboardText::team_prefix(prefix.clone(), 340)
+        );
+        assert!(ScoreboardText::team_display_name(prefix.clone(), 340).is_ok());
+        assert_eq!(
+            Ok(ScoreboardText::Chat(prefix.clone())),
+            ScoreboardText::team_suffix(prefix, 393)
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::SelectorComponent;
+
+/// A parsed target selector like `@e[type=zombie,limit=1]`.
+///
+/// Arguments are kept in their original order as `(name, value)` pairs, values
+/// aren't unescaped. Parsing checks the argument names and the values of the
+/// arguments with a fixed format, like `limit` and `sort`, following the
+/// syntax since 1.13. Displaying a selector writes it without whitespace.
+///
+/// # Example
+/// ```
+/// use mc_chat::{SelectorKind, TargetSelector};
+///
+/// let selector: TargetSelector = "@e[ type = zombie, limit=1 ]".parse().unwrap();
+/// assert_eq!(SelectorKind::AllEntities, selector.kind);
+/// assert_eq!(Some("zombie"), selector.get("type"));
+/// assert_eq!("@e[type=zombie,limit=1]", selector.to_string());
+///
+/// assert!("@e[limit=0]".parse::<TargetSelector>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TargetSelector {
+    pub kind: SelectorKind,
+    pub arguments: Vec<(String, String)>,
+}
+
+/// The variable of a [`TargetSelector`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SelectorKind {
+    /// `@p`
+    NearestPlayer,
+    /// `@r`
+    RandomPlayer,
+    /// `@a`
+    AllPlayers,
+    /// `@e`
+    AllEntities,
+    /// `@s`
+    Executor,
+    /// `@n`, since 1.21
+    NearestEntity,
+}
+
+impl SelectorKind {
+    /// The character following the `@`.
+    pub fn char(self) -> char {
+        match self {
+            SelectorKind::NearestPlayer => 'p',
+            SelectorKind::RandomPlayer => 'r',
+            SelectorKind::AllPlayers => 'a',
+            SelectorKind::AllEntities => 'e',
+            SelectorKind::Executor => 's',
+            SelectorKind::NearestEntity => 'n',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'p' => SelectorKind::NearestPlayer,
+            'r' => SelectorKind::RandomPlayer,
+            'a' => SelectorKind::AllPlayers,
+            'e' => SelectorKind::AllEntities,
+            's' => SelectorKind::Executor,
+            'n' => SelectorKind::NearestEntity,
+            _ => return None,
+        })
+    }
+}
+
+impl TargetSelector {
+    pub fn new(kind: SelectorKind) -> Self {
+        TargetSelector {
+            kind,
+            arguments: Vec::new(),
+        }
+    }
+
+    /// Adds an argument without checking it.
+    pub fn argument<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.arguments.push((name.into(), value.into()));
+        self
+    }
+
+    /// The value of the first argument with this name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.arguments
+            .iter()
+            .find(|(argument, _)| argument == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Checks the names and values of the arguments.
+    pub fn validate(&self) -> Result<(), SelectorErr> {
+        self.arguments
+            .iter()
+            .try_for_each(|(name, value)| validate_argument(name, value))
+    }
+}
+
+impl FromStr for TargetSelector {
+    type Err = SelectorErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let kind = match (chars.next(), chars.next()) {
+            (Some('@'), Some(c)) => SelectorKind::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| SelectorErr::InvalidKind(s.to_string()))?;
+        let mut selector = TargetSelector::new(kind);
+
+        let rest = chars.as_str();
+        if rest.is_empty() {
+            return Ok(selector);
+        }
+        let arguments = rest
+            .strip_prefix('[')
+            .ok_or_else(|| SelectorErr::InvalidKind(s.to_string()))?
+            .strip_suffix(']')
+            .ok_or(SelectorErr::Unclosed)?;
+        if arguments.trim().is_empty() {
+            return Ok(selector);
+        }
+        for argument in split_arguments(arguments)? {
+            let (name, value) = argument
+                .split_once('=')
+                .ok_or_else(|| SelectorErr::InvalidArgument(argument.trim().to_string()))?;
+            selector
+                .arguments
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+        selector.validate()?;
+        Ok(selector)
+    }
+}
+
+impl Display for TargetSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}", self.kind.char())?;
+        if !self.arguments.is_empty() {
+            f.write_str("[")?;
+            for (i, (name, value)) in self.arguments.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{}={}", name, value)?;
+            }
+            f.write_str("]")?;
+        }
+        Ok(())
+    }
+}
+
+impl SelectorComponent {
+    /// Parses the selector of this component, see [`TargetSelector`].
+    pub fn target(&self) -> Result<TargetSelector, SelectorErr> {
+        self.selector.parse()
+    }
+}
+
+/// Splits arguments on commas outside of quotes and brackets.
+fn split_arguments(arguments: &str) -> Result<Vec<&str>, SelectorErr> {
+    let mut result = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in arguments.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth = depth.checked_sub(1).ok_or(SelectorErr::Unclosed)?,
+            ',' if depth == 0 => {
+                result.push(&arguments[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quote.is_some() || depth > 0 {
+        return Err(SelectorErr::Unclosed);
+    }
+    result.push(&arguments[start..]);
+    Ok(result)
+}
+
+fn validate_argument(name: &str, value: &str) -> Result<(), SelectorErr> {
+    let invalid = || SelectorErr::InvalidValue {
+        argument: name.to_string(),
+        value: value.to_string(),
+    };
+    let valid = match name {
+        "x" | "y" | "z" | "dx" | "dy" | "dz" => value.parse::<f64>().is_ok(),
+        "distance" | "level" | "x_rotation" | "y_rotation" => is_range(value),
+        "limit" => matches!(value.parse::<u32>(), Ok(limit) if limit > 0),
+        "sort" => matches!(value, "nearest" | "furthest" | "random" | "arbitrary"),
+        "gamemode" => matches!(
+            value.strip_prefix('!').unwrap_or(value),
+            "survival" | "creative" | "adventure" | "spectator"
+        ),
+        "scores" | "advancements" => value.starts_with('{') && value.ends_with('}'),
+        "nbt" => {
+            let nbt = value.strip_prefix('!').unwrap_or(value);
+            nbt.starts_with('{') && nbt.ends_with('}')
+        }
+        // an empty tag or team matches entities without any
+        "tag" | "team" => true,
+        "name" | "type" | "predicate" => !value.strip_prefix('!').unwrap_or(value).is_empty(),
+        _ => return Err(SelectorErr::UnknownArgument(name.to_string())),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid())
+    }
+}
+
+/// An exact number or a range like `1..`, `..5` or `1.5..2`.
+fn is_range(value: &str) -> bool {
+    match value.split_once("..") {
+        Some((min, max)) => {
+            (!min.is_empty() || !max.is_empty())
+                && (min.is_empty() || min.parse::<f64>().is_ok())
+                && (max.is_empty() || max.parse::<f64>().is_ok())
+        }
+        None => value.parse::<f64>().is_ok(),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SelectorErr {
+    /// The selector doesn't start with `@` and a valid variable.
+    InvalidKind(String),
+    /// A bracket or quote isn't closed.
+    Unclosed,
+    /// An argument without a value.
+    InvalidArgument(String),
+    UnknownArgument(String),
+    InvalidValue {
+        argument: String,
+        value: String,
+    },
+}
+
+impl Display for SelectorErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorErr::InvalidKind(selector) => {
+                write!(f, "{:?} doesn't start with a selector variable", selector)
+            }
+            SelectorErr::Unclosed => write!(f, "unclosed bracket or quote"),
+            SelectorErr::InvalidArgument(argument) => {
+                write!(f, "expected a value for argument {:?}", argument)
+            }
+            SelectorErr::UnknownArgument(argument) => {
+                write!(f, "unknown argument {:?}", argument)
+            }
+            SelectorErr::InvalidValue { argument, value } => {
+                write!(f, "invalid value {:?} for argument {:?}", value, argument)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SelectorErr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let selector: TargetSelector =
+            r#"@a[name="a,b]",scores={kills=1..,deaths=..5},nbt=!{Tags:["x"]},gamemode=!creative]"#
+                .parse()
+                .unwrap();
+        assert_eq!(
+            vec![
+                ("name", r#""a,b]""#),
+                ("scores", "{kills=1..,deaths=..5}"),
+                ("nbt", r#"!{Tags:["x"]}"#),
+                ("gamemode", "!creative"),
+            ],
+            selector
+                .arguments
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Ok(TargetSelector::new(SelectorKind::Executor)),
+            "@s[]".parse()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |s: &str| s.parse::<TargetSelector>().unwrap_err();
+        assert_eq!(SelectorErr::InvalidKind("@x".to_string()), parse("@x"));
+        assert_eq!(
+            SelectorErr::InvalidKind("@ptype".to_string()),
+            parse("@ptype")
+        );
+        assert_eq!(SelectorErr::Unclosed, parse("@e[type=zombie"));
+        assert_eq!(SelectorErr::Unclosed, parse("@e[name=\"a]"));
+        assert_eq!(
+            SelectorErr::InvalidArgument("limit".to_string()),
+            parse("@e[limit]")
+        );
+        assert_eq!(
+            SelectorErr::UnknownArgument("r".to_string()),
+            parse("@e[r=10]")
+        );
+        assert_eq!(
+            SelectorErr::InvalidValue {
+                argument: "distance".to_string(),
+                value: "..".to_string()
+            },
+            parse("@e[distance=..]")
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::{Display, Formatter};
+
+use crate::metrics::{FontMetrics, SIGN_WIDTH};
+use crate::Chat;
+
+#[cfg(feature = "snbt")]
+mod serde_support;
+
+/// The text on one side of a sign: up to 4 lines of [`Chat`] components.
+///
+/// # Example
+/// ```
+/// use mc_chat::metrics::DefaultFont;
+/// use mc_chat::{Chat, SignText, TextColor};
+///
+/// let sign = SignText::new()
+///     .line(Chat::text("Welcome to").color(TextColor::Gold))
+///     .line(Chat::text("Spawn").bold(true))
+///     .glowing(true);
+/// assert!(sign.validate(&DefaultFont).is_ok());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SignText {
+    pub lines: Vec<Chat>,
+    /// Since **1.17**, ignored for older versions.
+    pub glowing: bool,
+}
+
+impl SignText {
+    /// The maximum amount of lines on a sign.
+    pub const MAX_LINES: usize = 4;
+
+    /// Creates a side of a sign without any text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a line below the others.
+    pub fn line<C: Into<Chat>>(mut self, line: C) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+
+    /// Change whether the text glows.
+    pub fn glowing(mut self, glowing: bool) -> Self {
+        self.glowing = glowing;
+        self
+    }
+
+    /// Checks the amount of lines and the width of every line,
+    /// measured with the given font, against the limits of a sign.
+    pub fn validate<M: FontMetrics + ?Sized>(&self, metrics: &M) -> Result<(), SignErr> {
+        if self.lines.len() > Self::MAX_LINES {
+            return Err(SignErr::TooManyLines(self.lines.len()));
+        }
+        for (line, chat) in self.lines.iter().enumerate() {
+            let width = metrics.chat_width(chat);
+            if width > SIGN_WIDTH {
+                return Err(SignErr::LineTooWide { line, width });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SignErr {
+    TooManyLines(usize),
+    /// The index of the line and its width in pixels.
+    LineTooWide {
+        line: usize,
+        width: u32,
+    },
+    #[cfg(feature = "snbt")]
+    Nbt(String),
+}
+
+impl Display for SignErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignErr::TooManyLines(lines) => write!(
+                f,
+                "the sign has {} lines, the limit is {}",
+                lines,
+                SignText::MAX_LINES
+            ),
+            SignErr::LineTooWide { line, width } => write!(
+                f,
+                "line {} is {} pixels wide, the limit is {}",
+                line + 1,
+                width,
+                SIGN_WIDTH
+            ),
+            #[cfg(feature = "snbt")]
+            SignErr::Nbt(err) => write!(f, "could not serialize the sign: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SignErr {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Monospace;
+
+    impl FontMetrics for Monospace {
+        fn advance(&self, _: char, bold: bool) -> u32 {
+            if bold {
+                7
+            } else {
+                6
+            }
+        }
+    }
+
+    #[test]
+    fn limits() {
+        // 15 characters of 6 pixels fill a line
+        let sign = SignText::new()
+            .line(Chat::text("a".repeat(15)))
+            .line(Chat::text("b"));
+        assert_eq!(Ok(()), sign.validate(&Monospace));
+
+        let sign = sign.line(Chat::text("").bold(true).child(Chat::text("c".repeat(13))));
+        assert_eq!(
+            Err(SignErr::LineTooWide { line: 2, width: 91 }),
+            sign.validate(&Monospace)
+        );
+
+        let sign = SignText::new()
+            .line("a")
+            .line("b")
+            .line("c")
+            .line("d")
+            .line("e");
+        assert_eq!(Err(SignErr::TooManyLines(5)), sign.validate(&Monospace));
+    }
+}
@@ -0,0 +1,178 @@
+use serde::Serialize;
+
+use crate::component::serde_support::{SerializeChat, SerializeCtx};
+use crate::{Chat, ProtocolVersion, SerializeOpts};
+
+use super::{SignErr, SignText};
+
+/// The text of a sign before 1.20.
+#[derive(Serialize)]
+struct SerializeLegacySign {
+    #[serde(rename = "Text1")]
+    text1: String,
+    #[serde(rename = "Text2")]
+    text2: String,
+    #[serde(rename = "Text3")]
+    text3: String,
+    #[serde(rename = "Text4")]
+    text4: String,
+    #[serde(rename = "GlowingText", skip_serializing_if = "Option::is_none")]
+    glowing: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct SerializeSign<P> {
+    front_text: SerializeSide<P>,
+    back_text: SerializeSide<P>,
+}
+
+#[derive(Serialize)]
+struct SerializeSide<P> {
+    messages: Vec<P>,
+    has_glowing_text: bool,
+}
+
+impl SignText {
+    /// Serialize a sign with this text on the front to SNBT.
+    ///
+    /// See [`SignText::to_nbt_with_back()`].
+    pub fn to_nbt<V: Into<ProtocolVersion>>(&self, version: V) -> Result<String, SignErr> {
+        self.to_nbt_with_back(&SignText::new(), version)
+    }
+
+    /// Serialize a sign with text on both sides to SNBT, the block entity
+    /// data of the sign. Missing lines are written as empty text.
+    ///
+    /// Before 1.20 (protocol 763), signs only have a front, written as `Text1`
+    /// to `Text4`. Since then, each side has its own `messages`. Since 1.21.5
+    /// (protocol 770), the lines are written as NBT instead of JSON.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, SignText};
+    ///
+    /// let front = SignText::new().line(Chat::text("Spawn"));
+    /// let back = SignText::new().line(Chat::text("Shops"));
+    /// let legacy = front.to_nbt(340).unwrap();
+    /// let modern = front.to_nbt_with_back(&back, 763).unwrap();
+    /// ```
+    pub fn to_nbt_with_back<V: Into<ProtocolVersion>>(
+        &self,
+        back: &SignText,
+        version: V,
+    ) -> Result<String, SignErr> {
+        let version = version.into();
+        for side in [self, back].iter() {
+            if side.lines.len() > Self::MAX_LINES {
+                return Err(SignErr::TooManyLines(side.lines.len()));
+            }
+        }
+        let empty = Chat::text("");
+        let result = if version < ProtocolVersion::V1_20 {
+            let mut lines = self
+                .padded_lines(&empty)
+                .map(|line| line.serialize_str(version))
+                .collect::<serde_json::Result<Vec<_>>>()
+                .map_err(|err| SignErr::Nbt(err.to_string()))?
+                .into_iter();
+            let mut next = || lines.next().unwrap_or_default();
+            fastsnbt::to_string(&SerializeLegacySign {
+                text1: next(),
+                text2: next(),
+                text3: next(),
+                text4: next(),
+                glowing: if version >= ProtocolVersion::V1_17 {
+                    Some(self.glowing)
+                } else {
+                    None
+                },
+            })
+        } else if version < ProtocolVersion::V1_21_5 {
+            let side = |side: &SignText| {
+                Ok(SerializeSide {
+                    messages: side
+                        .padded_lines(&empty)
+                        .map(|line| line.serialize_str(version))
+                        .collect::<serde_json::Result<Vec<_>>>()?,
+                    has_glowing_text: side.glowing,
+                })
+            };
+            let sign = side(self)
+                .and_then(|front_text| {
+                    Ok(SerializeSign {
+                        front_text,
+                        back_text: side(back)?,
+                    })
+                })
+                .map_err(|err: serde_json::Error| SignErr::Nbt(err.to_string()))?;
+            fastsnbt::to_string(&sign)
+        } else {
+            let opts = SerializeOpts::default();
+            let ctx = SerializeCtx {
+                version,
+                opts: &opts,
+            };
+            fastsnbt::to_string(&SerializeSign {
+                front_text: self.nbt_side(&empty, &ctx),
+                back_text: back.nbt_side(&empty, &ctx),
+            })
+        };
+        result.map_err(|err| SignErr::Nbt(err.to_string()))
+    }
+
+    fn nbt_side<'a>(
+        &'a self,
+        empty: &'a Chat,
+        ctx: &'a SerializeCtx<'a>,
+    ) -> SerializeSide<SerializeChat<'a>> {
+        SerializeSide {
+            messages: self
+                .padded_lines(empty)
+                .map(|line| SerializeChat::new(ctx, line))
+                .collect(),
+            has_glowing_text: self.glowing,
+        }
+    }
+
+    /// All 4 lines, the missing ones are `empty`.
+    fn padded_lines<'a>(&'a self, empty: &'a Chat) -> impl Iterator<Item = &'a Chat> {
+        self.lines
+            .iter()
+            .chain(std::iter::repeat(empty))
+            .take(Self::MAX_LINES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Chat, SignErr, SignText};
+
+    #[test]
+    fn nbt() {
+        let front = SignText::new().line(Chat::text("Spawn")).glowing(true);
+        let back = SignText::new().line(Chat::text("Shops"));
+
+        let legacy = front.to_nbt_with_back(&back, 340).unwrap();
+        assert!(legacy.contains("Text4"));
+        assert!(!legacy.contains("Shops"));
+        assert!(!legacy.contains("GlowingText"));
+        assert!(front.to_nbt(755).unwrap().contains("GlowingText"));
+
+        let modern = front.to_nbt_with_back(&back, 763).unwrap();
+        assert!(modern.contains("front_text"));
+        assert!(modern.contains("Shops"));
+        assert!(modern.contains(r#"\"text\""#));
+        assert!(!front.to_nbt(770).unwrap().contains(r#"\"text\""#));
+
+        let back = SignText::new()
+            .line("a")
+            .line("b")
+            .line("c")
+            .line("d")
+            .line("e");
+        assert_eq!(
+            Err(SignErr::TooManyLines(5)),
+            front.to_nbt_with_back(&back, 763)
+        );
+    }
+}
@@ -1,8 +1,13 @@
-#[cfg(feature = "serde")]
-use crate::{component::Chat, freeze::FrozenStr};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+use crate::freeze::{FrozenChat, FrozenStr};
+use crate::{Chat, ProtocolVersion};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "uuid")]
 use uuid::Uuid;
 #[cfg(feature = "serde")]
 pub(crate) mod serde_support;
@@ -38,17 +43,73 @@ pub struct Style {
     pub insertion: Option<FrozenStr>,
     /// This field is ignored for versions older than 1.16
     pub font: Option<FrozenStr>,
-    #[cfg_attr(feature = "serde", serde(rename = "clickEvent"))]
+    #[cfg_attr(feature = "serde", serde(rename = "clickEvent", alias = "click_event"))]
     pub click_event: Option<ClickEvent>,
-    #[cfg_attr(feature = "serde", serde(rename = "hoverEvent"))]
+    #[cfg_attr(feature = "serde", serde(rename = "hoverEvent", alias = "hover_event"))]
     pub hover_event: Option<HoverEvent>,
 }
 
 impl Style {
     /// Create a new style that inherits everything
     /// from the parent component.
-    pub fn new() -> Self {
-        Style::default()
+    ///
+    /// This is a `const fn`, so with the `with_*` methods a style can be
+    /// built for a `static`:
+    /// ```
+    /// use mc_chat::{Style, TextColor};
+    ///
+    /// static WARNING: Style = Style::new().with_color(TextColor::Red).with_bold(true);
+    /// assert_eq!(Some(true), WARNING.bold);
+    /// ```
+    pub const fn new() -> Self {
+        Style {
+            bold: None,
+            italic: None,
+            underlined: None,
+            strikethrough: None,
+            obfuscated: None,
+            color: None,
+            insertion: None,
+            font: None,
+            click_event: None,
+            hover_event: None,
+        }
+    }
+
+    /// Sets the text color, usable in a `const`.
+    pub const fn with_color(mut self, color: TextColor) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets whether the text is bold, usable in a `const`.
+    pub const fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Sets whether the text is italic, usable in a `const`.
+    pub const fn with_italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Sets whether the text is underlined, usable in a `const`.
+    pub const fn with_underlined(mut self, underlined: bool) -> Self {
+        self.underlined = Some(underlined);
+        self
+    }
+
+    /// Sets whether the text is struck through, usable in a `const`.
+    pub const fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+
+    /// Sets whether the text is obfuscated, usable in a `const`.
+    pub const fn with_obfuscated(mut self, obfuscated: bool) -> Self {
+        self.obfuscated = Some(obfuscated);
+        self
     }
 
     /// Change the text color.
@@ -72,28 +133,45 @@ impl Style {
         self
     }
 
-    pub fn bold(&mut self, bold: bool) -> &mut Self {
-        self.bold = Some(bold);
+    /// Change whether the text is bold.
+    ///
+    /// Like [`Style::color()`], this takes a [`bool`] or an [`Option<bool>`]:
+    /// `false` explicitly turns off the parent's bold text while [`None`]
+    /// inherits it again. The other decorations work the same way.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::Style;
+    ///
+    /// let mut style = Style::new();
+    /// style.bold(false);
+    /// assert_eq!(Some(false), style.bold);
+    ///
+    /// style.bold(None);
+    /// assert_eq!(None, style.bold);
+    /// ```
+    pub fn bold<I: Into<Option<bool>>>(&mut self, bold: I) -> &mut Self {
+        self.bold = bold.into();
         self
     }
 
-    pub fn italic(&mut self, italic: bool) -> &mut Self {
-        self.italic = Some(italic);
+    pub fn italic<I: Into<Option<bool>>>(&mut self, italic: I) -> &mut Self {
+        self.italic = italic.into();
         self
     }
 
-    pub fn underlined(&mut self, underlined: bool) -> &mut Self {
-        self.underlined = Some(underlined);
+    pub fn underlined<I: Into<Option<bool>>>(&mut self, underlined: I) -> &mut Self {
+        self.underlined = underlined.into();
         self
     }
 
-    pub fn strikethrough(&mut self, strikethrough: bool) -> &mut Self {
-        self.strikethrough = Some(strikethrough);
+    pub fn strikethrough<I: Into<Option<bool>>>(&mut self, strikethrough: I) -> &mut Self {
+        self.strikethrough = strikethrough.into();
         self
     }
 
-    pub fn obfuscated(&mut self, obfuscated: bool) -> &mut Self {
-        self.obfuscated = Some(obfuscated);
+    pub fn obfuscated<I: Into<Option<bool>>>(&mut self, obfuscated: I) -> &mut Self {
+        self.obfuscated = obfuscated.into();
         self
     }
 
@@ -116,6 +194,78 @@ impl Style {
         self.hover_event = hover_event;
         self
     }
+
+    /// Whether this style has no settings and inherits everything from
+    /// the parent component.
+    pub fn is_plain(&self) -> bool {
+        self.bold.is_none()
+            && self.italic.is_none()
+            && self.underlined.is_none()
+            && self.strikethrough.is_none()
+            && self.obfuscated.is_none()
+            && self.color.is_none()
+            && self.insertion.is_none()
+            && self.font.is_none()
+            && self.click_event.is_none()
+            && self.hover_event.is_none()
+    }
+
+    /// Whether this style serializes to nothing for the given version,
+    /// because it's plain or its settings don't exist in that version.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Style, TextColor};
+    ///
+    /// let mut style = Style::new();
+    /// style.color(TextColor::from_rgb(0xff, 0x80, 0x00)).font(Some("minecraft:uniform"));
+    /// assert!(!style.is_plain());
+    /// // hex colors and fonts are since 1.16
+    /// assert!(style.is_empty(47));
+    /// assert!(!style.is_empty(735));
+    /// ```
+    pub fn is_empty<V: Into<ProtocolVersion>>(&self, version: V) -> bool {
+        let version = version.into();
+        self.bold.is_none()
+            && self.italic.is_none()
+            && self.underlined.is_none()
+            && self.strikethrough.is_none()
+            && self.obfuscated.is_none()
+            && match self.color {
+                Some(TextColor::Custom(_)) => !version.supports_hex_colors(),
+                ref color => color.is_none(),
+            }
+            && (self.insertion.is_none() || !version.supports_insertion())
+            && (self.font.is_none() || !version.supports_fonts())
+            && self
+                .click_event
+                .as_ref()
+                .is_none_or(|click_event| !click_event.supported(version))
+            && self.hover_event.is_none()
+    }
+
+    /// Fills in every setting this style inherits from the (resolved)
+    /// style of its parent.
+    pub(crate) fn inherit(&self, parent: &Style) -> Style {
+        Style {
+            bold: self.bold.or(parent.bold),
+            italic: self.italic.or(parent.italic),
+            underlined: self.underlined.or(parent.underlined),
+            strikethrough: self.strikethrough.or(parent.strikethrough),
+            obfuscated: self.obfuscated.or(parent.obfuscated),
+            color: self.color.clone().or_else(|| parent.color.clone()),
+            insertion: self.insertion.clone().or_else(|| parent.insertion.clone()),
+            font: self.font.clone().or_else(|| parent.font.clone()),
+            click_event: self
+                .click_event
+                .clone()
+                .or_else(|| parent.click_event.clone()),
+            hover_event: self
+                .hover_event
+                .clone()
+                .or_else(|| parent.hover_event.clone()),
+        }
+    }
 }
 
 /// The different colors a [`Chat`] component can have.
@@ -140,19 +290,283 @@ pub enum TextColor {
     Pink,
     Yellow,
     White,
+    /// A `#RRGGBB` color.
+    ///
     /// This field is ignored for versions older than 1.16.
     ///
-    /// See [`TextColor::custom()`].
-    Custom(FrozenStr),
+    /// See [`TextColor::from_hex()`] and [`TextColor::from_rgb()`].
+    Custom(Rgb),
     Reset,
 }
 
 impl TextColor {
+    /// A custom color from its `#RRGGBB` hex code, the digits are case
+    /// insensitive.
+    ///
+    /// Before 0.4.0 this stored any string, now that custom colors are
+    /// [`Rgb`] values a string that isn't a hex code or a color name like
+    /// `red` becomes [`TextColor::Reset`] (and is logged with the `log`
+    /// feature). Use [`TextColor::try_custom()`] to handle invalid colors.
+    #[deprecated(
+        since = "0.4.0",
+        note = "invalid colors become `TextColor::Reset`, use `TextColor::try_custom()` or `TextColor::from_hex()`"
+    )]
     pub fn custom<T: Into<FrozenStr>>(color: T) -> TextColor {
-        TextColor::Custom(color.into())
+        let color = color.into();
+        match TextColor::try_from(&*color) {
+            Ok(color) => color,
+            Err(_error) => {
+                #[cfg(feature = "log")]
+                log::warn!("invalid color {:?}: {}", color, _error);
+                TextColor::Reset
+            }
+        }
+    }
+
+    /// Parses a `#RRGGBB` hex code, returning why `color` isn't one.
+    ///
+    /// The same as [`TextColor::from_hex()`] for any string type.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{HexColorErr, TextColor};
+    ///
+    /// assert_eq!(Ok(TextColor::from_rgb(0xff, 0x80, 0x00)), TextColor::try_custom("#ff8000"));
+    /// assert_eq!(Err(HexColorErr::WrongLength(7)), TextColor::try_custom("#ff80000"));
+    /// ```
+    pub fn try_custom<T: AsRef<str>>(color: T) -> Result<TextColor, HexColorErr> {
+        TextColor::from_hex(color.as_ref())
+    }
+
+    /// Parses a `#RRGGBB` hex code, the digits are case insensitive.
+    ///
+    /// This works in constants too.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{HexColorErr, TextColor};
+    ///
+    /// const ORANGE: TextColor = match TextColor::from_hex("#ff8000") {
+    ///     Ok(color) => color,
+    ///     Err(_) => panic!("not a color"),
+    /// };
+    /// assert_eq!(TextColor::from_rgb(0xff, 0x80, 0x00), ORANGE);
+    /// assert_eq!(Err(HexColorErr::MissingHash), TextColor::from_hex("ff8000"));
+    /// assert_eq!(Err(HexColorErr::InvalidDigit(6)), TextColor::from_hex("#ff800g"));
+    /// ```
+    pub const fn from_hex(color: &str) -> Result<TextColor, HexColorErr> {
+        let bytes = color.as_bytes();
+        if bytes.is_empty() || bytes[0] != b'#' {
+            return Err(HexColorErr::MissingHash);
+        }
+        if bytes.len() != 7 {
+            return Err(HexColorErr::WrongLength(bytes.len() - 1));
+        }
+        let mut rgb = 0;
+        let mut i = 1;
+        while i < bytes.len() {
+            let digit = match bytes[i] {
+                b'0'..=b'9' => bytes[i] - b'0',
+                b'a'..=b'f' => bytes[i] - b'a' + 10,
+                b'A'..=b'F' => bytes[i] - b'A' + 10,
+                _ => return Err(HexColorErr::InvalidDigit(i)),
+            };
+            rgb = rgb << 4 | digit as u32;
+            i += 1;
+        }
+        Ok(TextColor::Custom(Rgb(rgb)))
+    }
+
+    /// A custom color from its red, green and blue values.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Rgb, TextColor};
+    ///
+    /// const ORANGE: TextColor = TextColor::from_rgb(0xff, 0x80, 0x00);
+    /// assert_eq!(TextColor::Custom(Rgb::new(0xff, 0x80, 0x00)), ORANGE);
+    /// ```
+    pub const fn from_rgb(r: u8, g: u8, b: u8) -> TextColor {
+        TextColor::Custom(Rgb::new(r, g, b))
+    }
+
+    /// The 16 named colors with their red, green and blue values, in the
+    /// order of their legacy codes `0` to `f`.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::TextColor;
+    ///
+    /// let colors = TextColor::named_colors();
+    /// assert_eq!(16, colors.len());
+    /// assert_eq!((TextColor::Gold, [0xff, 0xaa, 0x00]), colors[6]);
+    /// ```
+    pub fn named_colors() -> &'static [(TextColor, [u8; 3]); 16] {
+        &NAMED_COLORS
+    }
+
+    /// The color as red, green and blue, unless it's [`TextColor::Reset`].
+    pub(crate) fn rgb(&self) -> Option<[u8; 3]> {
+        match self {
+            TextColor::Custom(rgb) => Some(rgb.components()),
+            TextColor::Reset => None,
+            named => NAMED_COLORS
+                .iter()
+                .find(|(color, _)| color == named)
+                .map(|(_, rgb)| *rgb),
+        }
+    }
+}
+
+/// The red, green and blue of a [`TextColor::Custom`] color.
+///
+/// It always fits in `0xRRGGBB`, so every value can be written as a
+/// `#RRGGBB` hex code, which is what it displays as.
+///
+/// # Example
+/// ```
+/// use mc_chat::Rgb;
+///
+/// let orange = Rgb::new(0xff, 0x80, 0x00);
+/// assert_eq!(Some(orange), Rgb::from_u32(0xff8000));
+/// assert_eq!(None, Rgb::from_u32(0x1ff8000));
+/// assert_eq!("#ff8000", orange.to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(u32);
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb((r as u32) << 16 | (g as u32) << 8 | b as u32)
+    }
+
+    /// A color packed as `0xRRGGBB`, `None` if it's above `0xffffff`.
+    pub const fn from_u32(rgb: u32) -> Option<Rgb> {
+        if rgb > 0xff_ffff {
+            None
+        } else {
+            Some(Rgb(rgb))
+        }
+    }
+
+    /// The color packed as `0xRRGGBB`.
+    pub const fn to_u32(self) -> u32 {
+        self.0
+    }
+
+    /// The red, green and blue values.
+    pub const fn components(self) -> [u8; 3] {
+        [(self.0 >> 16) as u8, (self.0 >> 8) as u8, self.0 as u8]
+    }
+}
+
+impl Display for Rgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:06x}", self.0)
+    }
+}
+
+/// The old name of [`TextColor`].
+///
+/// To migrate, rename `ChatColor` to `TextColor` and replace the colors
+/// that were named like vanilla: `Aqua` is [`TextColor::Cyan`], `DarkAqua`
+/// is [`TextColor::DarkCyan`], `DarkPurple` is [`TextColor::Purple`] and
+/// `LightPurple` is [`TextColor::Pink`]. Since 0.4.0 the old names still
+/// work, with a deprecation warning.
+#[deprecated(since = "0.4.0", note = "renamed to `TextColor`")]
+pub type ChatColor = TextColor;
+
+/// The vanilla names of the colors that are named differently here,
+/// see [`ChatColor`].
+#[allow(non_upper_case_globals)]
+impl TextColor {
+    #[deprecated(since = "0.4.0", note = "renamed to `TextColor::Cyan`")]
+    pub const Aqua: TextColor = TextColor::Cyan;
+    #[deprecated(since = "0.4.0", note = "renamed to `TextColor::DarkCyan`")]
+    pub const DarkAqua: TextColor = TextColor::DarkCyan;
+    #[deprecated(since = "0.4.0", note = "renamed to `TextColor::Purple`")]
+    pub const DarkPurple: TextColor = TextColor::Purple;
+    #[deprecated(since = "0.4.0", note = "renamed to `TextColor::Pink`")]
+    pub const LightPurple: TextColor = TextColor::Pink;
+}
+
+static NAMED_COLORS: [(TextColor, [u8; 3]); 16] = [
+    (TextColor::Black, [0x00, 0x00, 0x00]),
+    (TextColor::DarkBlue, [0x00, 0x00, 0xAA]),
+    (TextColor::DarkGreen, [0x00, 0xAA, 0x00]),
+    (TextColor::DarkCyan, [0x00, 0xAA, 0xAA]),
+    (TextColor::DarkRed, [0xAA, 0x00, 0x00]),
+    (TextColor::Purple, [0xAA, 0x00, 0xAA]),
+    (TextColor::Gold, [0xFF, 0xAA, 0x00]),
+    (TextColor::Gray, [0xAA, 0xAA, 0xAA]),
+    (TextColor::DarkGray, [0x55, 0x55, 0x55]),
+    (TextColor::Blue, [0x55, 0x55, 0xFF]),
+    (TextColor::Green, [0x55, 0xFF, 0x55]),
+    (TextColor::Cyan, [0x55, 0xFF, 0xFF]),
+    (TextColor::Red, [0xFF, 0x55, 0x55]),
+    (TextColor::Pink, [0xFF, 0x55, 0xFF]),
+    (TextColor::Yellow, [0xFF, 0xFF, 0x55]),
+    (TextColor::White, [0xFF, 0xFF, 0xFF]),
+];
+
+/// Parses a color the way it's written in a component: its vanilla name,
+/// e.g. `dark_aqua`, or a `#RRGGBB` hex code.
+///
+/// Anything that isn't a name is parsed by [`TextColor::try_custom()`].
+impl TryFrom<&str> for TextColor {
+    type Error = HexColorErr;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "black" => TextColor::Black,
+            "dark_blue" => TextColor::DarkBlue,
+            "dark_green" => TextColor::DarkGreen,
+            "dark_aqua" => TextColor::DarkCyan,
+            "dark_red" => TextColor::DarkRed,
+            "dark_purple" => TextColor::Purple,
+            "gold" => TextColor::Gold,
+            "gray" => TextColor::Gray,
+            "dark_gray" => TextColor::DarkGray,
+            "blue" => TextColor::Blue,
+            "green" => TextColor::Green,
+            "aqua" => TextColor::Cyan,
+            "red" => TextColor::Red,
+            "light_purple" => TextColor::Pink,
+            "yellow" => TextColor::Yellow,
+            "white" => TextColor::White,
+            "reset" => TextColor::Reset,
+            custom => TextColor::try_custom(custom)?,
+        })
     }
 }
 
+/// Why a hex color code is invalid, see [`TextColor::from_hex()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexColorErr {
+    /// The code doesn't start with `#`.
+    MissingHash,
+    /// The number of digits after the `#` if it isn't 6.
+    WrongLength(usize),
+    /// The byte index of a character that isn't a hex digit.
+    InvalidDigit(usize),
+}
+
+impl Display for HexColorErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HexColorErr::MissingHash => write!(f, "Hex color doesn't start with '#'"),
+            HexColorErr::WrongLength(len) => {
+                write!(f, "Hex color has {} digits instead of 6", len)
+            }
+            HexColorErr::InvalidDigit(index) => {
+                write!(f, "Hex color has an invalid digit at index {}", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexColorErr {}
+
 /// A ClickEvent useful in a chat message or book.
 /// TODO: Discuss feature gated `open_file` option
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -165,6 +579,18 @@ pub enum ClickEvent {
     ChangePage(u32),
     /// This field is ignored for versions older than 1.15.
     CopyToClipBoard(FrozenStr),
+    /// Sends a custom payload to the server, like
+    /// `minecraft:custom_click_action`. The payload is SNBT.
+    ///
+    /// This field is ignored for versions older than 1.21.6.
+    Custom {
+        id: FrozenStr,
+        payload: Option<FrozenStr>,
+    },
+    /// Opens the dialog with the given id.
+    ///
+    /// This field is ignored for versions older than 1.21.6.
+    ShowDialog(FrozenStr),
 }
 
 impl ClickEvent {
@@ -172,6 +598,38 @@ impl ClickEvent {
         Self::OpenUrl(url.into())
     }
 
+    /// Like [`ClickEvent::url()`], but percent-encodes every character the
+    /// client doesn't accept in a URL, like spaces and non-ASCII characters.
+    /// Characters that are already encoded stay as they are.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::ClickEvent;
+    ///
+    /// assert_eq!(
+    ///     ClickEvent::url("https://example.com/a%20b/c%20d/%C3%A9t%C3%A9?q=100%25"),
+    ///     ClickEvent::url_encoded("https://example.com/a b/c%20d/été?q=100%")
+    /// );
+    /// ```
+    pub fn url_encoded(url: &str) -> Self {
+        let mut encoded = String::with_capacity(url.len());
+        for (index, c) in url.char_indices() {
+            let escaped = c == '%'
+                && url
+                    .get(index + 1..index + 3)
+                    .is_some_and(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()));
+            if escaped || (c.is_ascii_graphic() && !"%\"<>\\^`{|}".contains(c)) {
+                encoded.push(c);
+            } else {
+                let mut bytes = [0; 4];
+                for byte in c.encode_utf8(&mut bytes).bytes() {
+                    encoded.push_str(&format!("%{:02X}", byte));
+                }
+            }
+        }
+        Self::OpenUrl(encoded.into())
+    }
+
     pub fn command<T: Into<FrozenStr>>(cmd: T) -> Self {
         Self::RunCommand(cmd.into())
     }
@@ -184,9 +642,50 @@ impl ClickEvent {
         Self::ChangePage(page.into())
     }
 
+    /// Goes to a page of a book with `max` pages, if it exists.
+    ///
+    /// Pages are numbered from 1.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::ClickEvent;
+    ///
+    /// assert_eq!(Some(ClickEvent::ChangePage(3)), ClickEvent::try_page(3u32, 3));
+    /// assert_eq!(None, ClickEvent::try_page(0u32, 3));
+    /// assert_eq!(None, ClickEvent::try_page(4u32, 3));
+    /// ```
+    pub fn try_page<T: Into<u32>>(page: T, max: u32) -> Option<Self> {
+        let page = page.into();
+        if (1..=max).contains(&page) {
+            Some(Self::ChangePage(page))
+        } else {
+            None
+        }
+    }
+
     pub fn clipboard<T: Into<FrozenStr>>(str: T) -> Self {
         Self::CopyToClipBoard(str.into())
     }
+
+    pub fn custom<T: Into<FrozenStr>, U: Into<FrozenStr>>(id: T, payload: Option<U>) -> Self {
+        Self::Custom {
+            id: id.into(),
+            payload: payload.map(Into::into),
+        }
+    }
+
+    pub fn dialog<T: Into<FrozenStr>>(id: T) -> Self {
+        Self::ShowDialog(id.into())
+    }
+
+    /// Whether a version knows this click event, older versions ignore it.
+    pub(crate) fn supported(&self, version: ProtocolVersion) -> bool {
+        match self {
+            ClickEvent::CopyToClipBoard(_) => version.supports_clipboard(),
+            ClickEvent::Custom { .. } | ClickEvent::ShowDialog(_) => version.supports_dialogs(),
+            _ => true,
+        }
+    }
 }
 
 /// A HoverEvent useful in a chat message or book.
@@ -195,11 +694,121 @@ impl ClickEvent {
 /// It is highly recommended to provide the data for deserialization
 /// with the action first and then the value/contents (based on the version).
 /// **Doing otherwise will result in an extra allocation.**
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+///
+/// # Sharing text
+/// A tooltip that's repeated for many components, like the items of a list,
+/// can be frozen and shared between them with [`HoverEvent::ShowSharedText`]
+/// instead of cloning it for every component. It's serialized and compared
+/// like [`HoverEvent::ShowText`].
+///
+/// ```
+/// use mc_chat::{Chat, HoverEvent};
+///
+/// let tooltip = Chat::text("Click to teleport").freeze();
+/// let places = ["Spawn", "Shop", "Arena"]
+///     .iter()
+///     .map(|place| Chat::text(*place).hover(Some(HoverEvent::ShowSharedText(tooltip.clone()))))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     Some(HoverEvent::ShowText(Box::new(Chat::text("Click to teleport")))),
+///     places[2].style.hover_event
+/// );
+/// ```
+#[derive(Clone, Debug)]
 pub enum HoverEvent {
     ShowText(Box<Chat>),
+    ShowSharedText(FrozenChat),
     ShowItem(ItemStack),
     ShowEntity(EntityTooltip),
+    /// Shows an achievement by its key, like `achievement.openInventory`.
+    ///
+    /// Achievements were replaced by advancements in 1.12 (protocol 335),
+    /// newer versions get a [`HoverEvent::ShowText`] with the key as
+    /// translation instead.
+    ShowAchievement(FrozenStr),
+}
+
+impl HoverEvent {
+    /// Shows the tooltip of an item without a tag, see [`ItemStack::simple()`].
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{HoverEvent, ItemStack};
+    ///
+    /// assert_eq!(
+    ///     HoverEvent::ShowItem(ItemStack::new("minecraft:diamond_sword", Some(1), Option::<&str>::None)),
+    ///     HoverEvent::item_simple("minecraft:diamond_sword", 1)
+    /// );
+    /// ```
+    pub fn item_simple<I: Into<FrozenStr>>(id: I, count: i32) -> Self {
+        HoverEvent::ShowItem(ItemStack {
+            count: Some(count),
+            ..ItemStack::simple(id)
+        })
+    }
+
+    /// The text of a `show_text` event, shared or not.
+    pub fn text(&self) -> Option<&Chat> {
+        match self {
+            HoverEvent::ShowText(text) => Some(text),
+            HoverEvent::ShowSharedText(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The text of a `show_text` event to change. Shared text is turned into
+    /// [`HoverEvent::ShowText`] first, cloning it if it's still shared.
+    pub fn text_mut(&mut self) -> Option<&mut Chat> {
+        if let HoverEvent::ShowSharedText(_) = self {
+            let placeholder = HoverEvent::ShowText(Box::new(Chat::text("")));
+            if let HoverEvent::ShowSharedText(text) = std::mem::replace(self, placeholder) {
+                *self = HoverEvent::ShowText(Box::new(text.thaw()));
+            }
+        }
+        match self {
+            HoverEvent::ShowText(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for HoverEvent {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HoverEvent::ShowItem(item), HoverEvent::ShowItem(other)) => item == other,
+            (HoverEvent::ShowEntity(entity), HoverEvent::ShowEntity(other)) => entity == other,
+            (HoverEvent::ShowAchievement(key), HoverEvent::ShowAchievement(other)) => key == other,
+            _ => match (self.text(), other.text()) {
+                (Some(text), Some(other)) => text == other,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for HoverEvent {}
+
+impl Hash for HoverEvent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            HoverEvent::ShowText(_) | HoverEvent::ShowSharedText(_) => {
+                0.hash(state);
+                self.text().hash(state);
+            }
+            HoverEvent::ShowItem(item) => {
+                1.hash(state);
+                item.hash(state);
+            }
+            HoverEvent::ShowEntity(entity) => {
+                2.hash(state);
+                entity.hash(state);
+            }
+            HoverEvent::ShowAchievement(key) => {
+                3.hash(state);
+                key.hash(state);
+            }
+        }
+    }
 }
 
 /// Chat data from an itemstack.
@@ -217,6 +826,9 @@ pub struct ItemStack {
         )
     )]
     pub count: Option<i32>,
+    /// The SNBT tag of the item. Since 1.21.5 hover events carry the data
+    /// components as JSON instead, so there the tag is only written when it
+    /// is a JSON object, like the tags read from such events.
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     #[cfg_attr(
         feature = "serde",
@@ -237,8 +849,217 @@ impl ItemStack {
             tag: tag.map(|t| t.into()),
         }
     }
+
+    /// A single item without a tag.
+    ///
+    /// The count is always set: before 1.16 the item of a hover event is
+    /// read from SNBT, where an item without a `Count` is empty and doesn't
+    /// show a tooltip. Since then it defaults to 1.
+    pub fn simple<I: Into<FrozenStr>>(id: I) -> Self {
+        Self {
+            id: id.into(),
+            count: Some(1),
+            tag: None,
+        }
+    }
+
+    /// A builder for an item with the given id.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::ItemStack;
+    ///
+    /// let item = ItemStack::builder("minecraft:diamond_sword")
+    ///     .count(3)
+    ///     .tag("{Unbreakable:1b}")
+    ///     .enchant("minecraft:sharpness", 5)
+    ///     .build();
+    /// assert_eq!(
+    ///     ItemStack::new(
+    ///         "minecraft:diamond_sword",
+    ///         Some(3),
+    ///         Some("{Unbreakable:1b,Enchantments:[{id:\"minecraft:sharpness\",lvl:5s}]}"),
+    ///     ),
+    ///     item
+    /// );
+    /// ```
+    pub fn builder<I: Into<FrozenStr>>(id: I) -> ItemStackBuilder {
+        ItemStackBuilder {
+            id: id.into(),
+            count: None,
+            tag: None,
+            enchantments: Vec::new(),
+        }
+    }
+
+    /// The `/give` command for this item, giving it to the player running it.
+    ///
+    /// Before 1.13 (protocol 393), the tag follows the amount and a data value
+    /// of 0. Until 1.20.5 (protocol 766) it follows the id. Since then, items
+    /// have components instead of a tag: the tag has to be a compound of
+    /// components with their namespace, like `minecraft:unbreakable`, and is
+    /// written as `id[component=value,...]`. A tag with other keys, like the
+    /// NBT `Unbreakable` of older versions, has no command and returns
+    /// [`None`].
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::ItemStack;
+    ///
+    /// let item = ItemStack::new("minecraft:diamond_sword", Some(1), Some("{Unbreakable:1b}"));
+    /// assert_eq!(
+    ///     Some("/give @s minecraft:diamond_sword 1 0 {Unbreakable:1b}"),
+    ///     item.to_give_command(340).as_deref()
+    /// );
+    /// assert_eq!(
+    ///     Some("/give @s minecraft:diamond_sword{Unbreakable:1b} 1"),
+    ///     item.to_give_command(393).as_deref()
+    /// );
+    /// assert_eq!(None, item.to_give_command(766));
+    ///
+    /// let item = ItemStack::new("minecraft:diamond_sword", None, Some("{\"minecraft:unbreakable\":{}}"));
+    /// assert_eq!(
+    ///     Some("/give @s minecraft:diamond_sword[minecraft:unbreakable={}]"),
+    ///     item.to_give_command(766).as_deref()
+    /// );
+    /// ```
+    pub fn to_give_command<V: Into<ProtocolVersion>>(&self, version: V) -> Option<String> {
+        // `@s` is since 1.11, before that the nearest player is the one
+        // running the command
+        const SELF_SELECTOR: ProtocolVersion = ProtocolVersion::new(315);
+
+        let version = version.into();
+        let target = if version < SELF_SELECTOR { "@p" } else { "@s" };
+        let mut command = format!("/give {} {}", target, self.id);
+        if version < ProtocolVersion::V1_13 {
+            if self.count.is_some() || self.tag.is_some() {
+                command.push_str(&format!(" {}", self.count.unwrap_or(1)));
+            }
+            if let Some(ref tag) = self.tag {
+                command.push_str(&format!(" 0 {}", tag));
+            }
+            return Some(command);
+        }
+        if let Some(ref tag) = self.tag {
+            if version < ProtocolVersion::V1_20_5 {
+                command.push_str(tag);
+            } else {
+                let components = compound_entries(tag)
+                    .into_iter()
+                    .map(|(key, value)| {
+                        is_component_key(&key).then(|| format!("{}={}", key, value))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                command.push_str(&format!("[{}]", components.join(",")));
+            }
+        }
+        if let Some(count) = self.count {
+            command.push_str(&format!(" {}", count));
+        }
+        Some(command)
+    }
+}
+
+/// Whether a key is a namespaced data component type, like
+/// `minecraft:custom_name`.
+fn is_component_key(key: &str) -> bool {
+    let valid = |part: &str, extra: &[char]| {
+        !part.is_empty()
+            && part.chars().all(|c| {
+                c.is_ascii_lowercase()
+                    || c.is_ascii_digit()
+                    || "_-.".contains(c)
+                    || extra.contains(&c)
+            })
+    };
+    match key.split_once(':') {
+        Some((namespace, path)) => valid(namespace, &[]) && valid(path, &['/']),
+        None => false,
+    }
 }
 
+/// Splits an SNBT compound into its keys (unquoted) and values.
+fn compound_entries(compound: &str) -> Vec<(String, &str)> {
+    let inner = compound.trim();
+    let inner = inner
+        .strip_prefix('{')
+        .and_then(|inner| inner.strip_suffix('}'))
+        .unwrap_or(inner);
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in inner
+        .char_indices()
+        .chain(std::iter::once((inner.len(), ',')))
+    {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                let entry = inner[start..index].trim();
+                start = index + 1;
+                if entry.is_empty() {
+                    continue;
+                }
+                let (key, value) = split_entry(entry);
+                entries.push((key, value));
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// Splits `key:value` at the first colon outside of a quoted key.
+fn split_entry(entry: &str) -> (String, &str) {
+    let first = entry.chars().next().unwrap_or_default();
+    if first == '"' || first == '\'' {
+        let mut escaped = false;
+        let mut key = String::new();
+        for (index, c) in entry.char_indices().skip(1) {
+            if escaped {
+                key.push(c);
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == first {
+                let value = entry[index + 1..].trim_start();
+                return (key, value.strip_prefix(':').unwrap_or(value).trim());
+            } else {
+                key.push(c);
+            }
+        }
+        (key, "")
+    } else {
+        match entry.split_once(':') {
+            Some((key, value)) => (key.trim().to_string(), value.trim()),
+            None => (entry.to_string(), ""),
+        }
+    }
+}
+
+/// The id of an entity: a [`Uuid`](uuid::Uuid) with the `uuid` feature,
+/// otherwise the UUID as a string, e.g. `"f81d4fae-7dec-11d0-a765-00a0c91e6bf6"`.
+#[cfg(feature = "uuid")]
+pub type EntityId = Uuid;
+/// The id of an entity: a `Uuid` with the `uuid` feature, otherwise the
+/// UUID as a string, e.g. `"f81d4fae-7dec-11d0-a765-00a0c91e6bf6"`.
+#[cfg(not(feature = "uuid"))]
+pub type EntityId = FrozenStr;
+
 /// Entity tooltip.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize))]
@@ -258,11 +1079,11 @@ pub struct EntityTooltip {
         feature = "serde",
         serde(default, deserialize_with = "optional_serde::deserialize")
     )]
-    pub id: Option<Uuid>,
+    pub id: Option<EntityId>,
 }
 
 impl EntityTooltip {
-    pub fn new<I>(name: Option<Chat>, kind: Option<I>, id: Option<Uuid>) -> Self
+    pub fn new<I>(name: Option<Chat>, kind: Option<I>, id: Option<EntityId>) -> Self
     where
         I: Into<FrozenStr>,
     {
@@ -272,6 +1093,121 @@ impl EntityTooltip {
             id,
         }
     }
+
+    /// A builder for an entity without any information.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, EntityTooltip};
+    ///
+    /// let tooltip = EntityTooltip::builder()
+    ///     .name(Chat::text("Bob"))
+    ///     .kind("minecraft:zombie")
+    ///     .random_id()
+    ///     .build();
+    /// assert_eq!(Some("minecraft:zombie"), tooltip.kind.as_deref());
+    /// assert!(tooltip.id.is_some());
+    /// ```
+    pub fn builder() -> EntityTooltipBuilder {
+        EntityTooltipBuilder::default()
+    }
+}
+
+/// Builds an [`ItemStack`], see [`ItemStack::builder()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemStackBuilder {
+    id: FrozenStr,
+    count: Option<i32>,
+    tag: Option<FrozenStr>,
+    enchantments: Vec<(FrozenStr, i16)>,
+}
+
+impl ItemStackBuilder {
+    pub fn count(mut self, count: i32) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// The SNBT compound of the item, enchantments are added to it.
+    pub fn tag<T: Into<FrozenStr>>(mut self, tag: T) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Adds an enchantment, written as the `Enchantments` list that items
+    /// have from 1.13 until 1.20.5.
+    pub fn enchant<T: Into<FrozenStr>>(mut self, id: T, level: i16) -> Self {
+        self.enchantments.push((id.into(), level));
+        self
+    }
+
+    pub fn build(self) -> ItemStack {
+        let tag = if self.enchantments.is_empty() {
+            self.tag
+        } else {
+            let enchantments = self
+                .enchantments
+                .iter()
+                .map(|(id, level)| format!("{{id:{:?},lvl:{}s}}", &**id, level))
+                .collect::<Vec<_>>()
+                .join(",");
+            let tag = self.tag.as_deref().unwrap_or("{}").trim();
+            let fields = tag
+                .strip_prefix('{')
+                .and_then(|tag| tag.strip_suffix('}'))
+                .unwrap_or(tag)
+                .trim();
+            Some(
+                if fields.is_empty() {
+                    format!("{{Enchantments:[{}]}}", enchantments)
+                } else {
+                    format!("{{{},Enchantments:[{}]}}", fields, enchantments)
+                }
+                .into(),
+            )
+        };
+        ItemStack {
+            id: self.id,
+            count: self.count,
+            tag,
+        }
+    }
+}
+
+/// Builds an [`EntityTooltip`], see [`EntityTooltip::builder()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntityTooltipBuilder {
+    name: Option<Chat>,
+    kind: Option<FrozenStr>,
+    id: Option<EntityId>,
+}
+
+impl EntityTooltipBuilder {
+    pub fn name<C: Into<Chat>>(mut self, name: C) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The entity type, like `minecraft:zombie`.
+    pub fn kind<T: Into<FrozenStr>>(mut self, kind: T) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    pub fn id<T: Into<EntityId>>(mut self, id: T) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Uses a new random id, for entities that don't exist.
+    #[cfg(feature = "uuid")]
+    pub fn random_id(self) -> Self {
+        self.id(Uuid::new_v4())
+    }
+
+    pub fn build(self) -> EntityTooltip {
+        EntityTooltip::new(self.name, self.kind, self.id)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -289,6 +1225,45 @@ mod optional_serde {
 mod tests {
     use super::*;
 
+    #[test]
+    fn from_hex() {
+        assert_eq!(
+            Ok(TextColor::from_rgb(0x0a, 0x0b, 0x0c)),
+            TextColor::from_hex("#0A0b0c")
+        );
+        assert_eq!(Err(HexColorErr::MissingHash), TextColor::from_hex(""));
+        assert_eq!(
+            Err(HexColorErr::WrongLength(3)),
+            TextColor::from_hex("#fff")
+        );
+        assert_eq!(
+            Err(HexColorErr::InvalidDigit(1)),
+            TextColor::from_hex("#é0000")
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn custom() {
+        assert_eq!(
+            TextColor::from_rgb(0xff, 0x80, 0x00),
+            TextColor::custom("#FF8000")
+        );
+        assert_eq!(TextColor::Red, TextColor::custom("red"));
+        assert_eq!(TextColor::Reset, TextColor::custom("#ff80"));
+        assert_eq!(TextColor::Reset, TextColor::custom(String::from("orange")));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn chat_color_alias() {
+        let color: ChatColor = ChatColor::Aqua;
+        assert_eq!(TextColor::Cyan, color);
+        assert!(matches!(TextColor::Pink, ChatColor::LightPurple));
+        assert_eq!(TextColor::Purple, TextColor::DarkPurple);
+    }
+
+    #[cfg(feature = "snbt")]
     #[test]
     fn test_itemstack() {
         let itemstack = ItemStack::new("minecraft:clay", Some(10), Some("{other:0}"));
@@ -301,4 +1276,93 @@ mod tests {
         let str = fastsnbt::to_string(&itemstack).unwrap();
         assert_eq!("{\"id\":\"minecraft:clay\",\"tag\":\"{other:2}\"}", &str);
     }
+
+    #[test]
+    fn shared_hover() {
+        let shared = Chat::text("tip").freeze();
+        let mut event = HoverEvent::ShowSharedText(shared.clone());
+        assert_eq!(HoverEvent::ShowText(Box::new(Chat::text("tip"))), event);
+        assert_ne!(HoverEvent::ShowText(Box::new(Chat::text("other"))), event);
+
+        event.text_mut().unwrap().children.push(Chat::text("!"));
+        assert_eq!(Chat::text("tip"), *shared);
+        assert!(matches!(event, HoverEvent::ShowText(_)));
+        assert_eq!(
+            Some(&Chat::text("tip").child(Chat::text("!"))),
+            event.text()
+        );
+    }
+
+    #[test]
+    fn item_builder() {
+        let item = ItemStack::builder("minecraft:bow").build();
+        assert_eq!(
+            ItemStack::new("minecraft:bow", None, Option::<&str>::None),
+            item
+        );
+        let item = ItemStack::builder("minecraft:bow")
+            .tag("{ }")
+            .enchant("minecraft:power", 2)
+            .enchant("minecraft:flame", 1)
+            .build();
+        assert_eq!(
+            Some(
+                "{Enchantments:[{id:\"minecraft:power\",lvl:2s},{id:\"minecraft:flame\",lvl:1s}]}"
+            ),
+            item.tag.as_deref()
+        );
+    }
+
+    #[test]
+    fn give_command() {
+        let item = ItemStack::new("minecraft:stone", None, Option::<&str>::None);
+        assert_eq!(
+            Some("/give @s minecraft:stone"),
+            item.to_give_command(340).as_deref()
+        );
+        assert_eq!(
+            Some("/give @s minecraft:stone"),
+            item.to_give_command(766).as_deref()
+        );
+        assert_eq!(
+            Some("/give @p minecraft:stone"),
+            item.to_give_command(47).as_deref()
+        );
+
+        let item = ItemStack::new(
+            "minecraft:diamond",
+            Some(3),
+            Some(
+                r#"{"minecraft:custom_name":'{"text":"a,b"}', "minecraft:lore":['"x"','"y"'], 'minecraft:rarity':"epic"}"#,
+            ),
+        );
+        assert_eq!(
+            Some(
+                r#"/give @s minecraft:diamond[minecraft:custom_name='{"text":"a,b"}',minecraft:lore=['"x"','"y"'],minecraft:rarity="epic"] 3"#
+            ),
+            item.to_give_command(770).as_deref()
+        );
+
+        for tag in [
+            "{Unbreakable:1b}",
+            "{display:{Name:'\"a\"'}}",
+            "{\"minecraft:\":1}",
+        ] {
+            let item = ItemStack::new("minecraft:diamond_sword", None, Some(tag));
+            assert_eq!(None, item.to_give_command(766), "{}", tag);
+            assert!(item.to_give_command(765).is_some());
+        }
+    }
+
+    #[test]
+    fn url_encoded() {
+        assert_eq!(
+            ClickEvent::url("https://example.com/%7Bid%7D?name=%22a%22%09%E2%98%85#top"),
+            ClickEvent::url_encoded("https://example.com/{id}?name=\"a\"\t★#top")
+        );
+        assert_eq!(
+            ClickEvent::url("https://example.com/%25%252"),
+            ClickEvent::url_encoded("https://example.com/%%2")
+        );
+    }
 }
@@ -2,22 +2,22 @@ use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 
-use crate::component::serde_support::{serialize_chat_option, version_option_none, SerializeChat};
+use crate::component::serde_support::{SerializeChat, SerializeCtx, SerializeUuid};
 use crate::freeze::FrozenStr;
-use crate::{Chat, VERSION_1_16};
+use crate::Chat;
 use serde::de::{self, Unexpected, Visitor};
 use serde::ser::{self, SerializeMap, SerializeStruct};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::Value;
-use uuid::Uuid;
+use serde_json::{Map, Value};
 
-use crate::style::{ClickEvent, HoverEvent, Style, TextColor};
+use crate::style::{ClickEvent, EntityTooltip, HoverEvent, Style, TextColor};
 
 impl Serialize for TextColor {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
+        let hex;
         serializer.serialize_str(match self {
             TextColor::Black => "black",
             TextColor::DarkBlue => "dark_blue",
@@ -35,7 +35,10 @@ impl Serialize for TextColor {
             TextColor::Pink => "light_purple",
             TextColor::Yellow => "yellow",
             TextColor::White => "white",
-            TextColor::Custom(color) => color,
+            TextColor::Custom(rgb) => {
+                hex = rgb.to_string();
+                &hex
+            }
             TextColor::Reset => "reset",
         })
     }
@@ -48,40 +51,11 @@ impl<'de> Deserialize<'de> for TextColor {
         D: Deserializer<'de>,
     {
         let input = FrozenStr::deserialize(deserializer)?;
-        Ok(match input.deref() {
-            "black" => TextColor::Black,
-            "dark_blue" => TextColor::DarkBlue,
-            "dark_green" => TextColor::DarkGreen,
-            "dark_aqua" => TextColor::DarkCyan,
-            "dark_red" => TextColor::DarkRed,
-            "dark_purple" => TextColor::Purple,
-            "gold" => TextColor::Gold,
-            "gray" => TextColor::Gray,
-            "dark_gray" => TextColor::DarkGray,
-            "blue" => TextColor::Blue,
-            "green" => TextColor::Green,
-            "aqua" => TextColor::Cyan,
-            "red" => TextColor::Red,
-            "light_purple" => TextColor::Pink,
-            "yellow" => TextColor::Yellow,
-            "white" => TextColor::White,
-            "reset" => TextColor::Reset,
-            custom => {
-                let error = serde::de::Error::invalid_value(
-                    Unexpected::Str(custom),
-                    &"a 6 digit hex color prefixed by '#'",
-                );
-                if custom.len() != 7 || !custom.starts_with('#') {
-                    return Err(error);
-                } else {
-                    for c in custom.chars() {
-                        if !"0123456789abcdefABCDEF".contains(c) {
-                            return Err(error);
-                        }
-                    }
-                    TextColor::custom(input)
-                }
-            }
+        TextColor::try_from(input.deref()).map_err(|_| {
+            serde::de::Error::invalid_value(
+                Unexpected::Str(&input),
+                &"a color name or a 6 digit hex color prefixed by '#'",
+            )
         })
     }
 }
@@ -91,28 +65,60 @@ impl Serialize for ClickEvent {
     where
         S: Serializer,
     {
+        ClickEventSerialize {
+            typed_fields: false,
+            event: self,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Writes a click event with its value in `value`, or from 1.21.5 on in the
+/// typed field of its action.
+struct ClickEventSerialize<'a> {
+    typed_fields: bool,
+    event: &'a ClickEvent,
+}
+
+impl<'a> Serialize for ClickEventSerialize<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let field = |typed: &'static str| if self.typed_fields { typed } else { "value" };
         let mut item = serializer.serialize_struct("clickEvent", 2)?;
-        match self {
+        match self.event {
             ClickEvent::OpenUrl(url) => {
                 item.serialize_field("action", "open_url")?;
-                item.serialize_field("value", url)?;
+                item.serialize_field(field("url"), url)?;
             }
             ClickEvent::RunCommand(cmd) => {
                 item.serialize_field("action", "run_command")?;
-                item.serialize_field("value", cmd)?;
+                item.serialize_field(field("command"), cmd)?;
             }
             ClickEvent::SuggestCommand(cmd) => {
                 item.serialize_field("action", "suggest_command")?;
-                item.serialize_field("value", cmd)?;
+                item.serialize_field(field("command"), cmd)?;
             }
             ClickEvent::ChangePage(page) => {
                 item.serialize_field("action", "change_page")?;
-                item.serialize_field("value", page)?;
+                item.serialize_field(field("page"), page)?;
             }
             ClickEvent::CopyToClipBoard(value) => {
                 item.serialize_field("action", "copy_to_clipboard")?;
                 item.serialize_field("value", value)?;
             }
+            ClickEvent::Custom { id, payload } => {
+                item.serialize_field("action", "custom")?;
+                item.serialize_field("id", id)?;
+                if let Some(payload) = payload {
+                    item.serialize_field("payload", payload)?;
+                }
+            }
+            ClickEvent::ShowDialog(dialog) => {
+                item.serialize_field("action", "show_dialog")?;
+                item.serialize_field("dialog", dialog)?;
+            }
         }
         item.end()
     }
@@ -128,7 +134,22 @@ enum ClickEventType {
 #[derive(Deserialize)]
 pub(crate) struct ClickEventData {
     action: FrozenStr,
-    value: ClickEventType,
+    #[serde(default)]
+    value: Option<ClickEventType>,
+    /// The typed values that replaced `value` in 1.21.5.
+    #[serde(default)]
+    url: Option<FrozenStr>,
+    #[serde(default)]
+    command: Option<FrozenStr>,
+    #[serde(default)]
+    page: Option<u32>,
+    /// The id of a `custom` event.
+    #[serde(default)]
+    id: Option<FrozenStr>,
+    #[serde(default)]
+    payload: Option<FrozenStr>,
+    #[serde(default)]
+    dialog: Option<FrozenStr>,
 }
 
 pub enum ClickEventDeserializeErr {
@@ -149,46 +170,65 @@ impl TryFrom<ClickEventData> for ClickEvent {
     type Error = ClickEventDeserializeErr;
 
     fn try_from(data: ClickEventData) -> Result<Self, Self::Error> {
-        if data.action.deref() == "change_page" {
-            if let ClickEventType::U32(value) = data.value {
-                Ok(ClickEvent::ChangePage(value))
-            } else {
-                Err(ClickEventDeserializeErr::NoValueFound(data.action))
+        let action = data.action;
+        let missing = || ClickEventDeserializeErr::NoValueFound(action.clone());
+        let value = data.value.or(match action.deref() {
+            "open_url" => data.url.map(ClickEventType::String),
+            "run_command" | "suggest_command" => data.command.map(ClickEventType::String),
+            "change_page" => data.page.map(ClickEventType::U32),
+            _ => None,
+        });
+        match (action.deref(), value) {
+            ("custom", _) => Ok(ClickEvent::Custom {
+                id: data.id.ok_or_else(missing)?,
+                payload: data.payload,
+            }),
+            ("show_dialog", _) => Ok(ClickEvent::ShowDialog(data.dialog.ok_or_else(missing)?)),
+            ("change_page", Some(ClickEventType::U32(value))) => Ok(ClickEvent::ChangePage(value)),
+            ("change_page", _) => Err(missing()),
+            ("open_url", Some(ClickEventType::String(str))) => Ok(ClickEvent::OpenUrl(str)),
+            ("run_command", Some(ClickEventType::String(str))) => Ok(ClickEvent::RunCommand(str)),
+            ("suggest_command", Some(ClickEventType::String(str))) => {
+                Ok(ClickEvent::SuggestCommand(str))
             }
-        } else if let ClickEventType::String(str) = data.value {
-            match data.action.deref() {
-                "open_url" => Ok(ClickEvent::OpenUrl(str)),
-                "run_command" => Ok(ClickEvent::RunCommand(str)),
-                "suggest_command" => Ok(ClickEvent::SuggestCommand(str)),
-                "copy_to_clipboard" => Ok(ClickEvent::CopyToClipBoard(str)),
-                _ => Err(ClickEventDeserializeErr::WrongKey(str)),
+            ("copy_to_clipboard", Some(ClickEventType::String(str))) => {
+                Ok(ClickEvent::CopyToClipBoard(str))
             }
-        } else {
-            Err(ClickEventDeserializeErr::WrongKey(data.action))
+            (_, None) => Err(missing()),
+            _ => Err(ClickEventDeserializeErr::WrongKey(action.clone())),
         }
     }
 }
 
 #[derive(Serialize)]
 struct SerializeEntity<'a> {
-    #[serde(skip_serializing_if = "version_option_none")]
-    #[serde(serialize_with = "serialize_chat_option")]
-    pub name: (i32, &'a Option<Box<Chat>>),
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<SerializeChat<'a>>,
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub kind: &'a Option<FrozenStr>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: &'a Option<Uuid>,
+    pub id: Option<SerializeUuid<'a>>,
 }
 
-struct HoverEventSerialize<'a> {
-    pub version: i32,
-    pub event: &'a HoverEvent,
+impl<'a> SerializeEntity<'a> {
+    fn new(ctx: &'a SerializeCtx<'a>, entity: &'a EntityTooltip) -> Self {
+        SerializeEntity {
+            name: entity
+                .name
+                .as_deref()
+                .map(|name| SerializeChat::new(ctx, name)),
+            kind: &entity.kind,
+            id: entity.id.as_ref().map(|id| SerializeUuid {
+                format: ctx.opts.uuid_format,
+                id,
+            }),
+        }
+    }
 }
 
-impl<'a> From<(i32, &'a HoverEvent)> for HoverEventSerialize<'a> {
-    fn from((version, event): (i32, &'a HoverEvent)) -> Self {
-        Self { version, event }
-    }
+struct HoverEventSerialize<'a> {
+    pub ctx: &'a SerializeCtx<'a>,
+    pub event: &'a HoverEvent,
 }
 
 impl<'a> Serialize for HoverEventSerialize<'a> {
@@ -197,43 +237,84 @@ impl<'a> Serialize for HoverEventSerialize<'a> {
         S: Serializer,
     {
         let mut event = serializer.serialize_struct("hoverEvent", 2)?;
-        if let HoverEvent::ShowText(ref text) = self.event {
+        let text_key = if self.ctx.version.uses_hover_contents()
+            && !self.ctx.version.uses_snake_case_events()
+        {
+            "contents"
+        } else {
+            "value"
+        };
+        if let HoverEvent::ShowAchievement(key) = self.event {
+            // achievements were replaced by advancements in 1.12
+            if !self.ctx.version.supports_keybinds() {
+                event.serialize_field("action", "show_achievement")?;
+                event.serialize_field("value", key)?;
+            } else {
+                event.serialize_field("action", "show_text")?;
+                event.serialize_field(
+                    text_key,
+                    &SerializeChat::new(self.ctx, &Chat::translate(key.clone())),
+                )?;
+            }
+        } else if let Some(text) = self.event.text() {
             event.serialize_field("action", "show_text")?;
-            event.serialize_field(
-                if self.version < VERSION_1_16 {
-                    "value"
-                } else {
-                    "contents"
-                },
-                &SerializeChat {
-                    kind: (self.version, &text.kind).into(),
-                    style: (self.version, &text.style).into(),
-                    children: (self.version, &text.children),
-                },
-            )?;
-        } else if self.version < VERSION_1_16 {
+            event.serialize_field(text_key, &SerializeChat::new(self.ctx, text))?;
+        } else if self.ctx.version.uses_snake_case_events() {
+            // since 1.21.5 the item or entity is inlined in the event
             match &self.event {
                 HoverEvent::ShowItem(item) => {
                     event.serialize_field("action", "show_item")?;
-                    event.serialize_field(
-                        "value",
-                        &fastsnbt::to_string(&item)
-                            .map_err(|_| ser::Error::custom("invalid item"))?,
-                    )?;
+                    event.serialize_field("id", &item.id)?;
+                    if let Some(count) = item.count {
+                        event.serialize_field("count", &count)?;
+                    }
+                    if let Some(tag) = &item.tag {
+                        match serde_json::from_str::<Value>(tag) {
+                            Ok(components @ Value::Object(_)) => {
+                                event.serialize_field("components", &components)?
+                            }
+                            _ if self.ctx.opts.strict => {
+                                return Err(ser::Error::custom(format!(
+                                    "item tag {} isn't a JSON object of data components",
+                                    tag
+                                )))
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                HoverEvent::ShowEntity(entity) => {
+                    let entity = SerializeEntity::new(self.ctx, entity);
+                    event.serialize_field("action", "show_entity")?;
+                    if let Some(kind) = entity.kind {
+                        event.serialize_field("id", kind)?;
+                    }
+                    if let Some(id) = &entity.id {
+                        event.serialize_field("uuid", id)?;
+                    }
+                    if let Some(name) = &entity.name {
+                        event.serialize_field("name", name)?;
+                    }
+                }
+                _ => unreachable!("text is already matched earlier"),
+            }
+        } else if !self.ctx.version.uses_hover_contents() {
+            match &self.event {
+                HoverEvent::ShowItem(item) => {
+                    event.serialize_field("action", "show_item")?;
+                    event.serialize_field("value", &to_snbt::<_, S::Error>(&item, "item")?)?;
                 }
                 HoverEvent::ShowEntity(entity) => {
                     event.serialize_field("action", "show_entity")?;
                     event.serialize_field(
                         "value",
-                        &fastsnbt::to_string(&SerializeEntity {
-                            name: (self.version, &entity.name),
-                            kind: &entity.kind,
-                            id: &entity.id,
-                        })
-                        .map_err(|_| ser::Error::custom("invalid entity data"))?,
+                        &to_snbt::<_, S::Error>(
+                            &SerializeEntity::new(self.ctx, entity),
+                            "entity data",
+                        )?,
                     )?;
                 }
-                _ => unreachable!("third arm is already matched earlier"),
+                _ => unreachable!("text is already matched earlier"),
             }
         } else {
             match &self.event {
@@ -243,16 +324,9 @@ impl<'a> Serialize for HoverEventSerialize<'a> {
                 }
                 HoverEvent::ShowEntity(entity) => {
                     event.serialize_field("action", "show_entity")?;
-                    event.serialize_field(
-                        "contents",
-                        &SerializeEntity {
-                            name: (self.version, &entity.name),
-                            kind: &entity.kind,
-                            id: &entity.id,
-                        },
-                    )?;
+                    event.serialize_field("contents", &SerializeEntity::new(self.ctx, entity))?;
                 }
-                _ => unreachable!("third arm is already matched earlier"),
+                _ => unreachable!("text is already matched earlier"),
             }
         }
         event.end()
@@ -297,21 +371,23 @@ impl<'de> Deserialize<'de> for HoverEvent {
                         ("value", "show_text") => {
                             Ok(HoverEvent::ShowText(Box::new(map.next_value()?)))
                         }
-                        ("value", "show_item") => Ok(HoverEvent::ShowItem(
-                            fastsnbt::from_str(&map.next_value::<String>()?)
-                                .map_err(|e| de::Error::custom(e.to_string()))?,
-                        )),
-                        ("value", "show_entity") => Ok(HoverEvent::ShowEntity(
-                            fastsnbt::from_str(&map.next_value::<String>()?)
-                                .map_err(|e| de::Error::custom(e.to_string()))?,
-                        )),
+                        ("value", "show_item") => Ok(HoverEvent::ShowItem(from_snbt(
+                            &map.next_value::<String>()?,
+                        )?)),
+                        ("value", "show_entity") => Ok(HoverEvent::ShowEntity(from_snbt(
+                            &map.next_value::<String>()?,
+                        )?)),
+                        ("contents", "show_achievement") | ("value", "show_achievement") => {
+                            Ok(HoverEvent::ShowAchievement(map.next_value()?))
+                        }
+                        (_, "show_item") | (_, "show_entity") => inlined(Some(action), key, map),
                         ("contents", _) => Err(de::Error::invalid_value(
                             Unexpected::Str(key),
-                            &"`show_text`, `show_item` or `show_entity`",
+                            &"`show_text`, `show_item`, `show_entity` or `show_achievement`",
                         )),
                         ("value", _) => Err(de::Error::invalid_value(
                             Unexpected::Str(key),
-                            &"`show_text`, `show_item` or `show_entity`",
+                            &"`show_text`, `show_item`, `show_entity` or `show_achievement`",
                         )),
                         _ => Err(de::Error::invalid_value(
                             Unexpected::Str(key),
@@ -342,46 +418,119 @@ impl<'de> Deserialize<'de> for HoverEvent {
                                 de::Error::custom(format!("Invalid text component: {}", e))
                             })?,
                         ))),
-                        ("value", "show_item") => Ok(HoverEvent::ShowItem(
-                            fastsnbt::from_str(
-                                content_value
-                                    .as_str()
-                                    .ok_or(de::Error::custom("Expected itemstack sNBT"))?,
-                            )
-                            .map_err(|e| de::Error::custom(e.to_string()))?,
-                        )),
-                        ("value", "show_entity") => Ok(HoverEvent::ShowEntity(
-                            fastsnbt::from_str(
-                                content_value
-                                    .as_str()
-                                    .ok_or(de::Error::custom("Expected entity sNBT"))?,
-                            )
-                            .map_err(|e| de::Error::custom(e.to_string()))?,
-                        )),
+                        ("value", "show_item") => Ok(HoverEvent::ShowItem(from_snbt(
+                            content_value
+                                .as_str()
+                                .ok_or(de::Error::custom("Expected itemstack sNBT"))?,
+                        )?)),
+                        ("value", "show_entity") => Ok(HoverEvent::ShowEntity(from_snbt(
+                            content_value
+                                .as_str()
+                                .ok_or(de::Error::custom("Expected entity sNBT"))?,
+                        )?)),
+                        ("contents", "show_achievement") | ("value", "show_achievement") => {
+                            Ok(HoverEvent::ShowAchievement(
+                                serde_json::from_value(content_value)
+                                    .map_err(|_| de::Error::custom("Expected achievement key"))?,
+                            ))
+                        }
                         _ => Err(de::Error::invalid_value(
                             Unexpected::Str(key),
-                            &"`show_text`, `show_item` or `show_entity`",
+                            &"`show_text`, `show_item`, `show_entity` or `show_achievement`",
                         )),
                     }
                 } else {
-                    Err(de::Error::invalid_value(Unexpected::Str(key), &"`action`"))
+                    inlined(None, key, map)
                 }
             }
         }
 
+        /// Reads an item or entity that is inlined in the event since 1.21.5,
+        /// from the first key after the action on.
+        fn inlined<'de, A>(
+            action: Option<&str>,
+            key: &str,
+            mut map: A,
+        ) -> Result<HoverEvent, A::Error>
+        where
+            A: serde::de::MapAccess<'de>,
+        {
+            let mut fields = Map::new();
+            fields.insert(key.to_string(), map.next_value()?);
+            while let Some(key) = map.next_key::<String>()? {
+                fields.insert(key, map.next_value()?);
+            }
+            let action = match action {
+                Some(action) => Value::from(action),
+                None => fields
+                    .remove("action")
+                    .ok_or(de::Error::missing_field("action"))?,
+            };
+            let mut rename =
+                |from: &str, to: &str| fields.remove(from).map(|value| (to.to_string(), value));
+            let contents: Map<String, Value> = match action.as_str() {
+                Some("show_item") => rename("id", "id")
+                    .into_iter()
+                    .chain(rename("count", "Count"))
+                    .chain(
+                        rename("components", "tag")
+                            .map(|(key, components)| (key, Value::from(components.to_string()))),
+                    )
+                    .collect(),
+                Some("show_entity") => rename("id", "type")
+                    .into_iter()
+                    .chain(rename("uuid", "id"))
+                    .chain(rename("name", "name"))
+                    .collect(),
+                _ => {
+                    return Err(de::Error::invalid_value(
+                        Unexpected::Str(key),
+                        &"`action`, `contents` or `value`",
+                    ))
+                }
+            };
+            let event = if action == "show_item" {
+                serde_json::from_value(Value::Object(contents)).map(HoverEvent::ShowItem)
+            } else {
+                serde_json::from_value(Value::Object(contents)).map(HoverEvent::ShowEntity)
+            };
+            event.map_err(de::Error::custom)
+        }
+
         deserializer.deserialize_map(HoverVisitor)
     }
 }
 
-pub(crate) struct StyleVersioned<'a> {
-    pub version: i32,
-    pub style: &'a Style,
+/// Writes an item or entity as SNBT, the value of hover events before 1.16.
+#[cfg(feature = "snbt")]
+fn to_snbt<T: Serialize, E: ser::Error>(value: &T, what: &str) -> Result<String, E> {
+    fastsnbt::to_string(value).map_err(|_| E::custom(format!("invalid {}", what)))
 }
 
-impl<'a> From<(i32, &'a Style)> for StyleVersioned<'a> {
-    fn from((version, style): (i32, &'a Style)) -> Self {
-        Self { version, style }
-    }
+#[cfg(not(feature = "snbt"))]
+fn to_snbt<T: Serialize, E: ser::Error>(_: &T, what: &str) -> Result<String, E> {
+    Err(E::custom(format!(
+        "writing {} before 1.16 needs the `snbt` feature",
+        what
+    )))
+}
+
+/// Reads an item or entity from the SNBT value of a hover event.
+#[cfg(feature = "snbt")]
+fn from_snbt<T: de::DeserializeOwned, E: de::Error>(snbt: &str) -> Result<T, E> {
+    fastsnbt::from_str(snbt).map_err(|e| E::custom(e.to_string()))
+}
+
+#[cfg(not(feature = "snbt"))]
+fn from_snbt<T, E: de::Error>(_: &str) -> Result<T, E> {
+    Err(E::custom(
+        "reading items and entities before 1.16 needs the `snbt` feature",
+    ))
+}
+
+pub(crate) struct StyleVersioned<'a> {
+    pub ctx: &'a SerializeCtx<'a>,
+    pub style: &'a Style,
 }
 
 impl<'a> Serialize for StyleVersioned<'a> {
@@ -389,7 +538,7 @@ impl<'a> Serialize for StyleVersioned<'a> {
     where
         S: Serializer,
     {
-        let version = self.version;
+        let version = self.ctx.version;
         let style = &self.style;
         let mut map = serializer.serialize_map(None)?;
         if style.bold.is_some() {
@@ -408,35 +557,54 @@ impl<'a> Serialize for StyleVersioned<'a> {
             map.serialize_entry("obfuscated", &style.obfuscated)?;
         }
         if style.color.is_some() {
-            if let Some(TextColor::Custom(_)) = style.color {
-                if version >= 713 {
+            if let Some(TextColor::Custom(rgb)) = style.color {
+                if version.supports_hex_colors() {
                     map.serialize_entry("color", &style.color)?;
+                } else if self.ctx.opts.strict {
+                    return Err(ser::Error::custom(format!(
+                        "custom color {} isn't supported before 1.16",
+                        rgb
+                    )));
                 }
             } else {
                 map.serialize_entry("color", &style.color)?;
             }
         }
-        if version >= 5 {
+        if version.supports_insertion() {
             if style.insertion.is_some() {
                 map.serialize_entry("insertion", &style.insertion)?;
             }
-            if version >= 713 && style.font.is_some() {
+            if version.supports_fonts() && style.font.is_some() {
                 map.serialize_entry("font", &style.font)?;
             }
         }
-        if style.click_event.is_some() {
-            if let Some(ClickEvent::CopyToClipBoard(_)) = style.click_event {
-                if version >= 558 {
-                    map.serialize_entry("clickEvent", &style.click_event)?;
-                }
-            } else {
-                map.serialize_entry("clickEvent", &style.click_event)?;
+        let typed_fields = version.uses_snake_case_events();
+        if let Some(ref click_event) = style.click_event {
+            if click_event.supported(version) {
+                map.serialize_entry(
+                    if typed_fields {
+                        "click_event"
+                    } else {
+                        "clickEvent"
+                    },
+                    &ClickEventSerialize {
+                        typed_fields,
+                        event: click_event,
+                    },
+                )?;
             }
         }
         if let Some(hover_event) = &style.hover_event {
-            map.serialize_entry::<_, HoverEventSerialize>(
-                "hoverEvent",
-                &(version, hover_event).into(),
+            map.serialize_entry(
+                if typed_fields {
+                    "hover_event"
+                } else {
+                    "hoverEvent"
+                },
+                &HoverEventSerialize {
+                    ctx: self.ctx,
+                    event: hover_event,
+                },
             )?;
         }
 
@@ -446,45 +614,166 @@ impl<'a> Serialize for StyleVersioned<'a> {
 
 #[cfg(test)]
 mod tests {
+    mod text_color {
+        use crate::TextColor;
+
+        #[test]
+        pub fn deserialize_custom() {
+            let color: TextColor = serde_json::from_str(r##""#Ff8000""##).unwrap();
+            assert_eq!(TextColor::from_rgb(0xff, 0x80, 0x00), color);
+            assert_eq!(r##""#ff8000""##, serde_json::to_string(&color).unwrap());
+            assert!(serde_json::from_str::<TextColor>(r##""#ff800g""##).is_err());
+            assert!(serde_json::from_str::<TextColor>(r#""ff8000""#).is_err());
+        }
+    }
+
+    mod click_event {
+        use crate::{Chat, ClickEvent, VERSION_1_16, VERSION_1_21, VERSION_1_21_5, VERSION_1_21_6};
+
+        #[test]
+        pub fn custom_and_dialog() {
+            let event = ClickEvent::custom("mypack:vote", Some("{choice:1}"));
+            let json = r#"{"action":"custom","id":"mypack:vote","payload":"{choice:1}"}"#;
+            assert_eq!(json, serde_json::to_string(&event).unwrap());
+            assert_eq!(event, serde_json::from_str(json).unwrap());
+            let event = ClickEvent::custom("mypack:ping", Option::<&str>::None);
+            assert_eq!(
+                event,
+                serde_json::from_str(r#"{"action":"custom","id":"mypack:ping"}"#).unwrap()
+            );
+
+            let event = ClickEvent::dialog("mypack:rules");
+            let json = r#"{"action":"show_dialog","dialog":"mypack:rules"}"#;
+            assert_eq!(json, serde_json::to_string(&event).unwrap());
+            assert_eq!(event, serde_json::from_str(json).unwrap());
+            assert!(serde_json::from_str::<ClickEvent>(r#"{"action":"show_dialog"}"#).is_err());
+
+            let chat = Chat::text("Rules").click(Some(event));
+            assert_eq!(
+                r#"{"text":"Rules"}"#,
+                chat.serialize_str(VERSION_1_16).unwrap()
+            );
+            let json = r#"{"text":"Rules","click_event":{"action":"show_dialog","dialog":"mypack:rules"}}"#;
+            assert_eq!(json, chat.serialize_str(VERSION_1_21_6).unwrap());
+            assert_eq!(chat, Chat::from_json(json).unwrap());
+        }
+
+        #[test]
+        pub fn typed_fields() {
+            let events = vec![
+                (
+                    ClickEvent::url("https://example.com"),
+                    r#"{"action":"open_url","url":"https://example.com"}"#,
+                ),
+                (
+                    ClickEvent::command("/help"),
+                    r#"{"action":"run_command","command":"/help"}"#,
+                ),
+                (
+                    ClickEvent::suggest("/msg "),
+                    r#"{"action":"suggest_command","command":"/msg "}"#,
+                ),
+                (
+                    ClickEvent::page(2u32),
+                    r#"{"action":"change_page","page":2}"#,
+                ),
+                (
+                    ClickEvent::clipboard("copied"),
+                    r#"{"action":"copy_to_clipboard","value":"copied"}"#,
+                ),
+            ];
+            for (event, json) in events {
+                let chat = Chat::text("").click(Some(event));
+                let expected = format!(r#"{{"text":"","click_event":{}}}"#, json);
+                assert_eq!(expected, chat.serialize_str(VERSION_1_21_5).unwrap());
+                assert_eq!(chat, Chat::from_json(&expected).unwrap());
+                let json = chat.serialize_str(VERSION_1_21).unwrap();
+                assert!(json.contains(r#""clickEvent":{"#) && json.contains(r#""value":"#));
+            }
+        }
+    }
+
     mod hover_event {
-        use crate::{Chat, EntityTooltip, HoverEvent, ItemStack, VERSION_1_16, VERSION_1_8};
+        use crate::EntityTooltip;
+        use crate::{Chat, HoverEvent, ItemStack, VERSION_1_16, VERSION_1_21_5, VERSION_1_8};
 
         use super::super::HoverEventSerialize;
+        use crate::component::serde_support::SerializeCtx;
+        use crate::SerializeOpts;
+
+        fn serialize(version: i32, event: &HoverEvent) -> serde_json::Result<String> {
+            let opts = SerializeOpts::default();
+            let ctx = SerializeCtx {
+                version: version.into(),
+                opts: &opts,
+            };
+            serde_json::to_string(&HoverEventSerialize { ctx: &ctx, event })
+        }
 
         #[test]
         pub fn serialize_text() {
             let event = HoverEvent::ShowText(Box::new(Chat::text("Sample text")));
-            let serialized_str_pre =
-                serde_json::to_string(&HoverEventSerialize::from((VERSION_1_8, &event))).unwrap();
+            let serialized_str_pre = serialize(VERSION_1_8, &event).unwrap();
             assert_eq!(
                 r#"{"action":"show_text","value":{"text":"Sample text"}}"#,
                 serialized_str_pre
             );
-            let serialized_str_post =
-                serde_json::to_string(&HoverEventSerialize::from((VERSION_1_16, &event))).unwrap();
+            let serialized_str_post = serialize(VERSION_1_16, &event).unwrap();
             assert_eq!(
                 r#"{"action":"show_text","contents":{"text":"Sample text"}}"#,
                 serialized_str_post
             );
         }
 
+        #[test]
+        pub fn achievement() {
+            let event = HoverEvent::ShowAchievement("achievement.openInventory".into());
+            let json = r#"{"action":"show_achievement","value":"achievement.openInventory"}"#;
+            assert_eq!(json, serialize(VERSION_1_8, &event).unwrap());
+            assert_eq!(event, serde_json::from_str(json).unwrap());
+            assert_eq!(
+                event,
+                serde_json::from_str(
+                    r#"{"value":"achievement.openInventory","action":"show_achievement"}"#
+                )
+                .unwrap()
+            );
+            assert_eq!(
+                r#"{"action":"show_text","value":{"translate":"achievement.openInventory"}}"#,
+                serialize(335, &event).unwrap()
+            );
+            assert_eq!(
+                r#"{"action":"show_text","contents":{"translate":"achievement.openInventory"}}"#,
+                serialize(VERSION_1_16, &event).unwrap()
+            );
+        }
+
+        #[cfg(not(feature = "snbt"))]
+        #[test]
+        pub fn snbt_disabled() {
+            let event = HoverEvent::ShowItem(ItemStack::simple("diamond"));
+            let err = serialize(VERSION_1_8, &event).unwrap_err();
+            assert!(err.to_string().contains("`snbt` feature"));
+            assert!(serialize(VERSION_1_16, &event).is_ok());
+        }
+
+        #[cfg(feature = "snbt")]
         #[test]
         pub fn serialize_itemstack() {
             let event = HoverEvent::ShowItem(ItemStack::new("diamond", None, Option::<&str>::None));
-            let serialized_str_pre =
-                serde_json::to_string(&HoverEventSerialize::from((VERSION_1_8, &event))).unwrap();
+            let serialized_str_pre = serialize(VERSION_1_8, &event).unwrap();
             assert_eq!(
                 r#"{"action":"show_item","value":"{\"id\":\"diamond\"}"}"#,
                 serialized_str_pre
             );
-            let serialized_str_post =
-                serde_json::to_string(&HoverEventSerialize::from((VERSION_1_16, &event))).unwrap();
+            let serialized_str_post = serialize(VERSION_1_16, &event).unwrap();
             assert_eq!(
                 r#"{"action":"show_item","contents":{"id":"diamond"}}"#,
                 serialized_str_post
             );
         }
 
+        #[cfg(feature = "snbt")]
         #[test]
         pub fn serialize_entity() {
             let event = HoverEvent::ShowEntity(EntityTooltip::new(
@@ -492,32 +781,76 @@ mod tests {
                 Some("minecraft:pig"),
                 None,
             ));
-            let serialized_str_pre =
-                serde_json::to_string(&HoverEventSerialize::from((VERSION_1_8, &event))).unwrap();
+            let serialized_str_pre = serialize(VERSION_1_8, &event).unwrap();
             assert_eq!(
                 r#"{"action":"show_entity","value":"{\"name\":{\"text\":\"Sample name\"},\"type\":\"minecraft:pig\"}"}"#,
                 serialized_str_pre
             );
-            let serialized_str_post =
-                serde_json::to_string(&HoverEventSerialize::from((VERSION_1_16, &event))).unwrap();
+            let serialized_str_post = serialize(VERSION_1_16, &event).unwrap();
             assert_eq!(
                 r#"{"action":"show_entity","contents":{"name":{"text":"Sample name"},"type":"minecraft:pig"}}"#,
                 serialized_str_post
             );
         }
 
+        #[test]
+        pub fn inlined() {
+            let event = HoverEvent::ShowText(Box::new(Chat::text("tip")));
+            assert_eq!(
+                r#"{"action":"show_text","value":{"text":"tip"}}"#,
+                serialize(VERSION_1_21_5, &event).unwrap()
+            );
+
+            let event = HoverEvent::ShowItem(ItemStack::new(
+                "minecraft:diamond",
+                Some(2),
+                Some(r#"{"minecraft:rarity":"epic"}"#),
+            ));
+            let json = r#"{"action":"show_item","id":"minecraft:diamond","count":2,"components":{"minecraft:rarity":"epic"}}"#;
+            assert_eq!(json, serialize(VERSION_1_21_5, &event).unwrap());
+            assert_eq!(event, serde_json::from_str(json).unwrap());
+            assert_eq!(
+                event,
+                serde_json::from_str(
+                    r#"{"count":2,"id":"minecraft:diamond","components":{"minecraft:rarity":"epic"},"action":"show_item"}"#
+                )
+                .unwrap()
+            );
+            // an SNBT tag can't be written as components
+            let event = HoverEvent::ShowItem(ItemStack::new("stone", Some(1), Some("{a:1b}")));
+            assert_eq!(
+                r#"{"action":"show_item","id":"stone","count":1}"#,
+                serialize(VERSION_1_21_5, &event).unwrap()
+            );
+
+            let event = HoverEvent::ShowEntity(EntityTooltip::new(
+                Some(Chat::text("Pig")),
+                Some("minecraft:pig"),
+                None,
+            ));
+            let json = r#"{"action":"show_entity","id":"minecraft:pig","name":{"text":"Pig"}}"#;
+            assert_eq!(json, serialize(VERSION_1_21_5, &event).unwrap());
+            assert_eq!(event, serde_json::from_str(json).unwrap());
+
+            let chat = Chat::text("").hover(Some(event));
+            let json = chat.serialize_str(VERSION_1_21_5).unwrap();
+            assert!(json.contains(r#""hover_event":{"#));
+            assert_eq!(chat, Chat::from_json(&json).unwrap());
+        }
+
         #[test]
         pub fn deserialize_text() {
             let event_orig = HoverEvent::ShowText(Box::new(Chat::text("Sample text")));
 
             let serialized_str_pre = r#"{"value":{"text":"Sample text"},"action":"show_text"}"#;
             let serialized_str_post = r#"{"contents":{"text":"Sample text"},"action":"show_text"}"#;
-            let event = serde_json::from_str(&serialized_str_pre).unwrap();
+            let event = serde_json::from_str(serialized_str_pre).unwrap();
             assert_eq!(event_orig, event);
-            let event = serde_json::from_str(&serialized_str_post).unwrap();
+            let event = serde_json::from_str(serialized_str_post).unwrap();
             assert_eq!(event_orig, event);
         }
 
+        #[cfg(feature = "snbt")]
         #[test]
         pub fn deserialize_item() {
             let event_orig =
@@ -527,12 +860,13 @@ mod tests {
                 r#"{"value":"{\"id\":\"diamond\",\"Count\":30}","action":"show_item"}"#;
             let serialized_str_post =
                 r#"{"contents":{"id":"diamond","Count":30},"action":"show_item"}"#;
-            let event = serde_json::from_str(&serialized_str_pre).unwrap();
+            let event = serde_json::from_str(serialized_str_pre).unwrap();
             assert_eq!(event_orig, event);
-            let event = serde_json::from_str(&serialized_str_post).unwrap();
+            let event = serde_json::from_str(serialized_str_post).unwrap();
             assert_eq!(event_orig, event);
         }
 
+        #[cfg(feature = "snbt")]
         #[test]
         pub fn deserialize_entity() {
             let event_orig = HoverEvent::ShowEntity(EntityTooltip::new(
@@ -543,9 +877,9 @@ mod tests {
 
             let serialized_str_pre = r#"{"action":"show_entity","value":"{\"name\":{\"text\":\"Sample name\"},\"type\":\"minecraft:pig\"}"}"#;
             let serialized_str_post = r#"{"action":"show_entity","contents":{"name":{"text":"Sample name"},"type":"minecraft:pig"}}"#;
-            let event = serde_json::from_str(&serialized_str_pre).unwrap();
+            let event = serde_json::from_str(serialized_str_pre).unwrap();
             assert_eq!(event_orig, event);
-            let event = serde_json::from_str(&serialized_str_post).unwrap();
+            let event = serde_json::from_str(serialized_str_post).unwrap();
             assert_eq!(event_orig, event);
         }
     }
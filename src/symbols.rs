@@ -0,0 +1,94 @@
+//! Glyphs that are commonly used in chat, with their width in pixels in
+//! the default font: the advance of the glyph including the spacing after
+//! it, add 1 for bold text. [`DefaultFont`](crate::metrics::DefaultFont)
+//! measures them with these widths as well.
+//!
+//! # Example
+//! ```
+//! use mc_chat::symbols::{HEART, LINE_HORIZONTAL};
+//! use mc_chat::{Chat, TextColor};
+//!
+//! let health = Chat::text(HEART.to_string().repeat(3)).color(TextColor::Red);
+//! let separator = Chat::text(LINE_HORIZONTAL.to_string().repeat(20)).color(TextColor::DarkGray);
+//! ```
+
+macro_rules! symbols {
+    ($($(#[$doc:meta])* $name:ident = $glyph:literal, $width:literal;)*) => {
+        $(
+            $(#[$doc])*
+            #[doc = concat!("\n\n`", $glyph, "`, ", $width, " pixels wide.")]
+            pub const $name: char = $glyph;
+        )*
+
+        /// The width of a symbol of this module.
+        pub(crate) fn advance(c: char) -> Option<u32> {
+            match c {
+                $($glyph => Some($width),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+symbols! {
+    /// A filled star, e.g. for ratings.
+    STAR = '★', 9;
+    /// An empty star.
+    STAR_OUTLINE = '☆', 9;
+    /// A four pointed star.
+    SPARKLE = '✦', 9;
+
+    /// A heart, e.g. for health.
+    HEART = '❤', 9;
+    /// The heart suit of playing cards, a bit smaller than [`HEART`].
+    HEART_SUIT = '♥', 8;
+
+    ARROW_LEFT = '←', 8;
+    ARROW_RIGHT = '→', 8;
+    ARROW_UP = '↑', 6;
+    ARROW_DOWN = '↓', 6;
+    /// Often used for "previous" buttons.
+    DOUBLE_ARROW_LEFT = '«', 6;
+    /// Often used for "next" buttons.
+    DOUBLE_ARROW_RIGHT = '»', 6;
+    TRIANGLE_RIGHT = '▶', 9;
+
+    /// A check mark, e.g. for a completed task.
+    CHECK = '✔', 9;
+    /// A cross, e.g. for a failed task.
+    CROSS = '✘', 9;
+
+    /// A bullet for lists.
+    BULLET = '•', 4;
+    /// A small dot, e.g. between the parts of a status line.
+    MIDDLE_DOT = '·', 2;
+    /// A long dash.
+    EM_DASH = '—', 9;
+    /// A full block, e.g. for progress bars.
+    BLOCK = '█', 9;
+    /// A light shaded block, e.g. for the empty part of progress bars.
+    BLOCK_LIGHT = '░', 9;
+
+    /// A horizontal line that connects to its neighbours without gaps.
+    LINE_HORIZONTAL = '─', 9;
+    LINE_VERTICAL = '│', 9;
+    CORNER_TOP_LEFT = '┌', 9;
+    CORNER_TOP_RIGHT = '┐', 9;
+    CORNER_BOTTOM_LEFT = '└', 9;
+    CORNER_BOTTOM_RIGHT = '┘', 9;
+    TEE_LEFT = '├', 9;
+    TEE_RIGHT = '┤', 9;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{DefaultFont, FontMetrics};
+
+    #[test]
+    fn widths() {
+        assert_eq!(Some(9), advance(STAR));
+        assert_eq!(None, advance('a'));
+        assert_eq!(7, DefaultFont.advance(DOUBLE_ARROW_LEFT, true));
+    }
+}
@@ -0,0 +1,384 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{freeze::FrozenStr, Chat, ComponentKind, TextComponent, TranslationArg};
+
+/// A chat component with `{name}` placeholders in its text, which are
+/// replaced by other components when it's rendered.
+///
+/// A placeholder's value becomes a child of the text around it, so it
+/// inherits that text's style. `{{` and `}}` escape the braces, placeholders
+/// without a value are left as they are.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use mc_chat::{Chat, Template, TextColor};
+///
+/// let template = Template::new(Chat::text("Welcome {player}!").color(TextColor::Yellow));
+/// let mut values = HashMap::new();
+/// values.insert("player", Chat::text("Steve").bold(true));
+/// assert_eq!(
+///     Chat::text("Welcome ")
+///         .color(TextColor::Yellow)
+///         .child(Chat::text("Steve").bold(true))
+///         .child(Chat::text("!")),
+///     template.render(&values)
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Template {
+    pub chat: Chat,
+}
+
+impl Template {
+    pub fn new<C: Into<Chat>>(chat: C) -> Self {
+        Self { chat: chat.into() }
+    }
+
+    /// Builds the component with the placeholders replaced by their values.
+    pub fn render<K: Borrow<str> + Eq + Hash>(&self, values: &HashMap<K, Chat>) -> Chat {
+        render(&self.chat, values)
+    }
+
+    /// Finds the placeholders once, for a template that's rendered often.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use mc_chat::{Chat, Template};
+    ///
+    /// let join = Template::new(Chat::text("{player} joined the game")).compile();
+    /// assert_eq!("player", &*join.slots()[0]);
+    /// for player in &["Steve", "Alex"] {
+    ///     let mut values = HashMap::new();
+    ///     values.insert("player", Chat::text(*player));
+    ///     join.render(&values);
+    /// }
+    /// ```
+    pub fn compile(&self) -> CompiledTemplate {
+        let mut slots = Vec::new();
+        let root = compile(&self.chat, &mut slots);
+        CompiledTemplate { root, slots }
+    }
+}
+
+/// A [`Template`] with its placeholders resolved, see [`Template::compile()`].
+///
+/// The parts without placeholders are kept as whole components,
+/// rendering only clones them and fills in the slots.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CompiledTemplate {
+    root: Compiled,
+    slots: Vec<FrozenStr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Compiled {
+    /// A tree without placeholders.
+    Static(Chat),
+    /// A component with placeholders in its text, arguments or children.
+    /// The head has no children and only keeps the primitive arguments,
+    /// the others are stored with their index.
+    Dynamic {
+        head: Chat,
+        arguments: Vec<(usize, Compiled)>,
+        children: Vec<Compiled>,
+    },
+    /// The index of a slot.
+    Slot(usize),
+}
+
+impl CompiledTemplate {
+    /// The names of the placeholders, in the order they first appear.
+    pub fn slots(&self) -> &[FrozenStr] {
+        &self.slots
+    }
+
+    /// Builds the component with the placeholders replaced by their values.
+    pub fn render<K: Borrow<str> + Eq + Hash>(&self, values: &HashMap<K, Chat>) -> Chat {
+        let values = self
+            .slots
+            .iter()
+            .map(|name| values.get(&**name))
+            .collect::<Vec<_>>();
+        self.render_compiled(&self.root, &values)
+    }
+
+    fn render_compiled(&self, compiled: &Compiled, values: &[Option<&Chat>]) -> Chat {
+        match compiled {
+            Compiled::Static(chat) => chat.clone(),
+            Compiled::Slot(index) => match values[*index] {
+                Some(value) => value.clone(),
+                None => Chat::text(format!("{{{}}}", &*self.slots[*index])),
+            },
+            Compiled::Dynamic {
+                head,
+                arguments,
+                children,
+            } => {
+                let mut chat = head.clone();
+                if let ComponentKind::Translation(ref mut translation) = chat.kind {
+                    let mut primitives = std::mem::take(&mut translation.with).into_iter();
+                    let mut arguments = arguments.iter().peekable();
+                    for index in 0..primitives.len() + arguments.len() {
+                        let argument = match arguments.next_if(|(at, _)| *at == index) {
+                            Some((_, argument)) => self.render_compiled(argument, values).into(),
+                            None => primitives.next().expect("primitive argument"),
+                        };
+                        translation.with.push(argument);
+                    }
+                }
+                chat.children = children
+                    .iter()
+                    .map(|child| self.render_compiled(child, values))
+                    .collect();
+                chat
+            }
+        }
+    }
+}
+
+fn compile(chat: &Chat, slots: &mut Vec<FrozenStr>) -> Compiled {
+    let mut head = Chat {
+        kind: chat.kind.clone(),
+        style: chat.style.clone(),
+        children: Vec::new(),
+        #[cfg(feature = "extensions")]
+        extensions: chat.extensions.clone(),
+    };
+    let mut arguments = Vec::new();
+    let mut children = Vec::new();
+    match chat.kind {
+        ComponentKind::Text(ref text) => {
+            let mut segments = split_placeholders(&text.text);
+            if let Some(Segment::Text(_)) = segments.first() {
+                if let Segment::Text(text) = segments.remove(0) {
+                    head.kind = TextComponent::new(text).into();
+                }
+            } else {
+                head.kind = TextComponent::new("").into();
+            }
+            for segment in segments {
+                children.push(match segment {
+                    Segment::Text(text) => Compiled::Static(Chat::text(text)),
+                    Segment::Placeholder(name) => {
+                        let index = match slots.iter().position(|slot| &**slot == name) {
+                            Some(index) => index,
+                            None => {
+                                slots.push(name.into());
+                                slots.len() - 1
+                            }
+                        };
+                        Compiled::Slot(index)
+                    }
+                });
+            }
+        }
+        ComponentKind::Translation(ref translation) => {
+            arguments = translation
+                .with
+                .iter()
+                .enumerate()
+                .filter_map(|(index, argument)| Some((index, compile(argument.as_chat()?, slots))))
+                .collect();
+            if let ComponentKind::Translation(ref mut translation) = head.kind {
+                translation
+                    .with
+                    .retain(|argument| argument.as_chat().is_none());
+            }
+        }
+        _ => {}
+    }
+    children.extend(chat.children.iter().map(|child| compile(child, slots)));
+
+    let is_static = |compiled: &Compiled| matches!(compiled, Compiled::Static(_));
+    if children.len() == chat.children.len()
+        && children.iter().all(is_static)
+        && arguments.iter().all(|(_, argument)| is_static(argument))
+    {
+        // nothing to fill in, keep the component as it was
+        Compiled::Static(chat.clone())
+    } else {
+        Compiled::Dynamic {
+            head,
+            arguments,
+            children,
+        }
+    }
+}
+
+fn render<K: Borrow<str> + Eq + Hash>(chat: &Chat, values: &HashMap<K, Chat>) -> Chat {
+    let mut result = Chat {
+        kind: chat.kind.clone(),
+        style: chat.style.clone(),
+        children: Vec::with_capacity(chat.children.len()),
+        #[cfg(feature = "extensions")]
+        extensions: chat.extensions.clone(),
+    };
+    match chat.kind {
+        ComponentKind::Text(ref text) => {
+            let mut segments = split_placeholders(&text.text);
+            // text in front of the first placeholder stays in the styled component
+            let mut first = String::new();
+            if let Some(Segment::Text(_)) = segments.first() {
+                if let Segment::Text(text) = segments.remove(0) {
+                    first = text;
+                }
+            }
+            result.kind = TextComponent::new(first).into();
+            for segment in segments {
+                result.children.push(match segment {
+                    Segment::Text(text) => Chat::text(text),
+                    Segment::Placeholder(name) => match values.get(name) {
+                        Some(value) => value.clone(),
+                        None => Chat::text(format!("{{{}}}", name)),
+                    },
+                });
+            }
+        }
+        ComponentKind::Translation(ref translation) => {
+            if let ComponentKind::Translation(ref mut rendered) = result.kind {
+                rendered.with = translation
+                    .with
+                    .iter()
+                    .map(|argument| match argument {
+                        TranslationArg::Chat(argument) => render(argument, values).into(),
+                        primitive => primitive.clone(),
+                    })
+                    .collect();
+            }
+        }
+        _ => {}
+    }
+    result
+        .children
+        .extend(chat.children.iter().map(|child| render(child, values)));
+    result
+}
+
+/// A piece of text split at its placeholders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    Text(String),
+    Placeholder(&'a str),
+}
+
+/// Splits text into literal text, with the escaped braces unescaped,
+/// and `{name}` placeholders. An unclosed `{` is kept as text.
+pub(crate) fn split_placeholders(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find(['{', '}']) {
+        literal.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        let brace = rest.as_bytes()[index];
+        if after.as_bytes().first() == Some(&brace) {
+            literal.push(brace as char);
+            rest = &after[1..];
+        } else if brace == b'{' {
+            match after.find('}') {
+                Some(end) if !after[..end].contains('{') => {
+                    if !literal.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(&after[..end]));
+                    rest = &after[end + 1..];
+                }
+                _ => {
+                    literal.push('{');
+                    rest = after;
+                }
+            }
+        } else {
+            literal.push('}');
+            rest = after;
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(Segment::Text(literal));
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TextColor, TranslationComponent};
+
+    #[test]
+    fn segments() {
+        assert_eq!(
+            vec![
+                Segment::Text("a {".into()),
+                Segment::Placeholder("b"),
+                Segment::Text("} {c".into()),
+            ],
+            split_placeholders("a {{{b}}} {c")
+        );
+        assert_eq!(vec![Segment::Placeholder("x")], split_placeholders("{x}"));
+    }
+
+    #[test]
+    fn render_nested() {
+        let template = Template::new(
+            Chat::text("")
+                .child(Chat::text("{player}").color(TextColor::Red))
+                .child(Chat::component(
+                    TranslationComponent::new("chat.type.text")
+                        .argument(Chat::text("{player}"))
+                        .argument(Chat::text("{unknown}")),
+                )),
+        );
+        let mut values = HashMap::new();
+        values.insert("player".to_string(), Chat::text("Steve"));
+        assert_eq!(
+            Chat::text("")
+                .child(
+                    Chat::text("")
+                        .color(TextColor::Red)
+                        .child(Chat::text("Steve"))
+                )
+                .child(Chat::component(
+                    TranslationComponent::new("chat.type.text")
+                        .argument(Chat::text("").child(Chat::text("Steve")))
+                        .argument(Chat::text("").child(Chat::text("{unknown}")))
+                )),
+            template.render(&values)
+        );
+    }
+
+    #[test]
+    fn compiled() {
+        let template = Template::new(
+            Chat::text("{a} and {b}, {a}")
+                .color(TextColor::Red)
+                .child(Chat::text("static"))
+                .child(Chat::component(
+                    TranslationComponent::new("chat.type.text").argument(Chat::text("{b}")),
+                )),
+        );
+        let compiled = template.compile();
+        assert_eq!(
+            vec!["a", "b"],
+            compiled
+                .slots()
+                .iter()
+                .map(|slot| &**slot)
+                .collect::<Vec<_>>()
+        );
+        assert!(matches!(
+            compiled.root,
+            Compiled::Dynamic { ref children, .. } if matches!(children[5], Compiled::Static(_))
+        ));
+
+        let mut values = HashMap::new();
+        values.insert("a", Chat::text("Steve"));
+        assert_eq!(template.render(&values), compiled.render(&values));
+        values.insert("b", Chat::text("Alex"));
+        assert_eq!(template.render(&values), compiled.render(&values));
+    }
+}
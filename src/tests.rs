@@ -5,9 +5,8 @@ mod serde_support {
     use std::str::FromStr;
 
     use serde_json::Value;
-    use uuid::Uuid;
 
-    use crate::{Chat, ClickEvent, EntityTooltip, HoverEvent, TranslationComponent, VERSION_1_8};
+    use crate::{Chat, VERSION_1_8};
 
     #[test]
     pub fn chat_serialize() {
@@ -20,8 +19,24 @@ mod serde_support {
         assert_eq!(chat_orig, chat);
     }
 
+    #[test]
+    pub fn explicit_false() {
+        let chat = Chat::text("a")
+            .bold(true)
+            .child(Chat::text("b").bold(false).italic(Some(false)).color(None));
+        assert_eq!(
+            r#"{"text":"a","bold":true,"extra":[{"text":"b","bold":false,"italic":false}]}"#,
+            chat.serialize_str(VERSION_1_8).unwrap()
+        );
+        assert_eq!(None, Chat::text("c").bold(true).bold(None).style.bold);
+    }
+
+    #[cfg(all(feature = "uuid", feature = "snbt"))]
     #[test]
     pub fn standard_chat() {
+        use crate::{ClickEvent, EntityTooltip, HoverEvent, TranslationComponent};
+        use uuid::Uuid;
+
         let chat_orig = Chat::component(
             TranslationComponent::new("chat.type.text")
                 .argument(
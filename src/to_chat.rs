@@ -0,0 +1,107 @@
+use crate::{freeze::FrozenStr, Chat};
+
+/// Conversion of a value into a [`Chat`] component.
+///
+/// Anything implementing [`ToChat`] can be passed by reference to
+/// [`Chat::child()`]. With the `macros` feature enabled, this trait can also
+/// be derived for structs and enums.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, TextColor, ToChat};
+///
+/// struct Rank {
+///     name: String,
+/// }
+///
+/// impl ToChat for Rank {
+///     fn to_chat(&self) -> Chat {
+///         Chat::text(self.name.as_str()).color(TextColor::Red)
+///     }
+/// }
+///
+/// let rank = Rank { name: "Admin".to_string() };
+/// let chat = Chat::text("[").child(&rank).child(Chat::text("] Steve"));
+/// ```
+pub trait ToChat {
+    fn to_chat(&self) -> Chat;
+}
+
+impl<T: ToChat + ?Sized> From<&T> for Chat {
+    fn from(value: &T) -> Self {
+        value.to_chat()
+    }
+}
+
+impl<T: ToChat + ?Sized> ToChat for &T {
+    fn to_chat(&self) -> Chat {
+        (**self).to_chat()
+    }
+}
+
+impl<T: ToChat + ?Sized> ToChat for Box<T> {
+    fn to_chat(&self) -> Chat {
+        (**self).to_chat()
+    }
+}
+
+impl ToChat for Chat {
+    fn to_chat(&self) -> Chat {
+        self.clone()
+    }
+}
+
+impl ToChat for str {
+    fn to_chat(&self) -> Chat {
+        Chat::text(self)
+    }
+}
+
+impl ToChat for String {
+    fn to_chat(&self) -> Chat {
+        Chat::text(self.as_str())
+    }
+}
+
+impl ToChat for FrozenStr {
+    fn to_chat(&self) -> Chat {
+        Chat::text(self.clone())
+    }
+}
+
+macro_rules! to_chat_display {
+    ($($ty:ty),*) => {
+        $(
+            impl ToChat for $ty {
+                fn to_chat(&self) -> Chat {
+                    Chat::text(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+to_chat_display!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives() {
+        assert_eq!(Chat::text("42"), 42.to_chat());
+        assert_eq!(Chat::text("true"), true.to_chat());
+        assert_eq!(Chat::text("Steve"), "Steve".to_chat());
+    }
+
+    #[test]
+    fn child() {
+        let name = String::from("Steve");
+        assert_eq!(
+            Chat::text("Hi ").child(Chat::text("Steve")),
+            Chat::text("Hi ").child(&name)
+        );
+    }
+}
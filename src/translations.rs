@@ -0,0 +1,556 @@
+//! Loading the translations of language files, to translate
+//! [`TranslationComponent`](crate::TranslationComponent)s without a client.
+//!
+//! Translations are patterns with `%s` placeholders for the arguments in order,
+//! or `%1$s`, `%2$s`... for the arguments at a position. `%%` is a percent sign.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use crate::{Chat, ComponentKind, HoverEvent, Resolver, TranslationComponent};
+
+#[cfg(feature = "keys")]
+pub mod keys;
+
+/// Translation keys mapped to their patterns, loaded from language files.
+///
+/// # Example
+/// ```
+/// use mc_chat::translations::{PatternPart, Translations};
+///
+/// let translations = Translations::from_lang("# comment\nchat.type.text=<%s> %s\n");
+/// assert_eq!(Some("<%s> %s"), translations.get("chat.type.text"));
+/// assert_eq!(
+///     vec![
+///         PatternPart::Text("<"),
+///         PatternPart::Argument(0),
+///         PatternPart::Text("> "),
+///         PatternPart::Argument(1),
+///     ],
+///     translations.pattern("chat.type.text").unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Translations {
+    translations: HashMap<String, String>,
+}
+
+impl Translations {
+    /// Creates an empty lookup.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a JSON language file like `en_us.json`, used since 1.13.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            translations: serde_json::from_str(json)?,
+        })
+    }
+
+    /// Loads a legacy `.lang` language file with a `key=value` pair on every
+    /// line, used before 1.13. Empty lines and `#` comments are skipped.
+    pub fn from_lang(lang: &str) -> Self {
+        let translations = lang
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        Self { translations }
+    }
+
+    /// Adds or replaces a translation, e.g. to layer the translations
+    /// of a plugin over the vanilla ones.
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, pattern: V) {
+        self.translations.insert(key.into(), pattern.into());
+    }
+
+    /// Adds all translations of another lookup, replacing existing ones.
+    pub fn extend(&mut self, other: Translations) {
+        self.translations.extend(other.translations);
+    }
+
+    /// The pattern of a translation key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.translations.get(key).map(String::as_str)
+    }
+
+    /// The pattern of a translation key split into text and arguments.
+    pub fn pattern(&self, key: &str) -> Option<Vec<PatternPart<'_>>> {
+        self.get(key).map(parse_pattern)
+    }
+
+    /// The amount of arguments the pattern of a translation key refers to,
+    /// which is the highest position of its placeholders.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::translations::Translations;
+    ///
+    /// let translations = Translations::from_lang("swapped=%2$s, %1$s\nempty=Hello");
+    /// assert_eq!(Some(2), translations.argument_count("swapped"));
+    /// assert_eq!(Some(0), translations.argument_count("empty"));
+    /// assert_eq!(None, translations.argument_count("unknown"));
+    /// ```
+    pub fn argument_count(&self, key: &str) -> Option<usize> {
+        self.pattern(key).map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| match part {
+                    PatternPart::Argument(index) => Some(index + 1),
+                    PatternPart::Text(_) => None,
+                })
+                .max()
+                .unwrap_or(0)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.translations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.translations.is_empty()
+    }
+
+    /// Generates Rust constants of the translation keys, sorted, with their
+    /// pattern as documentation. This is how the `keys` module is made
+    /// with the `keys` feature.
+    ///
+    /// Keys that only differ in their separators or case, like `a.b` and
+    /// `a_b`, would get the same name. The first of them in sorted order
+    /// keeps it, the others get `_2`, `_3`... appended.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::translations::Translations;
+    ///
+    /// let translations = Translations::from_lang("death.attack.inFire=%1$s went up in flames");
+    /// assert_eq!(
+    ///     "/// `%1$s went up in flames`\npub const DEATH_ATTACK_IN_FIRE: &str = \"death.attack.inFire\";\n",
+    ///     translations.to_rust_constants()
+    /// );
+    ///
+    /// let translations = Translations::from_lang("a.b=`dot`\na_b=underscore");
+    /// assert_eq!(
+    ///     "/// `` `dot` ``\npub const A_B: &str = \"a.b\";\n/// `underscore`\npub const A_B_2: &str = \"a_b\";\n",
+    ///     translations.to_rust_constants()
+    /// );
+    /// ```
+    pub fn to_rust_constants(&self) -> String {
+        let mut keys = self.translations.keys().collect::<Vec<_>>();
+        keys.sort();
+        let mut names = HashSet::new();
+        let mut constants = String::new();
+        for key in keys {
+            let base = constant_name(key);
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !names.insert(name.clone()) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            let pattern = self.translations[key].replace('\n', "\\n");
+            constants.push_str(&format!(
+                "/// {}\npub const {}: &str = {:?};\n",
+                code_span(&pattern),
+                name,
+                key
+            ));
+        }
+        constants
+    }
+}
+
+/// The name of the constant of a translation key: `gameMode.changed`
+/// becomes `GAME_MODE_CHANGED`.
+fn constant_name(key: &str) -> String {
+    let mut name = String::with_capacity(key.len() + 4);
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        name.push('_');
+    }
+    let mut previous = '.';
+    for c in key.chars() {
+        if c.is_ascii_uppercase() && previous.is_ascii_lowercase() {
+            name.push('_');
+        }
+        name.push(if c.is_ascii_alphanumeric() {
+            c.to_ascii_uppercase()
+        } else {
+            '_'
+        });
+        previous = c;
+    }
+    name
+}
+
+/// Markdown inline code of some text, fenced by more backticks than any
+/// run of them in the text.
+fn code_span(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 {
+        // a space keeps a backtick at either end from joining the fence
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+/// A piece of a translation pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PatternPart<'a> {
+    Text(&'a str),
+    /// The index of an argument, starting at 0.
+    Argument(usize),
+}
+
+/// Splits a translation pattern into text and arguments.
+///
+/// Like the client, `%s` takes the next argument and `%1$s` the argument
+/// at that position, counting from 1. Invalid placeholders are kept as text.
+pub fn parse_pattern(pattern: &str) -> Vec<PatternPart<'_>> {
+    let mut parts = Vec::new();
+    let mut next_argument = 0;
+    let mut text_start = 0;
+    let mut rest = pattern;
+    while let Some(index) = rest.find('%') {
+        let offset = pattern.len() - rest.len();
+        let after = &rest[index + 1..];
+        let (part, length) = if after.starts_with('s') {
+            next_argument += 1;
+            (Some(PatternPart::Argument(next_argument - 1)), 2)
+        } else if after.starts_with('%') {
+            (Some(PatternPart::Text("%")), 2)
+        } else {
+            let digits = after.chars().take_while(char::is_ascii_digit).count();
+            match after[digits..].starts_with("$s") {
+                true if digits > 0 => match after[..digits].parse::<usize>() {
+                    Ok(position) if position > 0 => {
+                        (Some(PatternPart::Argument(position - 1)), digits + 3)
+                    }
+                    _ => (None, 1),
+                },
+                _ => (None, 1),
+            }
+        };
+        if let Some(part) = part {
+            if text_start < offset + index {
+                parts.push(PatternPart::Text(&pattern[text_start..offset + index]));
+            }
+            parts.push(part);
+            text_start = offset + index + length;
+        }
+        rest = &pattern[offset + index + length..];
+    }
+    if text_start < pattern.len() {
+        parts.push(PatternPart::Text(&pattern[text_start..]));
+    }
+    parts
+}
+
+/// The components a translation turns into with the arguments filled in.
+///
+/// Like the client, a pattern referring to a missing argument
+/// is used as text without filling in any arguments.
+pub(crate) fn fill_pattern(pattern: &str, arguments: &[Chat]) -> Vec<Chat> {
+    let parts = parse_pattern(pattern);
+    let valid = parts.iter().all(|part| match part {
+        PatternPart::Argument(index) => *index < arguments.len(),
+        PatternPart::Text(_) => true,
+    });
+    if !valid {
+        return vec![Chat::text(pattern)];
+    }
+    parts
+        .into_iter()
+        .map(|part| match part {
+            PatternPart::Text(text) => Chat::text(text),
+            PatternPart::Argument(index) => arguments[index].clone(),
+        })
+        .collect()
+}
+
+impl Resolver for Translations {
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.get(key).map(Cow::Borrowed)
+    }
+}
+
+impl TranslationComponent {
+    /// Checks that this translation has exactly as many arguments as the
+    /// pattern of its key refers to. Unknown keys are not checked.
+    pub fn validate(&self, translations: &Translations) -> Result<(), TranslationErr> {
+        match translations.argument_count(&self.key) {
+            Some(expected) if expected != self.with.len() => Err(TranslationErr::ArgumentCount {
+                key: self.key.to_string(),
+                expected,
+                found: self.with.len(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A translation that doesn't match the pattern of its key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TranslationErr {
+    ArgumentCount {
+        key: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for TranslationErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranslationErr::ArgumentCount {
+                key,
+                expected,
+                found,
+            } => write!(
+                f,
+                "translation {} has {} arguments, its pattern expects {}",
+                key, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TranslationErr {}
+
+impl Chat {
+    /// Checks every translation in this component, its children, arguments
+    /// and hover text with [`TranslationComponent::validate()`], returning
+    /// the first mismatch.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::translations::{TranslationErr, Translations};
+    /// use mc_chat::{Chat, TranslationComponent};
+    ///
+    /// let translations = Translations::from_lang("chat.type.text=<%s> %s");
+    /// let chat = Chat::component(
+    ///     TranslationComponent::new("chat.type.text").argument(Chat::text("Herobrine")),
+    /// );
+    /// assert_eq!(
+    ///     Err(TranslationErr::ArgumentCount {
+    ///         key: "chat.type.text".to_string(),
+    ///         expected: 2,
+    ///         found: 1,
+    ///     }),
+    ///     chat.validate_translations(&translations)
+    /// );
+    /// ```
+    pub fn validate_translations(&self, translations: &Translations) -> Result<(), TranslationErr> {
+        if let ComponentKind::Translation(ref translation) = self.kind {
+            translation.validate(translations)?;
+            for argument in translation.components() {
+                argument.validate_translations(translations)?;
+            }
+        }
+        if let Some(text) = self.style.hover_event.as_ref().and_then(HoverEvent::text) {
+            text.validate_translations(translations)?;
+        }
+        self.children
+            .iter()
+            .try_for_each(|child| child.validate_translations(translations))
+    }
+
+    /// Replaces every translation with known key by its pattern, with the
+    /// arguments filled in. The text takes the translation's place in the tree,
+    /// keeping its style and children. Translations with an unknown key are
+    /// left for the client to translate.
+    ///
+    /// Like the client, a pattern referring to a missing argument
+    /// is used as text without filling in any arguments.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::translations::Translations;
+    /// use mc_chat::{Chat, TextColor, TranslationComponent};
+    ///
+    /// let translations = Translations::from_lang("multiplayer.player.joined=%s joined the game");
+    /// let chat = Chat::component(
+    ///     TranslationComponent::new("multiplayer.player.joined").argument(Chat::text("Steve")),
+    /// )
+    /// .color(TextColor::Yellow);
+    /// assert_eq!(
+    ///     Chat::text("")
+    ///         .color(TextColor::Yellow)
+    ///         .child(Chat::text("Steve"))
+    ///         .child(Chat::text(" joined the game")),
+    ///     chat.resolve_translations(&translations)
+    /// );
+    /// ```
+    pub fn resolve_translations(&self, translations: &Translations) -> Chat {
+        self.resolve(translations)
+    }
+
+    /// The text of this component as a player using these translations sees
+    /// it, see [`Chat::to_plain()`]. Unknown keys show their fallback or the
+    /// key itself, like the client does.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::translations::Translations;
+    /// use mc_chat::{Chat, TranslationComponent};
+    ///
+    /// let translations = Translations::from_lang("chat.type.text=<%s> %s");
+    /// let chat = Chat::component(
+    ///     TranslationComponent::new("chat.type.text")
+    ///         .argument(Chat::text("Steve"))
+    ///         .argument(Chat::text("Press ").child(Chat::keybind("key.jump"))),
+    /// );
+    /// assert_eq!("<Steve> Press Space", chat.to_plain_localized(&translations));
+    /// ```
+    pub fn to_plain_localized(&self, translations: &Translations) -> String {
+        self.resolve_translations(translations).to_plain()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns() {
+        assert_eq!(
+            vec![
+                PatternPart::Argument(1),
+                PatternPart::Text(" gave "),
+                PatternPart::Argument(0),
+                PatternPart::Text(" "),
+                PatternPart::Text("%"),
+                PatternPart::Text(" %d"),
+            ],
+            parse_pattern("%2$s gave %1$s %% %d")
+        );
+        assert_eq!(vec![PatternPart::Text("100%")], parse_pattern("100%"));
+    }
+
+    #[test]
+    fn lang() {
+        let translations = Translations::from_lang("a=b=c\n\n#x=y\ninvalid\n");
+        assert_eq!(1, translations.len());
+        assert_eq!(Some("b=c"), translations.get("a"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let translations =
+            Translations::from_json(r#"{"multiplayer.player.joined": "%s joined the game"}"#)
+                .unwrap();
+        assert_eq!(
+            Some("%s joined the game"),
+            translations.get("multiplayer.player.joined")
+        );
+        assert!(Translations::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn resolve() {
+        let mut translations = Translations::new();
+        translations.insert("death.attack.player", "%1$s was slain by %2$s");
+        translations.insert("swapped", "%2$s, %1$s");
+        translations.insert("broken", "%3$s");
+        let chat = Chat::component(
+            TranslationComponent::new("swapped")
+                .argument(Chat::component(
+                    TranslationComponent::new("death.attack.player")
+                        .argument(Chat::text("Steve"))
+                        .argument(Chat::text("Alex")),
+                ))
+                .argument(Chat::component(
+                    TranslationComponent::new("unknown").argument(Chat::component(
+                        TranslationComponent::new("broken").argument(Chat::text("x")),
+                    )),
+                )),
+        );
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::component(
+                    TranslationComponent::new("unknown")
+                        .argument(Chat::text("").child(Chat::text("%3$s")))
+                ))
+                .child(Chat::text(", "))
+                .child(
+                    Chat::text("")
+                        .child(Chat::text("Steve"))
+                        .child(Chat::text(" was slain by "))
+                        .child(Chat::text("Alex"))
+                ),
+            chat.resolve_translations(&translations)
+        );
+    }
+
+    #[test]
+    fn constant_names() {
+        assert_eq!("CHAT_TYPE_TEXT", constant_name("chat.type.text"));
+        assert_eq!(
+            "DEATH_ATTACK_OUT_OF_WORLD",
+            constant_name("death.attack.outOfWorld")
+        );
+        assert_eq!("_2B2T_MOTD", constant_name("2b2t-motd"));
+    }
+
+    #[test]
+    fn colliding_constants() {
+        let translations = Translations::from_lang("a.b=1\na_b=2\naB=3\na.b.2=4");
+        let constants = translations.to_rust_constants();
+        for line in [
+            "pub const A_B: &str = \"a.b\";",
+            "pub const A_B_2: &str = \"a.b.2\";",
+            "pub const A_B_3: &str = \"aB\";",
+            "pub const A_B_4: &str = \"a_b\";",
+        ] {
+            assert!(constants.contains(line), "{} in {}", line, constants);
+        }
+        assert_eq!("`a`", code_span("a"));
+        assert_eq!("`` a`b ``", code_span("a`b"));
+        assert_eq!("``` `` ```", code_span("``"));
+    }
+
+    #[cfg(feature = "keys")]
+    #[test]
+    fn generated_keys() {
+        let generated = Translations::from_lang(include_str!("translations/keys.lang"));
+        let module = include_str!("translations/keys.rs");
+        let constants = &module[module.find("\n/// ").unwrap() + 1..];
+        assert_eq!(generated.to_rust_constants(), constants);
+    }
+
+    #[test]
+    fn validate() {
+        let translations = Translations::from_lang("chat.type.text=<%s> %s\nswapped=%2$s, %1$s");
+        let valid = Chat::component(
+            TranslationComponent::new("swapped")
+                .argument(Chat::text("a"))
+                .argument(Chat::text("b")),
+        )
+        .child(Chat::translate("unknown"));
+        assert_eq!(Ok(()), valid.validate_translations(&translations));
+
+        let hidden =
+            Chat::text("Hover me").hover(Some(HoverEvent::ShowText(Box::new(Chat::component(
+                TranslationComponent::new("swapped")
+                    .argument(Chat::text("a"))
+                    .argument(Chat::text("b"))
+                    .argument(Chat::text("c")),
+            )))));
+        assert_eq!(
+            Err(TranslationErr::ArgumentCount {
+                key: "swapped".to_string(),
+                expected: 2,
+                found: 3
+            }),
+            valid.child(hidden).validate_translations(&translations)
+        );
+    }
+}
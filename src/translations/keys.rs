@@ -0,0 +1,114 @@
+//! Constants of vanilla translation keys, with their English pattern.
+//!
+//! This is a curated subset of `en_us.json`, the keys that servers send the
+//! most, not every vanilla key. It's generated with
+//! [`Translations::to_rust_constants()`](super::Translations::to_rust_constants)
+//! from `keys.lang` next to this file, add a key there to add a constant.
+//! A typo in a constant is a compile error, while a typo in a key shows as
+//! the raw key in game.
+//!
+//! # Example
+//! ```
+//! use mc_chat::translations::keys;
+//! use mc_chat::Chat;
+//!
+//! let chat = Chat::key_with(keys::MULTIPLAYER_PLAYER_JOINED, vec!["Steve"]);
+//! assert_eq!(Chat::key_with("multiplayer.player.joined", vec!["Steve"]), chat);
+//! ```
+
+/// `No player was found`
+pub const ARGUMENT_ENTITY_NOTFOUND_PLAYER: &str = "argument.entity.notfound.player";
+/// `Click to Copy to Clipboard`
+pub const CHAT_COPY_CLICK: &str = "chat.copy.click";
+/// `Open in Browser`
+pub const CHAT_LINK_OPEN: &str = "chat.link.open";
+/// `[%s]`
+pub const CHAT_SQUARE_BRACKETS: &str = "chat.square_brackets";
+/// `[%s: %s]`
+pub const CHAT_TYPE_ADMIN: &str = "chat.type.admin";
+/// `%s has completed the challenge %s`
+pub const CHAT_TYPE_ADVANCEMENT_CHALLENGE: &str = "chat.type.advancement.challenge";
+/// `%s has reached the goal %s`
+pub const CHAT_TYPE_ADVANCEMENT_GOAL: &str = "chat.type.advancement.goal";
+/// `%s has made the advancement %s`
+pub const CHAT_TYPE_ADVANCEMENT_TASK: &str = "chat.type.advancement.task";
+/// `[%s] %s`
+pub const CHAT_TYPE_ANNOUNCEMENT: &str = "chat.type.announcement";
+/// `* %s %s`
+pub const CHAT_TYPE_EMOTE: &str = "chat.type.emote";
+/// `Message Team`
+pub const CHAT_TYPE_TEAM_HOVER: &str = "chat.type.team.hover";
+/// `-> %s <%s> %s`
+pub const CHAT_TYPE_TEAM_SENT: &str = "chat.type.team.sent";
+/// `%s <%s> %s`
+pub const CHAT_TYPE_TEAM_TEXT: &str = "chat.type.team.text";
+/// `<%s> %s`
+pub const CHAT_TYPE_TEXT: &str = "chat.type.text";
+/// `%s says %s`
+pub const CHAT_TYPE_TEXT_NARRATE: &str = "chat.type.text.narrate";
+/// `Unknown or incomplete command, see below for error`
+pub const COMMAND_UNKNOWN_COMMAND: &str = "command.unknown.command";
+/// `Unknown command or insufficient permissions`
+pub const COMMANDS_HELP_FAILED: &str = "commands.help.failed";
+/// `Kicked %s: %s`
+pub const COMMANDS_KICK_SUCCESS: &str = "commands.kick.success";
+/// `%s whispers to you: %s`
+pub const COMMANDS_MESSAGE_DISPLAY_INCOMING: &str = "commands.message.display.incoming";
+/// `You whisper to %s: %s`
+pub const COMMANDS_MESSAGE_DISPLAY_OUTGOING: &str = "commands.message.display.outgoing";
+/// `%1$s was shot by %2$s`
+pub const DEATH_ATTACK_ARROW: &str = "death.attack.arrow";
+/// `%1$s drowned`
+pub const DEATH_ATTACK_DROWN: &str = "death.attack.drown";
+/// `%1$s hit the ground too hard`
+pub const DEATH_ATTACK_FALL: &str = "death.attack.fall";
+/// `%1$s died`
+pub const DEATH_ATTACK_GENERIC: &str = "death.attack.generic";
+/// `%1$s went up in flames`
+pub const DEATH_ATTACK_IN_FIRE: &str = "death.attack.inFire";
+/// `%1$s tried to swim in lava`
+pub const DEATH_ATTACK_LAVA: &str = "death.attack.lava";
+/// `%1$s was slain by %2$s`
+pub const DEATH_ATTACK_MOB: &str = "death.attack.mob";
+/// `%1$s burned to death`
+pub const DEATH_ATTACK_ON_FIRE: &str = "death.attack.onFire";
+/// `%1$s fell out of the world`
+pub const DEATH_ATTACK_OUT_OF_WORLD: &str = "death.attack.outOfWorld";
+/// `%1$s was slain by %2$s`
+pub const DEATH_ATTACK_PLAYER: &str = "death.attack.player";
+/// `%1$s starved to death`
+pub const DEATH_ATTACK_STARVE: &str = "death.attack.starve";
+/// `%1$s fell from a high place`
+pub const DEATH_FELL_ACCIDENT_GENERIC: &str = "death.fell.accident.generic";
+/// `Adventure Mode`
+pub const GAME_MODE_ADVENTURE: &str = "gameMode.adventure";
+/// `Your game mode has been updated to %s`
+pub const GAME_MODE_CHANGED: &str = "gameMode.changed";
+/// `Creative Mode`
+pub const GAME_MODE_CREATIVE: &str = "gameMode.creative";
+/// `Spectator Mode`
+pub const GAME_MODE_SPECTATOR: &str = "gameMode.spectator";
+/// `Survival Mode`
+pub const GAME_MODE_SURVIVAL: &str = "gameMode.survival";
+/// `You are banned from this server`
+pub const MULTIPLAYER_DISCONNECT_BANNED: &str = "multiplayer.disconnect.banned";
+/// `You logged in from another location`
+pub const MULTIPLAYER_DISCONNECT_DUPLICATE_LOGIN: &str = "multiplayer.disconnect.duplicate_login";
+/// `You have been idle for too long!`
+pub const MULTIPLAYER_DISCONNECT_IDLING: &str = "multiplayer.disconnect.idling";
+/// `Kicked by an operator`
+pub const MULTIPLAYER_DISCONNECT_KICKED: &str = "multiplayer.disconnect.kicked";
+/// `The server is full!`
+pub const MULTIPLAYER_DISCONNECT_SERVER_FULL: &str = "multiplayer.disconnect.server_full";
+/// `Server closed`
+pub const MULTIPLAYER_DISCONNECT_SERVER_SHUTDOWN: &str = "multiplayer.disconnect.server_shutdown";
+/// `%s joined the game`
+pub const MULTIPLAYER_PLAYER_JOINED: &str = "multiplayer.player.joined";
+/// `%s (formerly known as %s) joined the game`
+pub const MULTIPLAYER_PLAYER_JOINED_RENAMED: &str = "multiplayer.player.joined.renamed";
+/// `%s left the game`
+pub const MULTIPLAYER_PLAYER_LEFT: &str = "multiplayer.player.left";
+/// `A player is required to run this command here`
+pub const PERMISSIONS_REQUIRES_PLAYER: &str = "permissions.requires.player";
+/// `%s/%s players sleeping`
+pub const SLEEP_PLAYERS_SLEEPING: &str = "sleep.players_sleeping";
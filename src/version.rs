@@ -0,0 +1,148 @@
+/// A version of the Minecraft protocol, which decides what a component
+/// is serialized with.
+///
+/// Any protocol number converts into a version, the named ones are the
+/// releases the format changed in. Versions compare by their number.
+///
+/// # Example
+/// ```
+/// use mc_chat::{Chat, ProtocolVersion, TextColor};
+///
+/// let version = ProtocolVersion::from(754);
+/// assert!(version > ProtocolVersion::V1_16);
+/// assert!(version.supports_hex_colors());
+///
+/// let chat = Chat::text("Hi").color(TextColor::from_rgb(0xff, 0x80, 0x00));
+/// assert_eq!(chat.serialize_str(version).unwrap(), chat.serialize_str(754).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolVersion(i32);
+
+// the named releases are declared with the `VERSION_*` constants, see
+// `version/named.rs`
+impl ProtocolVersion {
+    /// 1.7.6, the first version with insertions.
+    pub const V1_7_6: ProtocolVersion = ProtocolVersion(5);
+
+    /// The snapshot of 1.15 that added `copy_to_clipboard` click events.
+    const CLIPBOARD: ProtocolVersion = ProtocolVersion(558);
+    /// The snapshot of 1.16 that added hex colors and fonts.
+    const HEX_COLORS: ProtocolVersion = ProtocolVersion(713);
+
+    pub const fn new(protocol: i32) -> Self {
+        ProtocolVersion(protocol)
+    }
+
+    /// The protocol number, like `735` for 1.16.
+    pub const fn protocol(self) -> i32 {
+        self.0
+    }
+
+    pub const fn supports_insertion(self) -> bool {
+        self.0 >= Self::V1_7_6.0
+    }
+
+    /// Whether keybind components exist, `show_achievement` hover events
+    /// were removed at the same time.
+    pub const fn supports_keybinds(self) -> bool {
+        self.0 >= Self::V1_12.0
+    }
+
+    pub const fn supports_nbt_components(self) -> bool {
+        self.0 >= Self::V1_14.0
+    }
+
+    /// Whether nbt components can read a command storage.
+    pub const fn supports_storage(self) -> bool {
+        self.0 >= Self::V1_15.0
+    }
+
+    pub const fn supports_clipboard(self) -> bool {
+        self.0 >= Self::CLIPBOARD.0
+    }
+
+    pub const fn supports_hex_colors(self) -> bool {
+        self.0 >= Self::HEX_COLORS.0
+    }
+
+    pub const fn supports_fonts(self) -> bool {
+        self.0 >= Self::HEX_COLORS.0
+    }
+
+    /// Whether hover events have `contents` instead of a `value` component.
+    pub const fn uses_hover_contents(self) -> bool {
+        self.0 >= Self::V1_16.0
+    }
+
+    /// Whether selector and nbt components have a `separator`.
+    pub const fn supports_separators(self) -> bool {
+        self.0 >= Self::V1_17.0
+    }
+
+    pub const fn supports_translation_fallback(self) -> bool {
+        self.0 >= Self::V1_19_4.0
+    }
+
+    /// Whether score components can have a value, it's filled in by the
+    /// server since 1.20.3.
+    pub const fn supports_score_values(self) -> bool {
+        self.0 < Self::V1_20_3.0
+    }
+
+    /// Whether components can name their kind with `type`.
+    pub const fn supports_component_types(self) -> bool {
+        self.0 >= Self::V1_20_3.0
+    }
+
+    /// Whether events are written as `click_event` and `hover_event`, with
+    /// their values in typed fields like `url` or `uuid`.
+    pub const fn uses_snake_case_events(self) -> bool {
+        self.0 >= Self::V1_21_5.0
+    }
+
+    /// Whether `custom` and `show_dialog` click events exist.
+    pub const fn supports_dialogs(self) -> bool {
+        self.0 >= Self::V1_21_6.0
+    }
+}
+
+impl From<i32> for ProtocolVersion {
+    fn from(protocol: i32) -> Self {
+        ProtocolVersion(protocol)
+    }
+}
+
+impl From<ProtocolVersion> for i32 {
+    fn from(version: ProtocolVersion) -> Self {
+        version.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn named() {
+        assert_eq!(ProtocolVersion::V1_8, VERSION_1_8.into());
+        assert_eq!(VERSION_1_16, ProtocolVersion::V1_16.protocol());
+        assert!(ProtocolVersion::from(712) < ProtocolVersion::HEX_COLORS);
+        assert!(!ProtocolVersion::from(712).supports_hex_colors());
+        assert!(ProtocolVersion::V1_16.supports_fonts());
+        assert!(ProtocolVersion::V1_20.supports_score_values());
+        assert!(!ProtocolVersion::V1_20_3.supports_score_values());
+        for (version, protocol) in [
+            (ProtocolVersion::V1_9, VERSION_1_9),
+            (ProtocolVersion::V1_12, VERSION_1_12),
+            (ProtocolVersion::V1_13, VERSION_1_13),
+            (ProtocolVersion::V1_17, VERSION_1_17),
+            (ProtocolVersion::V1_19_4, VERSION_1_19_4),
+            (ProtocolVersion::V1_20_3, VERSION_1_20_3),
+            (ProtocolVersion::V1_20_5, VERSION_1_20_5),
+            (ProtocolVersion::V1_21, VERSION_1_21),
+            (ProtocolVersion::V1_21_6, VERSION_1_21_6),
+        ] {
+            assert_eq!(version.protocol(), protocol);
+        }
+    }
+}
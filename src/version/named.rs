@@ -0,0 +1,26 @@
+// The releases with a `VERSION_*` and a `ProtocolVersion` constant, as
+// `VERSION_* ProtocolVersion::V* "release" protocol`.
+//
+// This is the only list of them: `mc_chat` and the `mc_chat_core` macros
+// both define `protocol_versions!` and include this file, so a new version
+// only has to be added here.
+protocol_versions! {
+    VERSION_1_7 V1_7 "1.7" 4;
+    VERSION_1_8 V1_8 "1.8" 47;
+    VERSION_1_9 V1_9 "1.9" 107;
+    VERSION_1_12 V1_12 "1.12" 335;
+    VERSION_1_13 V1_13 "1.13" 393;
+    VERSION_1_14 V1_14 "1.14" 477;
+    VERSION_1_15 V1_15 "1.15" 573;
+    VERSION_1_16 V1_16 "1.16" 735;
+    VERSION_1_17 V1_17 "1.17" 755;
+    VERSION_1_19 V1_19 "1.19" 759;
+    VERSION_1_19_1 V1_19_1 "1.19.1" 760;
+    VERSION_1_19_4 V1_19_4 "1.19.4" 762;
+    VERSION_1_20 V1_20 "1.20" 763;
+    VERSION_1_20_3 V1_20_3 "1.20.3" 765;
+    VERSION_1_20_5 V1_20_5 "1.20.5" 766;
+    VERSION_1_21 V1_21 "1.21" 767;
+    VERSION_1_21_5 V1_21_5 "1.21.5" 770;
+    VERSION_1_21_6 V1_21_6 "1.21.6" 771;
+}
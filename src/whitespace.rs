@@ -0,0 +1,137 @@
+use crate::{Chat, ComponentKind, HoverEvent, TextComponent};
+
+impl Chat {
+    /// Collapses runs of spaces and tabs into a single space and removes them
+    /// from the start and end of the message, across component boundaries.
+    ///
+    /// The tree and the styles stay as they are: a collapsed run keeps its
+    /// first space, with the style of the component it's in. Components other
+    /// than text are never changed and count as text. The text of hover
+    /// events is normalized on its own.
+    ///
+    /// # Example
+    /// ```
+    /// use mc_chat::{Chat, TextColor};
+    ///
+    /// let mut chat = Chat::text("  Hello ")
+    ///     .child(Chat::text("  world!").color(TextColor::Green))
+    ///     .child(Chat::text(" \t "));
+    /// chat.normalize_whitespace();
+    /// assert_eq!(
+    ///     Chat::text("Hello ")
+    ///         .child(Chat::text("world!").color(TextColor::Green))
+    ///         .child(Chat::text("")),
+    ///     chat
+    /// );
+    /// ```
+    pub fn normalize_whitespace(&mut self) {
+        // the start of the message is treated like a space
+        collapse(self, &mut true);
+        trim_end(self);
+    }
+}
+
+fn collapse(chat: &mut Chat, after_space: &mut bool) {
+    if let Some(text) = chat
+        .style
+        .hover_event
+        .as_mut()
+        .and_then(HoverEvent::text_mut)
+    {
+        text.normalize_whitespace();
+    }
+    match chat.kind {
+        ComponentKind::Text(ref mut text) => {
+            let mut result = String::with_capacity(text.text.len());
+            for c in text.text.chars() {
+                if c == ' ' || c == '\t' {
+                    if !*after_space {
+                        result.push(' ');
+                        *after_space = true;
+                    }
+                } else {
+                    result.push(c);
+                    *after_space = false;
+                }
+            }
+            if result != *text.text {
+                *text = TextComponent::new(result);
+            }
+        }
+        _ => *after_space = false,
+    }
+    for child in chat.children.iter_mut() {
+        collapse(child, after_space);
+    }
+}
+
+/// Removes the trailing space of the message, returns whether
+/// this component ends with anything else.
+fn trim_end(chat: &mut Chat) -> bool {
+    for child in chat.children.iter_mut().rev() {
+        if trim_end(child) {
+            return true;
+        }
+    }
+    match chat.kind {
+        ComponentKind::Text(ref mut text) => {
+            let trimmed = text.text.trim_end_matches(' ');
+            let found = !trimmed.is_empty();
+            if trimmed.len() != text.text.len() {
+                *text = TextComponent::new(trimmed.to_string());
+            }
+            found
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapse_across_children() {
+        let mut chat = Chat::text("a ")
+            .child(Chat::text(" ").bold(true).child(Chat::text("  b")))
+            .child(Chat::translate("key"))
+            .child(Chat::text("  c  d "));
+        chat.normalize_whitespace();
+        assert_eq!(
+            Chat::text("a ")
+                .child(Chat::text("").bold(true).child(Chat::text("b")))
+                .child(Chat::translate("key"))
+                .child(Chat::text(" c d")),
+            chat
+        );
+    }
+
+    #[test]
+    fn trailing() {
+        let mut chat = Chat::text("  ")
+            .child(Chat::text("a  ").child(Chat::text(" ")))
+            .child(Chat::text("\t"));
+        chat.normalize_whitespace();
+        assert_eq!(
+            Chat::text("")
+                .child(Chat::text("a").child(Chat::text("")))
+                .child(Chat::text("")),
+            chat
+        );
+
+        let mut chat = Chat::text("   ");
+        chat.normalize_whitespace();
+        assert_eq!(Chat::text(""), chat);
+    }
+
+    #[test]
+    fn hover() {
+        let mut chat =
+            Chat::text("a").hover(Some(HoverEvent::ShowText(Box::new(Chat::text(" tip ")))));
+        chat.normalize_whitespace();
+        assert_eq!(
+            Chat::text("a").hover(Some(HoverEvent::ShowText(Box::new(Chat::text("tip"))))),
+            chat
+        );
+    }
+}
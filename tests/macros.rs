@@ -1,7 +1,11 @@
-#![cfg(feature = "macros")]
+#![cfg(all(feature = "macros", feature = "serde"))]
 use std::assert_eq;
 
-use mc_chat::{chat, Chat, TextColor};
+use mc_chat::legacy::{parse_format, FormatPart};
+use mc_chat::{
+    book, chat, chat_static, Book, Chat, TextColor, ToChat, TranslationComponent, VERSION_1_16,
+    VERSION_1_8,
+};
 
 #[test]
 fn plaintext() {
@@ -54,3 +58,355 @@ fn custom_delimiter() {
     let chat = chat!("§@" => "§@0Hello §§@fworld!!");
     assert_eq!(orig_chat, chat);
 }
+
+#[test]
+fn hex_color() {
+    let orig_chat = Chat::text("")
+        .child(Chat::text("Hello ").color(TextColor::from_rgb(0xff, 0x88, 0x00)))
+        .child(Chat::text("world!!").color(TextColor::from_rgb(0x00, 0xff, 0x00)));
+    let chat = chat!("§#ff8800Hello §x§0§0§F§F§0§0world!!");
+    assert_eq!(orig_chat, chat);
+}
+
+#[test]
+fn translation() {
+    let name = Chat::text("Herobrine");
+    let orig_chat = Chat::component(
+        TranslationComponent::new("chat.type.text")
+            .argument(Chat::text("Herobrine"))
+            .argument(Chat::text("I don't exist").color(TextColor::Gray)),
+    )
+    .color(TextColor::Yellow);
+    let chat = chat!(tr "§echat.type.text", name, "§7I don't exist");
+    assert_eq!(orig_chat, chat);
+}
+
+#[test]
+fn inline_placeholders() {
+    let variable = String::from("My Variableee");
+    let orig_chat = Chat::text("Var: ").color(TextColor::Blue).child(
+        Chat::text(variable.clone())
+            .color(TextColor::Purple)
+            .underlined(true)
+            .child(Chat::text("!!").color(TextColor::Yellow)),
+    );
+    assert_eq!(orig_chat, chat!("§9Var: §5§n{}§e!!", variable.clone()));
+    assert_eq!(orig_chat, chat!("§9Var: §5§n{variable}§e!!"));
+}
+
+#[test]
+fn gradient() {
+    let orig_chat = Chat::text("")
+        .child(Chat::text("a").color(TextColor::from_rgb(0xff, 0x00, 0x00)))
+        .child(Chat::text("b").color(TextColor::from_rgb(0x80, 0x00, 0x80)))
+        .child(Chat::text("c").color(TextColor::from_rgb(0x00, 0x00, 0xff)));
+    let chat = chat!("§<#ff0000:#0000ff>abc§>");
+    assert_eq!(orig_chat, chat);
+}
+
+#[test]
+fn static_serialization() {
+    const GREETING: &str = chat_static!(VERSION_1_16, "§6Hello §#ff8800world!!");
+    const GREETING_OLD: &str = chat_static!(mc_chat::VERSION_1_8, "§6Hello §#ff8800world!!");
+    let chat = chat!("§6Hello §#ff8800world!!");
+    assert_eq!(chat.serialize_str(VERSION_1_16).unwrap(), GREETING);
+    assert_eq!(chat.serialize_str(VERSION_1_8).unwrap(), GREETING_OLD);
+
+    let translation = chat!(tr "§emultiplayer.player.joined", "§bSteve");
+    assert_eq!(
+        translation.serialize_str(VERSION_1_8).unwrap(),
+        chat_static!(47, tr "§emultiplayer.player.joined", "§bSteve")
+    );
+}
+
+#[test]
+fn static_hex_colors() {
+    for (runtime, compiled) in [
+        (
+            chat!("§#FF8800Hi"),
+            chat_static!(VERSION_1_16, "§#FF8800Hi"),
+        ),
+        (
+            chat!("§x§A§b§C§d§E§fHi"),
+            chat_static!(VERSION_1_16, "§x§A§b§C§d§E§fHi"),
+        ),
+        (
+            chat!("§<#FF0000:#0000FF>abc§>"),
+            chat_static!(VERSION_1_16, "§<#FF0000:#0000FF>abc§>"),
+        ),
+    ] {
+        assert_eq!(runtime.serialize_str(VERSION_1_16).unwrap(), compiled);
+    }
+    assert_eq!(
+        r##"{"text":"Hi","color":"#ff8800"}"##,
+        chat_static!(VERSION_1_16, "§#FF8800Hi")
+    );
+}
+
+/// `chat!` and `chat_static!` build their tree at compile time, while
+/// `legacy::parse_format()` has its own copy of the algorithm for runtime
+/// formats. Both have to agree on every literal, and `chat_static!`'s own
+/// serializer has to match the runtime one at every version the format
+/// changed in.
+macro_rules! assert_legacy_parity {
+    ($literal:literal) => {{
+        let runtime = parse_format("§", vec![FormatPart::from($literal)]).unwrap();
+        assert_eq!(chat!($literal), runtime, "{:?}", $literal);
+        assert_static_parity!(
+            runtime,
+            $literal,
+            mc_chat::VERSION_1_7,
+            5,
+            mc_chat::VERSION_1_8,
+            mc_chat::VERSION_1_9,
+            mc_chat::VERSION_1_12,
+            mc_chat::VERSION_1_13,
+            mc_chat::VERSION_1_14,
+            558,
+            mc_chat::VERSION_1_15,
+            712,
+            713,
+            mc_chat::VERSION_1_16,
+            mc_chat::VERSION_1_17,
+            mc_chat::VERSION_1_19,
+            mc_chat::VERSION_1_19_1,
+            mc_chat::VERSION_1_19_4,
+            mc_chat::VERSION_1_20,
+            mc_chat::VERSION_1_20_3,
+            mc_chat::VERSION_1_20_5,
+            mc_chat::VERSION_1_21,
+            mc_chat::VERSION_1_21_5,
+            mc_chat::VERSION_1_21_6,
+        );
+    }};
+}
+
+macro_rules! assert_static_parity {
+    ($runtime:expr, $literal:literal, $($version:expr,)*) => {
+        $(
+            assert_eq!(
+                $runtime.serialize_str($version).unwrap(),
+                chat_static!($version, $literal),
+                "{:?} at {}",
+                $literal,
+                $version
+            );
+        )*
+    };
+}
+
+#[test]
+fn legacy_parity() {
+    assert_legacy_parity!("Hello world!!");
+    assert_legacy_parity!("§6Hello world!!");
+    assert_legacy_parity!("§2§3§4§l§kTesting §l§l§2overly §7much");
+    assert_legacy_parity!("§cRed §rplain §lbold §othen italic");
+    assert_legacy_parity!("§l§§l and §§");
+    assert_legacy_parity!("§#ff8800Hello §x§0§0§F§F§0§0world!!");
+    assert_legacy_parity!("§#FF8800Hi §a§lthere");
+    assert_legacy_parity!("§<#ff0000:#0000ff>abc§>");
+    assert_legacy_parity!("§lBold §<#ff0000:#00ff00:#0000ff>gradient§> after");
+    assert_legacy_parity!("§a§nunder§r §m§kstruck");
+
+    assert_eq!(
+        chat!("§@" => "§@0Hello §§@fworld!!"),
+        parse_format("§@", vec![FormatPart::from("§@0Hello §§@fworld!!")]).unwrap()
+    );
+
+    let variable = String::from("My Variableee");
+    assert_eq!(
+        chat!("§9Var: §5§n", variable.clone(), "§e!!"),
+        parse_format(
+            "§",
+            vec![
+                "§9Var: §5§n".into(),
+                Chat::text(variable.clone()).into(),
+                "§e!!".into()
+            ]
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        chat!("§9Var: §5§n{}§e!!", variable.clone()),
+        parse_format(
+            "§",
+            vec!["§9Var: §5§n{}§e!!".into(), Chat::text(variable).into()]
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn target_version() {
+    let orig_chat = Chat::text("Hello").color(TextColor::from_rgb(0xff, 0x00, 0x00));
+    assert_eq!(orig_chat, chat!(target = VERSION_1_16; "§#ff0000Hello"));
+    assert_eq!(orig_chat, chat!(target = 735; "§@" => "§@#ff0000Hello"));
+    assert_eq!(
+        Chat::text("Hello").color(TextColor::Red),
+        chat!(target = VERSION_1_8; "§cHello")
+    );
+}
+
+#[test]
+fn styled_variable() {
+    let player_name = "Steve";
+    let rank_color = TextColor::Red;
+    let orig_chat = Chat::text("").child(Chat::text("Hello ")).child(
+        Chat::text("")
+            .child(Chat::text("Steve").color(TextColor::Red).bold(true))
+            .child(Chat::text("!")),
+    );
+    assert_eq!(
+        orig_chat,
+        chat!("Hello ", {player_name}: color = rank_color.clone(), bold, "!")
+    );
+
+    let orig_chat = Chat::text("")
+        .child(Chat::text("a"))
+        .child(Chat::text("b"))
+        .child(
+            Chat::text("")
+                .child(Chat::text("Steve").insertion(Some("Steve")))
+                .child(Chat::text("c")),
+        );
+    assert_eq!(
+        orig_chat,
+        chat!("a", "b", player_name: insertion = player_name, "c")
+    );
+}
+
+#[test]
+fn escaped_delimiter() {
+    assert_eq!(
+        Chat::text("100§ sure").color(TextColor::Yellow),
+        chat!("§e100§§ sure")
+    );
+    assert_eq!(Chat::text("a & b"), chat!("&" => "a && b"));
+    assert_eq!(r#"{"text":"§l","bold":true}"#, chat_static!(47, "§l§§l"));
+}
+
+#[derive(ToChat)]
+#[chat(color = "gray", format = "{x}, {y}, {z}")]
+struct Coordinates {
+    #[chat(format = "{:.1}")]
+    x: f64,
+    y: i32,
+    #[chat(color = "gold")]
+    z: i32,
+}
+
+#[derive(ToChat)]
+enum Rank {
+    #[chat(text = "Admin", color = "red", bold)]
+    Admin,
+    Member,
+    #[chat(text = "Custom ", separator = "")]
+    Custom(String, #[chat(italic)] String),
+}
+
+#[test]
+fn derive_to_chat() {
+    let coordinates = Coordinates {
+        x: 1.25,
+        y: 64,
+        z: -3,
+    };
+    assert_eq!(
+        Chat::text("")
+            .color(TextColor::Gray)
+            .child(Chat::text("1.2"))
+            .child(Chat::text(", "))
+            .child(Chat::text("64"))
+            .child(Chat::text(", "))
+            .child(Chat::text("-3").color(TextColor::Gold)),
+        coordinates.to_chat()
+    );
+
+    assert_eq!(
+        Chat::text("[")
+            .child(Chat::text("Admin").color(TextColor::Red).bold(true))
+            .child(Chat::text("]")),
+        Chat::text("[").child(&Rank::Admin).child(Chat::text("]"))
+    );
+    assert_eq!(Chat::text("Member"), Rank::Member.to_chat());
+    assert_eq!(
+        Chat::text("Custom ")
+            .child(Chat::text("VIP"))
+            .child(Chat::text("+").italic(true)),
+        Rank::Custom("VIP".into(), "+".into()).to_chat()
+    );
+}
+
+#[test]
+fn book() {
+    let player = "Steve";
+    let orig_book = Book::new("Adventures", "Steve")
+        .page(
+            Chat::text("")
+                .child(Chat::text("Hello ").bold(true))
+                .child(Chat::text("Steve")),
+        )
+        .page(Chat::text("The end").italic(true));
+    assert_eq!(
+        orig_book,
+        book! {
+            title: "Adventures",
+            author: "Steve",
+            ["§lHello §r", player],
+            ["§oThe end"],
+        }
+    );
+    assert_eq!(
+        orig_book,
+        book!("&" => title: "Adventures", author: "Steve", ["&lHello &r", player], ["&oThe end"])
+    );
+}
+
+#[test]
+fn runtime_parser() {
+    use mc_chat::legacy::parse_format;
+
+    let name = "Steve";
+    assert_eq!(
+        chat!("§2§3§4§l§kTesting §l§l§2overly §7much"),
+        parse_format("§", vec!["§2§3§4§l§kTesting §l§l§2overly §7much".into()]).unwrap()
+    );
+    assert_eq!(
+        chat!("a§lb§#ff8800c §<#000000:#ffffff>grad§> §§", name, "§r§9end"),
+        parse_format(
+            "§",
+            vec![
+                "a§lb§#ff8800c §<#000000:#ffffff>grad§> §§".into(),
+                Chat::text(name).into(),
+                "§r§9end".into()
+            ]
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn chat_values() {
+    let rank = Chat::text("[Admin]").color(TextColor::Red);
+    assert_eq!(
+        Chat::text("Hi ").color(TextColor::Yellow).child(
+            Chat::text("[Admin]")
+                .color(TextColor::Red)
+                .child(Chat::text(" Steve"))
+        ),
+        chat!("§eHi ", @rank.clone(), " Steve")
+    );
+    assert_eq!(
+        Chat::text("")
+            .child(Chat::text("Hi ").color(TextColor::Yellow))
+            .child(Chat::text("[Admin]").color(TextColor::Red))
+            .child(Chat::text(" Steve")),
+        chat!("§eHi ", @rank.clone(): sibling, " Steve")
+    );
+    assert_eq!(
+        Chat::text("")
+            .child(Chat::text("Hi "))
+            .child(Chat::text("").child(Chat::text("42").bold(true))),
+        chat!("Hi ", @&42: bold)
+    );
+}